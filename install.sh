@@ -56,25 +56,30 @@ askai() {
         return $?
     fi
 
-    # 일반 명령어 생성 및 실행
-    local cmd=$("$ASKAI_BIN" --quiet --yes "$@" 2>/dev/null)
-
-    if [ $? -eq 0 ] && [ -n "$cmd" ]; then
-        # 생성된 명령어를 stderr에 표시 (선택적)
-        echo "🤖 실행: $cmd" >&2
-
-        # 명령어 실행
-        eval "$cmd"
-    else
-        # 에러가 발생한 경우 전체 출력 보여주기
-        "$ASKAI_BIN" "$@"
+    # 명령어 생성 후 askai가 직접 실행 (출력은 실시간으로, exit code는 그대로)
+    # cd, export처럼 현재 쉘을 바꾸는 명령어만 파일로 받아 eval
+    local eval_file=$(mktemp)
+    "$ASKAI_BIN" --exec --eval-file "$eval_file" "$@"
+    local status=$?
+
+    if [ -s "$eval_file" ]; then
+        eval "$(cat "$eval_file")"
+        status=$?
     fi
+    rm -f "$eval_file"
+    return $status
 }
 
 # PATH에 추가 (필요한 경우)
 export PATH="$HOME/.local/bin:$PATH"
 '
 
+# askai fix가 읽는 실패한 명령어 기록 hook (bash, zsh)
+if [ "$SHELL_TYPE" != "unknown" ]; then
+    SHELL_FUNCTION="$SHELL_FUNCTION"'eval "$("$HOME/.local/bin/askai-bin" hook '"$SHELL_TYPE"')"
+'
+fi
+
 # 6. Shell RC 파일 업데이트
 echo "📝 $SHELL_RC 파일을 업데이트합니다..."
 
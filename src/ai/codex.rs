@@ -1,5 +1,5 @@
 use crate::error::{AskAiError, Result};
-use crate::ai::{AiProvider, factory::ProviderFactory, response_processor::ResponseProcessor, prompt_template::PromptTemplate};
+use crate::ai::{AiProvider, CommandFailure, explanation::CommandExplanation, factory::ProviderFactory, response_processor::{GeneratedCommand, PlannedTask, ResponseProcessor}, prompt_template::PromptTemplate};
 use async_trait::async_trait;
 use tokio::process::Command;
 
@@ -9,6 +9,25 @@ impl CodexProvider {
     pub fn new() -> Self {
         Self
     }
+
+    /// CLI에 프롬프트를 넘겨 실행하고 stdout을 반환
+    async fn run(&self, full_prompt: &str) -> Result<String> {
+        // Codex CLI 호출
+        let output = Command::new(self.cli_command())
+            .arg(full_prompt)
+            .output()
+            .await
+            .map_err(|e| AskAiError::AiCliError(e.to_string()))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(AskAiError::AiCliError(error.to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .to_string())
+    }
 }
 
 #[async_trait]
@@ -31,32 +50,60 @@ impl AiProvider for CodexProvider {
     }
 
     async fn generate_command(&self, prompt: &str, context: &str) -> Result<String> {
+        Ok(self.generate_structured(prompt, context).await?.command)
+    }
+
+    async fn generate_structured(&self, prompt: &str, context: &str) -> Result<GeneratedCommand> {
         // Codex CLI가 설치되어 있는지 확인 (캐싱됨)
         self.check_installation().await?;
 
         // 공통 프롬프트 템플릿 사용 (Codex 전용 규칙 포함)
         let full_prompt = PromptTemplate::for_codex(prompt, context);
 
-        // Codex CLI 호출
-        let output = Command::new(self.cli_command())
-            .arg(&full_prompt)
-            .output()
-            .await
-            .map_err(|e| AskAiError::AiCliError(e.to_string()))?;
+        let raw_output = self.run(&full_prompt).await?;
 
-        if !output.status.success() {
-            let error = String::from_utf8_lossy(&output.stderr);
-            return Err(AskAiError::AiCliError(error.to_string()));
-        }
+        // 후처리: JSON 응답 계약 파싱, 실패 시 휴리스틱으로 명령어만 추출 (공통 ResponseProcessor 사용)
+        ResponseProcessor::process_structured(&raw_output)
+    }
 
-        let raw_output = String::from_utf8_lossy(&output.stdout)
-            .trim()
-            .to_string();
+    async fn generate_script(&self, prompt: &str, context: &str) -> Result<String> {
+        self.check_installation().await?;
+
+        let full_prompt = PromptTemplate::script_prompt(prompt, context);
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 여러 줄을 보존하는 스크립트 전용 후처리
+        ResponseProcessor::process_script(&raw_output)
+    }
+
+    async fn repair_command(&self, prompt: &str, failure: &CommandFailure, context: &str) -> Result<GeneratedCommand> {
+        self.check_installation().await?;
+
+        let full_prompt = PromptTemplate::repair_prompt(prompt, failure, context);
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 생성과 같은 JSON 응답 계약
+        ResponseProcessor::process_structured(&raw_output)
+    }
+
+    async fn plan_tasks(&self, prompt: &str, context: &str) -> Result<Vec<PlannedTask>> {
+        self.check_installation().await?;
+
+        let full_prompt = PromptTemplate::plan_prompt(prompt, context);
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 작업 목록 JSON 계약 (휴리스틱 fallback 없음)
+        ResponseProcessor::process_plan(&raw_output)
+    }
+
+    async fn explain_command(&self, command: &str, context: &str) -> Result<CommandExplanation> {
+        self.check_installation().await?;
 
-        // 후처리: AI 응답을 정제하여 실제 명령어만 추출 (공통 ResponseProcessor 사용)
-        let command = ResponseProcessor::process(&raw_output)?;
+        let full_prompt = PromptTemplate::explain_prompt(command, context);
+        let raw_output = self.run(&full_prompt).await?;
 
-        Ok(command)
+        // 설명문은 ResponseProcessor가 거부하므로 전용 파서 사용
+        CommandExplanation::parse(&raw_output)
     }
 }
 
@@ -0,0 +1,435 @@
+use crate::ai::AiProvider;
+use futures::future::join_all;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// 플래그 순서를 바꾸면 의미가 달라지는 프로그램 (정규화 시 순서 유지)
+///
+/// `find . -delete -name x`처럼 표현식 순서가 동작을 결정합니다.
+const ORDER_SENSITIVE_PROGRAMS: &[&str] = &["find", "java", "gcc", "clang", "ffmpeg"];
+
+/// 값을 받는 짧은 플래그 (프로그램별)
+///
+/// 여기 있는 프로그램은 나머지 짧은 플래그를 값 없는 플래그로 보고 분리/정렬합니다.
+/// 여기 없는 프로그램은 뒤에 단어가 오는 플래그를 값을 받을 수 있는 것으로 보고 제자리에 둡니다.
+const SHORT_VALUE_FLAGS: &[(&str, &str)] = &[
+    ("ls", "ITw"),
+    ("rm", ""),
+    ("cp", "St"),
+    ("mv", "St"),
+    ("ln", "St"),
+    ("mkdir", "m"),
+    ("touch", "dr"),
+    ("chmod", ""),
+    ("chown", ""),
+    ("cat", ""),
+    ("wc", ""),
+    ("grep", "ABCDdefm"),
+    ("cut", "bcdf"),
+    ("sort", "kotST"),
+    ("uniq", "fsw"),
+    ("head", "cn"),
+    ("tail", "cns"),
+    ("du", "BdXt"),
+    ("df", "Btx"),
+    ("tar", "bCfgIKLTVX"),
+    ("ps", "CGgOoptUu"),
+    ("kill", "ns"),
+    ("xargs", "adEIjLnPs"),
+    ("awk", "Ffv"),
+    ("sed", "ef"),
+    ("unzip", "dx"),
+    ("ssh", "bcDEeFIiJLlmOopQRSWw"),
+];
+
+/// 명령어를 구분하는 셸 연산자
+const SEPARATORS: &[&str] = &["|", "||", "&&", ";", "&"];
+
+/// 개별 provider의 응답
+#[derive(Debug, Clone)]
+pub struct ProviderAnswer {
+    /// provider 이름
+    pub provider: String,
+    /// 생성된 명령어 (실패 시 None)
+    pub command: Option<String>,
+    /// 에러 메시지 (성공 시 None)
+    pub error: Option<String>,
+}
+
+/// 정규화 결과가 같은 응답들의 묶음
+#[derive(Debug, Clone)]
+pub struct ConsensusGroup {
+    /// 정규화된 명령어 (비교 키)
+    pub normalized: String,
+    /// 대표 명령어 (그룹에서 처음 나온 원본)
+    pub command: String,
+    /// 이 명령어를 제안한 provider 목록
+    pub providers: Vec<String>,
+}
+
+/// 합의 결과
+#[derive(Debug, Clone)]
+pub struct ConsensusResult {
+    /// provider별 응답 (요청 순서 유지)
+    pub answers: Vec<ProviderAnswer>,
+    /// 동의 그룹 (지지하는 provider 수 내림차순)
+    pub groups: Vec<ConsensusGroup>,
+}
+
+impl ConsensusResult {
+    /// provider 응답들을 정규화하여 그룹으로 묶음
+    pub fn from_answers(answers: Vec<ProviderAnswer>) -> Self {
+        let mut groups: Vec<ConsensusGroup> = Vec::new();
+
+        for answer in &answers {
+            let Some(command) = &answer.command else { continue };
+            let normalized = normalize_command(command);
+
+            match groups.iter_mut().find(|g| g.normalized == normalized) {
+                Some(group) => group.providers.push(answer.provider.clone()),
+                None => groups.push(ConsensusGroup {
+                    normalized,
+                    command: command.clone(),
+                    providers: vec![answer.provider.clone()],
+                }),
+            }
+        }
+
+        // 지지가 많은 그룹이 먼저 (동률이면 먼저 나온 순서 유지)
+        groups.sort_by_key(|g| std::cmp::Reverse(g.providers.len()));
+
+        Self { answers, groups }
+    }
+
+    /// 응답에 성공한 provider 수
+    pub fn answered_count(&self) -> usize {
+        self.answers.iter().filter(|a| a.command.is_some()).count()
+    }
+
+    /// 두 개 이상의 provider가 응답했고 모두 같은 명령어를 제안했는지 (자동 승인 조건)
+    pub fn is_unanimous(&self) -> bool {
+        self.groups.len() == 1 && self.answered_count() >= 2
+    }
+}
+
+/// 같은 프롬프트를 여러 provider에 동시에 보내고 결과를 비교
+///
+/// # Arguments
+/// * `providers` - 비교할 (provider, 타임아웃) 목록
+pub async fn run_consensus(
+    providers: &[(Arc<dyn AiProvider>, Duration)],
+    prompt: &str,
+    context: &str,
+) -> ConsensusResult {
+    let futures = providers.iter().map(|(provider, timeout)| async move {
+        let result = tokio::time::timeout(*timeout, provider.generate_command(prompt, context)).await;
+
+        match result {
+            Ok(Ok(command)) => ProviderAnswer {
+                provider: provider.name().to_string(),
+                command: Some(command),
+                error: None,
+            },
+            Ok(Err(e)) => ProviderAnswer {
+                provider: provider.name().to_string(),
+                command: None,
+                error: Some(e.to_string()),
+            },
+            Err(_) => ProviderAnswer {
+                provider: provider.name().to_string(),
+                command: None,
+                error: Some(format!("no response within {}s", timeout.as_secs())),
+            },
+        }
+    });
+
+    ConsensusResult::from_answers(join_all(futures).await)
+}
+
+/// 비교용으로 명령어를 정규화
+///
+/// - 공백과 따옴표 차이 제거 (`'*.txt'` == `"*.txt"` == `*.txt`)
+/// - 값을 받지 않는 플래그만 분리 후 정렬 (`ls -la /tmp` == `ls -a -l /tmp`)
+/// - 값을 받을 수 있는 플래그와 위치 인자의 순서는 유지 (`cp a b` != `cp b a`, `cut -d , -f 1` != `cut -d 1 -f ,`)
+///
+/// 완전한 셸 파서가 아닌 휴리스틱이므로 비교 용도로만 사용합니다.
+pub fn normalize_command(command: &str) -> String {
+    let tokens = tokenize(command);
+    let mut normalized: Vec<String> = Vec::new();
+    let mut segment: Vec<String> = Vec::new();
+
+    for token in tokens {
+        if SEPARATORS.contains(&token.as_str()) {
+            normalized.extend(normalize_segment(&segment));
+            normalized.push(token);
+            segment.clear();
+        } else {
+            segment.push(token);
+        }
+    }
+    normalized.extend(normalize_segment(&segment));
+
+    normalized.join(" ")
+}
+
+/// 단일 명령어(파이프/리스트 구분자 사이)의 플래그 정렬
+///
+/// 값을 받는 플래그는 값과 함께 제자리에 둡니다 (`tar -C x -xf y`와 `tar -xf x -C y`를 같게 보지 않음).
+/// `SHORT_VALUE_FLAGS`에 없는 프로그램이나 긴 플래그는 뒤에 단어가 오면 그 단어가 값인지 알 수 없으므로 같이 둡니다.
+fn normalize_segment(segment: &[String]) -> Vec<String> {
+    let Some((program, args)) = segment.split_first() else {
+        return Vec::new();
+    };
+
+    let program_name = program.rsplit('/').next().unwrap_or(program);
+    if ORDER_SENSITIVE_PROGRAMS.contains(&program_name) {
+        return segment.to_vec();
+    }
+
+    let value_letters = SHORT_VALUE_FLAGS
+        .iter()
+        .find(|(name, _)| *name == program_name)
+        .map(|(_, letters)| *letters);
+
+    let mut flags: Vec<String> = Vec::new();
+    let mut positionals: Vec<String> = Vec::new();
+    let mut end_of_options = false;
+    let mut index = 0;
+
+    while index < args.len() {
+        let arg = &args[index];
+        let next = args.get(index + 1);
+        let may_take_value = !arg.contains('=') && next.is_some_and(|next| !is_flag(next));
+        index += 1;
+
+        if end_of_options || !is_flag(arg) {
+            positionals.push(arg.clone());
+        } else if arg == "--" {
+            end_of_options = true;
+            positionals.push(arg.clone());
+        } else if let (Some(value_letters), false) = (value_letters, arg.starts_with("--")) {
+            // 값을 받는 글자가 나오면 그 뒤는 값 (`-d,`, `-xf archive.tar`)
+            let letters = &arg[1..];
+            match letters.char_indices().find(|(_, c)| value_letters.contains(*c)) {
+                None => flags.extend(letters.chars().map(|c| format!("-{}", c))),
+                Some((at, letter)) => {
+                    flags.extend(letters[..at].chars().map(|c| format!("-{}", c)));
+                    positionals.push(format!("-{}", &letters[at..]));
+                    if at + letter.len_utf8() == letters.len() {
+                        if let Some(value) = next {
+                            positionals.push(value.clone());
+                            index += 1;
+                        }
+                    }
+                }
+            }
+        } else if may_take_value {
+            // 값일 수도 있는 다음 단어와 함께 순서 유지
+            positionals.push(arg.clone());
+        } else if !arg.starts_with("--") && arg.len() > 2 && arg[1..].chars().all(|c| c.is_ascii_alphabetic()) {
+            // -la → -l -a
+            flags.extend(arg[1..].chars().map(|c| format!("-{}", c)));
+        } else {
+            flags.push(arg.clone());
+        }
+    }
+
+    flags.sort();
+    flags.dedup();
+
+    let mut result = vec![program.clone()];
+    result.extend(flags);
+    result.extend(positionals);
+    result
+}
+
+/// 플래그처럼 보이는 토큰인지 (`-`, 음수는 제외)
+fn is_flag(token: &str) -> bool {
+    token.starts_with('-')
+        && token.len() > 1
+        && !token[1..].chars().next().is_some_and(|c| c.is_ascii_digit())
+}
+
+/// 따옴표를 제거하며 공백/연산자 단위로 토큰 분리
+fn tokenize(command: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut has_token = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                has_token = true;
+                for inner in chars.by_ref() {
+                    if inner == '\'' {
+                        break;
+                    }
+                    current.push(inner);
+                }
+            }
+            '"' => {
+                has_token = true;
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '"' => break,
+                        '\\' => {
+                            if let Some(escaped) = chars.next() {
+                                current.push(escaped);
+                            }
+                        }
+                        _ => current.push(inner),
+                    }
+                }
+            }
+            '\\' => {
+                has_token = true;
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+            }
+            '|' | '&' | ';' => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+                let mut op = c.to_string();
+                if (c == '|' || c == '&') && chars.peek() == Some(&c) {
+                    op.push(chars.next().unwrap());
+                }
+                tokens.push(op);
+            }
+            c if c.is_whitespace() => {
+                if has_token {
+                    tokens.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            _ => {
+                has_token = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if has_token {
+        tokens.push(current);
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(provider: &str, command: Option<&str>) -> ProviderAnswer {
+        ProviderAnswer {
+            provider: provider.to_string(),
+            command: command.map(String::from),
+            error: command.is_none().then(|| "failed".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_normalize_whitespace_and_quotes() {
+        assert_eq!(
+            normalize_command("find .  -name '*.txt'"),
+            normalize_command("find . -name \"*.txt\"")
+        );
+        assert_eq!(normalize_command("echo   hello"), "echo hello");
+    }
+
+    #[test]
+    fn test_normalize_flag_order() {
+        assert_eq!(normalize_command("ls -la"), normalize_command("ls -a -l"));
+        assert_eq!(normalize_command("ls -la"), normalize_command("ls -al"));
+        assert_eq!(
+            normalize_command("grep -r --color=auto foo ."),
+            normalize_command("grep --color=auto -r foo .")
+        );
+
+        // 위치 인자가 뒤에 와도 값 없는 플래그는 정렬
+        assert_eq!(normalize_command("rm -rf build"), normalize_command("rm -fr build"));
+        assert_eq!(normalize_command("rm -rf build"), normalize_command("rm -r -f build"));
+        assert_eq!(normalize_command("ls -la /tmp"), normalize_command("ls -al /tmp"));
+        assert_eq!(normalize_command("grep -ri x"), normalize_command("grep -ir x"));
+        assert_eq!(normalize_command("tar -xzf a.tar.gz"), normalize_command("tar -zxf a.tar.gz"));
+        assert_eq!(normalize_command("grep -n -A 3 TODO src"), normalize_command("grep -A 3 -n TODO src"));
+    }
+
+    #[test]
+    fn test_normalize_keeps_positional_order() {
+        assert_ne!(normalize_command("cp a b"), normalize_command("cp b a"));
+        assert_ne!(normalize_command("cp -v a b"), normalize_command("cp b -v a"));
+    }
+
+    #[test]
+    fn test_normalize_keeps_flag_values() {
+        assert_ne!(normalize_command("cut -d , -f 1"), normalize_command("cut -d 1 -f ,"));
+        assert_ne!(normalize_command("tar -C x -xf y"), normalize_command("tar -xf x -C y"));
+        assert_ne!(normalize_command("sort -k 2 -t ,"), normalize_command("sort -k , -t 2"));
+        assert_ne!(normalize_command("tar -xf a -C b"), normalize_command("tar -fx a -C b"));
+        assert_ne!(normalize_command("cut -d, -f1"), normalize_command("cut -f, -d1"));
+        assert_eq!(normalize_command("du -sh --max-depth=1 ."), normalize_command("du -hs --max-depth=1 ."));
+    }
+
+    #[test]
+    fn test_normalize_pipelines() {
+        assert_eq!(
+            normalize_command("ps -ef|grep node"),
+            normalize_command("ps -e -f | grep node")
+        );
+        assert_ne!(
+            normalize_command("make && make test"),
+            normalize_command("make || make test")
+        );
+    }
+
+    #[test]
+    fn test_normalize_order_sensitive_program() {
+        // find의 표현식 순서는 의미가 있으므로 유지
+        assert_ne!(
+            normalize_command("find . -delete -name '*.tmp'"),
+            normalize_command("find . -name '*.tmp' -delete")
+        );
+    }
+
+    #[test]
+    fn test_unanimous_result() {
+        let result = ConsensusResult::from_answers(vec![
+            answer("gemini", Some("ls -la")),
+            answer("claude", Some("ls -al")),
+            answer("codex", None),
+        ]);
+
+        assert!(result.is_unanimous());
+        assert_eq!(result.answered_count(), 2);
+        assert_eq!(result.groups[0].providers, vec!["gemini", "claude"]);
+    }
+
+    #[test]
+    fn test_divergent_result() {
+        let result = ConsensusResult::from_answers(vec![
+            answer("gemini", Some("rm -rf build")),
+            answer("claude", Some("cargo clean")),
+            answer("codex", Some("cargo  clean")),
+        ]);
+
+        assert!(!result.is_unanimous());
+        assert_eq!(result.groups.len(), 2);
+        // 지지가 많은 그룹이 먼저
+        assert_eq!(result.groups[0].command, "cargo clean");
+        assert_eq!(result.groups[0].providers, vec!["claude", "codex"]);
+    }
+
+    #[test]
+    fn test_single_answer_is_not_unanimous() {
+        let result = ConsensusResult::from_answers(vec![
+            answer("gemini", Some("date")),
+            answer("claude", None),
+        ]);
+
+        assert!(!result.is_unanimous());
+    }
+}
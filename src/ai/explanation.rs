@@ -0,0 +1,152 @@
+use crate::error::{AskAiError, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// `<부분> => <설명>` 형식의 줄 (프롬프트에서 요청하는 기본 형식)
+static ARROW_LINE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(.+?)\s+=>\s+(.+)$").unwrap()
+});
+
+/// `` - `부분`: 설명 `` 형식의 마크다운 목록 (모델이 형식을 어겼을 때 대비)
+static MARKDOWN_LINE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:[-*]|\d+\.)?\s*`([^`]+)`\s*(?:[:\-–—]|=>)\s*(.+)$").unwrap()
+});
+
+/// 줄 앞의 목록 기호
+static BULLET_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:[-*]|\d+\.)\s+").unwrap()
+});
+
+/// 명령어의 한 부분(토큰/플래그)에 대한 설명
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExplanationPart {
+    /// 명령어의 일부 (예: `-la`, `| grep node`)
+    pub token: String,
+    /// 해당 부분이 하는 일
+    pub description: String,
+}
+
+/// 명령어 설명 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandExplanation {
+    /// 명령어 전체가 하는 일 (한 문장)
+    pub summary: String,
+    /// 토큰/플래그별 설명 (명령어 순서)
+    pub parts: Vec<ExplanationPart>,
+}
+
+impl CommandExplanation {
+    /// explain 프롬프트에 대한 AI 응답을 파싱
+    ///
+    /// `ResponseProcessor::process`는 설명문을 거부하므로 별도의 파서를 사용합니다.
+    /// 형식을 벗어난 줄은 요약에 덧붙여 정보가 사라지지 않도록 합니다.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let mut summary_lines: Vec<String> = Vec::new();
+        let mut parts = Vec::new();
+
+        for line in raw.lines().map(str::trim) {
+            // 빈 줄과 코드 블록 구분자는 무시
+            if line.is_empty() || line.starts_with("```") {
+                continue;
+            }
+
+            if let Some(rest) = strip_prefix_ignore_case(line, "SUMMARY:") {
+                summary_lines.insert(0, rest.trim().to_string());
+                continue;
+            }
+
+            let caps = ARROW_LINE_REGEX
+                .captures(line)
+                .or_else(|| MARKDOWN_LINE_REGEX.captures(line));
+
+            match caps {
+                Some(caps) => parts.push(ExplanationPart {
+                    token: clean_token(&caps[1]),
+                    description: caps[2].trim().to_string(),
+                }),
+                None => summary_lines.push(line.to_string()),
+            }
+        }
+
+        let summary = summary_lines
+            .into_iter()
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if summary.is_empty() && parts.is_empty() {
+            return Err(AskAiError::AiCliError(
+                "AI returned an empty explanation. Please try again.".to_string()
+            ));
+        }
+
+        Ok(Self { summary, parts })
+    }
+}
+
+/// 목록 기호(`- `, `1. `)와 백틱을 제거한 토큰
+///
+/// 플래그의 `-`는 뒤에 공백이 없으므로 목록 기호로 취급하지 않습니다.
+fn clean_token(token: &str) -> String {
+    BULLET_REGEX
+        .replace(token.trim(), "")
+        .trim()
+        .trim_matches('`')
+        .to_string()
+}
+
+/// 대소문자를 무시하고 접두사 제거
+fn strip_prefix_ignore_case<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| &line[prefix.len()..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_expected_format() {
+        let raw = "SUMMARY: Lists all files including hidden ones.\n\
+                   ls => list directory contents\n\
+                   -la => long format, include dotfiles";
+
+        let explanation = CommandExplanation::parse(raw).unwrap();
+        assert_eq!(explanation.summary, "Lists all files including hidden ones.");
+        assert_eq!(explanation.parts.len(), 2);
+        assert_eq!(explanation.parts[1].token, "-la");
+        assert_eq!(explanation.parts[1].description, "long format, include dotfiles");
+    }
+
+    #[test]
+    fn test_parse_markdown_list() {
+        let raw = "This shows processes using port 8080.\n\
+                   - `lsof`: list open files\n\
+                   - `-i :8080`: only network files on port 8080";
+
+        let explanation = CommandExplanation::parse(raw).unwrap();
+        assert_eq!(explanation.summary, "This shows processes using port 8080.");
+        assert_eq!(explanation.parts[0].token, "lsof");
+        assert_eq!(explanation.parts[1].token, "-i :8080");
+    }
+
+    #[test]
+    fn test_parse_bulleted_arrow_keeps_flag_dash() {
+        let explanation = CommandExplanation::parse("- -r => recurse into directories").unwrap();
+        assert_eq!(explanation.parts[0].token, "-r");
+    }
+
+    #[test]
+    fn test_parse_prose_only() {
+        // 형식을 전혀 따르지 않아도 설명은 보존
+        let explanation = CommandExplanation::parse("Deletes the build directory.").unwrap();
+        assert_eq!(explanation.summary, "Deletes the build directory.");
+        assert!(explanation.parts.is_empty());
+    }
+
+    #[test]
+    fn test_parse_empty() {
+        assert!(CommandExplanation::parse("  \n```\n```").is_err());
+    }
+}
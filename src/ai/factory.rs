@@ -1,7 +1,9 @@
 use crate::error::{AskAiError, Result};
-use crate::ai::{AiProvider, gemini::GeminiProvider, claude::ClaudeProvider, codex::CodexProvider};
+use crate::ai::{AiProvider, gemini::GeminiProvider, claude::ClaudeProvider, codex::CodexProvider, openai::OpenAiCompatibleProvider, ollama::OllamaProvider, generic::GenericCliProvider, fallback::FallbackProvider};
+use crate::config::Config;
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::Duration;
 use once_cell::sync::Lazy;
 use std::sync::Mutex;
 use tokio::process::Command;
@@ -60,8 +62,10 @@ impl ProviderFactory {
 
     /// Create a provider instance based on the provider name
     ///
+    /// HTTP provider 설정은 `~/.askai/config.toml`에서 읽어옵니다 (없으면 기본값).
+    ///
     /// # Arguments
-    /// * `provider_name` - The name of the provider (gemini, claude, codex)
+    /// * `provider_name` - The name of the provider (gemini, claude, codex, openai, ollama)
     ///
     /// # Returns
     /// * `Result<Arc<dyn AiProvider>>` - Boxed provider instance
@@ -69,24 +73,85 @@ impl ProviderFactory {
     /// # Errors
     /// * Returns `AskAiError::AiCliError` if the provider name is unknown
     pub fn create(provider_name: &str) -> Result<Arc<dyn AiProvider>> {
-        match provider_name.to_lowercase().as_str() {
+        let config = Config::load().unwrap_or_default();
+        Self::create_with_config(provider_name, &config)
+    }
+
+    /// 주어진 설정으로 provider 인스턴스 생성
+    ///
+    /// `[providers.<name>]`에 정의된 CLI provider가 내장 provider보다 우선합니다.
+    pub fn create_with_config(provider_name: &str, config: &Config) -> Result<Arc<dyn AiProvider>> {
+        let normalized = provider_name.to_lowercase();
+
+        if let Some((name, cli_config)) = config.providers
+            .iter()
+            .find(|(name, _)| name.to_lowercase() == normalized)
+        {
+            return Ok(Arc::new(GenericCliProvider::new(name, cli_config.clone())));
+        }
+
+        match normalized.as_str() {
             "gemini" => Ok(Arc::new(GeminiProvider::new())),
             "claude" => Ok(Arc::new(ClaudeProvider::new())),
             "codex" => Ok(Arc::new(CodexProvider::new())),
+            "openai" => Ok(Arc::new(OpenAiCompatibleProvider::new(&config.openai))),
+            "ollama" => Ok(Arc::new(OllamaProvider::new(&config.ollama))),
             _ => Err(AskAiError::AiCliError(
                 format!(
                     "Unknown AI provider: {}\n\
-                     Supported providers: gemini, claude, codex",
-                    provider_name
+                     Supported providers: {}",
+                    provider_name,
+                    Self::available_providers(config).join(", ")
                 )
             )),
         }
     }
 
+    /// 기본 provider 뒤에 `fallback_providers`를 이어 붙인 fallback chain 생성
+    ///
+    /// 각 provider에는 `Config::timeout_for`의 타임아웃이 적용됩니다 (provider 자체 타임아웃보다 짧아지지 않음).
+    /// fallback 목록이 비어 있어도 타임아웃 적용을 위해 wrapper로 감쌉니다.
+    pub fn create_with_fallback(provider_name: &str, config: &Config) -> Result<Arc<dyn AiProvider>> {
+        let mut names: Vec<String> = vec![provider_name.to_lowercase()];
+        for name in &config.fallback_providers {
+            let name = name.to_lowercase();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        let chain = names
+            .iter()
+            .map(|name| {
+                let provider = Self::create_with_config(name, config)?;
+                let timeout = Duration::from_secs(config.timeout_for(name));
+                Ok((provider, timeout))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Arc::new(FallbackProvider::new(chain)))
+    }
+
     /// Get a list of all supported provider names
-    #[allow(dead_code)]
     pub fn supported_providers() -> Vec<&'static str> {
-        vec!["gemini", "claude", "codex"]
+        vec!["gemini", "claude", "codex", "openai", "ollama"]
+    }
+
+    /// 내장 provider + 설정 파일에 정의된 provider 이름 목록
+    pub fn available_providers(config: &Config) -> Vec<String> {
+        let mut providers: Vec<String> = Self::supported_providers()
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let mut custom: Vec<&String> = config.providers
+            .keys()
+            .filter(|name| !providers.contains(&name.to_lowercase()))
+            .collect();
+        custom.sort();
+        providers.extend(custom.into_iter().cloned());
+
+        providers
     }
 
     /// Check if a provider name is supported
@@ -121,6 +186,76 @@ mod tests {
         assert_eq!(provider.unwrap().name(), "codex");
     }
 
+    #[test]
+    fn test_create_openai_provider() {
+        let provider = ProviderFactory::create_with_config("openai", &Config::default());
+        assert!(provider.is_ok());
+        assert_eq!(provider.unwrap().name(), "openai");
+    }
+
+    #[test]
+    fn test_create_ollama_provider() {
+        let provider = ProviderFactory::create_with_config("ollama", &Config::default());
+        assert!(provider.is_ok());
+        assert_eq!(provider.unwrap().name(), "ollama");
+    }
+
+    #[test]
+    fn test_create_generic_cli_provider() {
+        let config: Config = toml::from_str(r#"
+            [providers.llm]
+            binary = "llm"
+            args = ["{prompt}"]
+        "#).unwrap();
+
+        let provider = ProviderFactory::create_with_config("llm", &config);
+        assert!(provider.is_ok());
+        let provider = provider.unwrap();
+        assert_eq!(provider.name(), "llm");
+        assert_eq!(provider.cli_command(), "llm");
+
+        // 설정에 없는 이름은 여전히 에러
+        assert!(ProviderFactory::create_with_config("sgpt", &config).is_err());
+    }
+
+    #[test]
+    fn test_generic_cli_provider_overrides_builtin() {
+        let config: Config = toml::from_str(r#"
+            [providers.gemini]
+            binary = "gemini-wrapper"
+        "#).unwrap();
+
+        let provider = ProviderFactory::create_with_config("gemini", &config).unwrap();
+        assert_eq!(provider.cli_command(), "gemini-wrapper");
+    }
+
+    #[test]
+    fn test_available_providers_includes_custom() {
+        let config: Config = toml::from_str(r#"
+            [providers.llm]
+            binary = "llm"
+        "#).unwrap();
+
+        let providers = ProviderFactory::available_providers(&config);
+        assert!(providers.contains(&"gemini".to_string()));
+        assert!(providers.contains(&"llm".to_string()));
+    }
+
+    #[test]
+    fn test_create_with_fallback() {
+        let mut config = Config {
+            fallback_providers: vec!["claude".to_string(), "gemini".to_string()],
+            ..Config::default()
+        };
+
+        let provider = ProviderFactory::create_with_fallback("gemini", &config).unwrap();
+        assert_eq!(provider.name(), "gemini");
+
+        // fallback 목록에 잘못된 이름이 있으면 에러
+        config.fallback_providers = vec!["unknown".to_string()];
+        assert!(ProviderFactory::create_with_fallback("gemini", &config).is_err());
+    }
+
     #[test]
     fn test_create_unknown_provider() {
         let provider = ProviderFactory::create("unknown");
@@ -139,10 +274,12 @@ mod tests {
     #[test]
     fn test_supported_providers() {
         let providers = ProviderFactory::supported_providers();
-        assert_eq!(providers.len(), 3);
+        assert_eq!(providers.len(), 5);
         assert!(providers.contains(&"gemini"));
         assert!(providers.contains(&"claude"));
         assert!(providers.contains(&"codex"));
+        assert!(providers.contains(&"openai"));
+        assert!(providers.contains(&"ollama"));
     }
 
     #[test]
@@ -150,6 +287,8 @@ mod tests {
         assert!(ProviderFactory::is_supported("gemini"));
         assert!(ProviderFactory::is_supported("claude"));
         assert!(ProviderFactory::is_supported("codex"));
+        assert!(ProviderFactory::is_supported("openai"));
+        assert!(ProviderFactory::is_supported("ollama"));
         assert!(!ProviderFactory::is_supported("unknown"));
     }
 }
@@ -0,0 +1,239 @@
+use crate::error::{AskAiError, Result};
+use crate::ai::{AiProvider, CommandFailure, explanation::CommandExplanation, response_processor::{GeneratedCommand, PlannedTask}};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// 여러 provider를 순서대로 시도하는 wrapper provider
+///
+/// 각 provider는 자신의 타임아웃 안에 응답해야 하며, 실패하거나 시간이 초과되면
+/// 다음 provider로 넘어갑니다. 실제로 응답한 provider는 `answered_by()`로 확인합니다.
+pub struct FallbackProvider {
+    /// (provider, 타임아웃) 목록 (앞쪽이 우선)
+    chain: Vec<(Arc<dyn AiProvider>, Duration)>,
+    /// 마지막으로 응답에 성공한 provider 이름
+    last_answered: Mutex<Option<String>>,
+}
+
+impl FallbackProvider {
+    /// # Arguments
+    /// * `chain` - 시도할 provider와 타임아웃 목록 (비어 있으면 안 됨)
+    pub fn new(chain: Vec<(Arc<dyn AiProvider>, Duration)>) -> Self {
+        assert!(!chain.is_empty(), "FallbackProvider requires at least one provider");
+
+        Self {
+            chain,
+            last_answered: Mutex::new(None),
+        }
+    }
+
+    /// chain의 provider를 순서대로 호출하여 처음 성공한 결과 반환
+    async fn try_each<'a, T, F>(&'a self, call: F) -> Result<T>
+    where
+        F: Fn(&'a Arc<dyn AiProvider>) -> BoxFuture<'a, Result<T>>,
+    {
+        let mut errors = Vec::new();
+
+        for (provider, timeout) in &self.chain {
+            match tokio::time::timeout(*timeout, call(provider)).await {
+                Ok(Ok(value)) => {
+                    *self.last_answered.lock().unwrap() = Some(provider.name().to_string());
+                    return Ok(value);
+                }
+                Ok(Err(e)) => {
+                    errors.push(format!("  - {}: {}", provider.name(), e));
+                }
+                Err(_) => {
+                    errors.push(format!(
+                        "  - {}: no response within {}s",
+                        provider.name(),
+                        timeout.as_secs_f32()
+                    ));
+                }
+            }
+        }
+
+        Err(AskAiError::AiCliError(format!(
+            "All providers failed:\n{}",
+            errors.join("\n")
+        )))
+    }
+
+    /// 첫 번째 (기본) provider
+    fn primary(&self) -> &Arc<dyn AiProvider> {
+        &self.chain[0].0
+    }
+}
+
+#[async_trait]
+impl AiProvider for FallbackProvider {
+    fn name(&self) -> &str {
+        self.primary().name()
+    }
+
+    fn cli_command(&self) -> &str {
+        self.primary().cli_command()
+    }
+
+    async fn check_installation(&self) -> Result<()> {
+        // 하나라도 사용 가능하면 OK
+        let mut errors = Vec::new();
+        for (provider, _) in &self.chain {
+            match provider.check_installation().await {
+                Ok(()) => return Ok(()),
+                Err(e) => errors.push(format!("  - {}: {}", provider.name(), e)),
+            }
+        }
+
+        Err(AskAiError::AiCliError(format!(
+            "No AI provider is available:\n{}",
+            errors.join("\n")
+        )))
+    }
+
+    async fn generate_command(&self, prompt: &str, context: &str) -> Result<String> {
+        self.try_each(|provider| provider.generate_command(prompt, context)).await
+    }
+
+    async fn generate_structured(&self, prompt: &str, context: &str) -> Result<GeneratedCommand> {
+        self.try_each(|provider| provider.generate_structured(prompt, context)).await
+    }
+
+    async fn generate_candidates(&self, prompt: &str, context: &str, n: usize) -> Result<Vec<String>> {
+        self.try_each(|provider| provider.generate_candidates(prompt, context, n)).await
+    }
+
+    async fn generate_script(&self, prompt: &str, context: &str) -> Result<String> {
+        self.try_each(|provider| provider.generate_script(prompt, context)).await
+    }
+
+    async fn explain_command(&self, command: &str, context: &str) -> Result<CommandExplanation> {
+        self.try_each(|provider| provider.explain_command(command, context)).await
+    }
+
+    async fn repair_command(&self, prompt: &str, failure: &CommandFailure, context: &str) -> Result<GeneratedCommand> {
+        self.try_each(|provider| provider.repair_command(prompt, failure, context)).await
+    }
+
+    async fn plan_tasks(&self, prompt: &str, context: &str) -> Result<Vec<PlannedTask>> {
+        self.try_each(|provider| provider.plan_tasks(prompt, context)).await
+    }
+
+    fn answered_by(&self) -> String {
+        self.last_answered
+            .lock()
+            .unwrap()
+            .clone()
+            .unwrap_or_else(|| self.name().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 고정된 결과를 돌려주는 테스트용 provider
+    struct MockProvider {
+        name: &'static str,
+        result: std::result::Result<&'static str, &'static str>,
+        delay: Duration,
+    }
+
+    impl MockProvider {
+        fn ok(name: &'static str, command: &'static str) -> Arc<dyn AiProvider> {
+            Arc::new(Self { name, result: Ok(command), delay: Duration::ZERO })
+        }
+
+        fn failing(name: &'static str, error: &'static str) -> Arc<dyn AiProvider> {
+            Arc::new(Self { name, result: Err(error), delay: Duration::ZERO })
+        }
+
+        fn hanging(name: &'static str) -> Arc<dyn AiProvider> {
+            Arc::new(Self { name, result: Ok("never"), delay: Duration::from_secs(10) })
+        }
+    }
+
+    #[async_trait]
+    impl AiProvider for MockProvider {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn cli_command(&self) -> &str {
+            self.name
+        }
+
+        async fn check_installation(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn generate_command(&self, _prompt: &str, _context: &str) -> Result<String> {
+            tokio::time::sleep(self.delay).await;
+            self.result
+                .map(String::from)
+                .map_err(|e| AskAiError::AiCliError(e.to_string()))
+        }
+    }
+
+    const SHORT: Duration = Duration::from_millis(100);
+
+    #[tokio::test]
+    async fn test_primary_answers() {
+        let provider = FallbackProvider::new(vec![
+            (MockProvider::ok("gemini", "ls -la"), SHORT),
+            (MockProvider::ok("claude", "ls"), SHORT),
+        ]);
+
+        assert_eq!(provider.generate_command("p", "c").await.unwrap(), "ls -la");
+        assert_eq!(provider.answered_by(), "gemini");
+        assert_eq!(provider.name(), "gemini");
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_error() {
+        let provider = FallbackProvider::new(vec![
+            (MockProvider::failing("gemini", "quota exceeded"), SHORT),
+            (MockProvider::ok("claude", "git status"), SHORT),
+        ]);
+
+        assert_eq!(provider.generate_command("p", "c").await.unwrap(), "git status");
+        assert_eq!(provider.answered_by(), "claude");
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_timeout() {
+        let provider = FallbackProvider::new(vec![
+            (MockProvider::hanging("gemini"), SHORT),
+            (MockProvider::ok("ollama", "date"), SHORT),
+        ]);
+
+        assert_eq!(provider.generate_command("p", "c").await.unwrap(), "date");
+        assert_eq!(provider.answered_by(), "ollama");
+    }
+
+    #[tokio::test]
+    async fn test_candidates_fall_back_on_error() {
+        let provider = FallbackProvider::new(vec![
+            (MockProvider::failing("gemini", "quota exceeded"), SHORT),
+            (MockProvider::ok("claude", "git status"), SHORT),
+        ]);
+
+        // 기본 구현은 반복 호출 후 중복 제거
+        let candidates = provider.generate_candidates("p", "c", 3).await.unwrap();
+        assert_eq!(candidates, vec!["git status"]);
+        assert_eq!(provider.answered_by(), "claude");
+    }
+
+    #[tokio::test]
+    async fn test_all_failed_reports_each_provider() {
+        let provider = FallbackProvider::new(vec![
+            (MockProvider::failing("gemini", "quota exceeded"), SHORT),
+            (MockProvider::hanging("claude"), SHORT),
+        ]);
+
+        let error = provider.generate_command("p", "c").await.unwrap_err().to_string();
+        assert!(error.contains("gemini: Failed to execute AI CLI: quota exceeded"));
+        assert!(error.contains("claude: no response"));
+    }
+}
@@ -0,0 +1,301 @@
+use crate::error::{AskAiError, Result};
+use crate::ai::{AiProvider, CommandFailure, explanation::CommandExplanation, factory::ProviderFactory, response_processor::{GeneratedCommand, PlannedTask, ResponseProcessor}, prompt_template::PromptTemplate};
+use crate::config::{GenericCliConfig, PromptInput};
+use async_trait::async_trait;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// 인자 템플릿에서 프롬프트로 치환되는 자리표시자
+const PROMPT_PLACEHOLDER: &str = "{prompt}";
+
+/// 설정 파일의 `[providers.<name>]`로 정의되는 범용 CLI provider
+///
+/// llm, aichat, sgpt 같은 임의의 CLI를 크레이트 수정 없이 연결할 수 있습니다.
+pub struct GenericCliProvider {
+    name: String,
+    config: GenericCliConfig,
+}
+
+impl GenericCliProvider {
+    pub fn new(name: &str, config: GenericCliConfig) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+        }
+    }
+
+    /// 실제 CLI에 넘길 인자 목록 생성
+    ///
+    /// argv 모드에서 `{prompt}` 자리표시자가 없으면 프롬프트를 마지막 인자로 추가합니다.
+    fn build_args(&self, full_prompt: &str) -> Vec<String> {
+        match self.config.prompt_input {
+            PromptInput::Argv => {
+                let has_placeholder = self.config.args.iter().any(|a| a.contains(PROMPT_PLACEHOLDER));
+                let mut args: Vec<String> = self.config.args
+                    .iter()
+                    .map(|a| a.replace(PROMPT_PLACEHOLDER, full_prompt))
+                    .collect();
+
+                if !has_placeholder {
+                    args.push(full_prompt.to_string());
+                }
+
+                args
+            }
+            PromptInput::Stdin => self.config.args.clone(),
+        }
+    }
+
+    /// CLI를 실행하고 stdout을 반환
+    async fn run(&self, full_prompt: &str) -> Result<String> {
+        let mut command = Command::new(&self.config.binary);
+        command
+            .args(self.build_args(full_prompt))
+            .envs(&self.config.env)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true); // 타임아웃 시 프로세스 정리
+
+        if self.config.prompt_input == PromptInput::Stdin {
+            command.stdin(Stdio::piped());
+        } else {
+            command.stdin(Stdio::null());
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| AskAiError::AiCliError(format!("{}: {}", self.config.binary, e)))?;
+
+        // stdin 쓰기와 stdout/stderr 읽기를 동시에 진행 (파이프 버퍼가 차도 교착되지 않도록)
+        // 쓰기도 타임아웃 안에서 이루어짐
+        let stdin = child.stdin.take();
+        let write_stdin = async move {
+            if let Some(mut stdin) = stdin {
+                // CLI가 stdin을 다 읽지 않고 종료하면 BrokenPipe가 나므로, 결과는 종료 코드로 판단
+                match stdin.write_all(full_prompt.as_bytes()).await {
+                    Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e),
+                    _ => {}
+                }
+                // stdin을 닫아 EOF 전달
+                drop(stdin);
+            }
+            Ok(())
+        };
+        let run = async {
+            let (written, output) = tokio::join!(write_stdin, child.wait_with_output());
+            written?;
+            output
+        };
+
+        let output = match self.config.timeout_secs {
+            Some(secs) => tokio::time::timeout(Duration::from_secs(secs), run)
+                .await
+                .map_err(|_| AskAiError::AiCliError(format!(
+                    "{} did not respond within {}s",
+                    self.config.binary, secs
+                )))?,
+            None => run.await,
+        }
+        .map_err(|e| AskAiError::AiCliError(e.to_string()))?;
+
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(AskAiError::AiCliError(error.to_string()));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[async_trait]
+impl AiProvider for GenericCliProvider {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn cli_command(&self) -> &str {
+        &self.config.binary
+    }
+
+    async fn check_installation(&self) -> Result<()> {
+        let default_hint = format!(
+            "{} CLI is not installed (configured in [providers.{}]).",
+            self.config.binary, self.name
+        );
+
+        // 캐싱된 설치 확인 사용 (성능 최적화)
+        ProviderFactory::check_installation(
+            self.cli_command(),
+            self.config.install_hint.as_deref().unwrap_or(&default_hint),
+        ).await
+    }
+
+    async fn generate_command(&self, prompt: &str, context: &str) -> Result<String> {
+        Ok(self.generate_structured(prompt, context).await?.command)
+    }
+
+    async fn generate_structured(&self, prompt: &str, context: &str) -> Result<GeneratedCommand> {
+        // CLI가 설치되어 있는지 확인 (캐싱됨)
+        self.check_installation().await?;
+
+        // 공통 프롬프트 템플릿 사용 (설정에 정의된 규칙 포함)
+        let full_prompt = PromptTemplate::build_command_generation_prompt(
+            prompt,
+            context,
+            self.config.rules.as_deref(),
+        );
+
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 후처리: JSON 응답 계약 파싱, 실패 시 휴리스틱으로 명령어만 추출 (공통 ResponseProcessor 사용)
+        ResponseProcessor::process_structured(&raw_output)
+    }
+
+    async fn generate_script(&self, prompt: &str, context: &str) -> Result<String> {
+        self.check_installation().await?;
+
+        let full_prompt = PromptTemplate::build_script_generation_prompt(
+            prompt,
+            context,
+            self.config.rules.as_deref(),
+        );
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 여러 줄을 보존하는 스크립트 전용 후처리
+        ResponseProcessor::process_script(&raw_output)
+    }
+
+    async fn repair_command(&self, prompt: &str, failure: &CommandFailure, context: &str) -> Result<GeneratedCommand> {
+        self.check_installation().await?;
+
+        let full_prompt = PromptTemplate::build_repair_prompt(
+            prompt,
+            failure,
+            context,
+            self.config.rules.as_deref(),
+        );
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 생성과 같은 JSON 응답 계약
+        ResponseProcessor::process_structured(&raw_output)
+    }
+
+    async fn plan_tasks(&self, prompt: &str, context: &str) -> Result<Vec<PlannedTask>> {
+        self.check_installation().await?;
+
+        let full_prompt = PromptTemplate::build_plan_prompt(prompt, context, self.config.rules.as_deref());
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 작업 목록 JSON 계약 (휴리스틱 fallback 없음)
+        ResponseProcessor::process_plan(&raw_output)
+    }
+
+    async fn explain_command(&self, command: &str, context: &str) -> Result<CommandExplanation> {
+        self.check_installation().await?;
+
+        // `rules`는 명령어 생성용이므로 설명에는 적용하지 않음
+        let full_prompt = PromptTemplate::explain_prompt(command, context);
+        let raw_output = self.run(&full_prompt).await?;
+
+        // 설명문은 ResponseProcessor가 거부하므로 전용 파서 사용
+        CommandExplanation::parse(&raw_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(binary: &str, args: &[&str], prompt_input: PromptInput) -> GenericCliConfig {
+        GenericCliConfig {
+            binary: binary.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            prompt_input,
+            env: HashMap::new(),
+            timeout_secs: None,
+            install_hint: None,
+            rules: None,
+        }
+    }
+
+    #[test]
+    fn test_provider_creation() {
+        let provider = GenericCliProvider::new("llm", config("llm", &[], PromptInput::Argv));
+        assert_eq!(provider.name(), "llm");
+        assert_eq!(provider.cli_command(), "llm");
+    }
+
+    #[test]
+    fn test_build_args_with_placeholder() {
+        let provider = GenericCliProvider::new(
+            "llm",
+            config("llm", &["-m", "gpt-4o-mini", "--prompt={prompt}"], PromptInput::Argv),
+        );
+
+        let args = provider.build_args("list files");
+        assert_eq!(args, vec!["-m", "gpt-4o-mini", "--prompt=list files"]);
+    }
+
+    #[test]
+    fn test_build_args_appends_prompt_without_placeholder() {
+        let provider = GenericCliProvider::new("sgpt", config("sgpt", &["--shell"], PromptInput::Argv));
+
+        let args = provider.build_args("list files");
+        assert_eq!(args, vec!["--shell", "list files"]);
+    }
+
+    #[test]
+    fn test_build_args_stdin_mode() {
+        let provider = GenericCliProvider::new("aichat", config("aichat", &["-S"], PromptInput::Stdin));
+
+        let args = provider.build_args("list files");
+        assert_eq!(args, vec!["-S"]);
+    }
+
+    #[tokio::test]
+    async fn test_generate_command_via_stdin() {
+        // stdin을 끝까지 읽은 뒤 고정된 명령어를 출력하는 가짜 CLI
+        let mut cfg = config("sh", &["-c", "cat > /dev/null; echo \"$ASKAI_TEST_CMD\""], PromptInput::Stdin);
+        cfg.env.insert("ASKAI_TEST_CMD".to_string(), "git status".to_string());
+
+        let provider = GenericCliProvider::new("fake", cfg);
+        let command = provider.generate_command("git 상태", "OS: linux").await.unwrap();
+        assert_eq!(command, "git status");
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let mut cfg = config("sleep", &["5"], PromptInput::Stdin);
+        cfg.timeout_secs = Some(1);
+
+        let provider = GenericCliProvider::new("slow", cfg);
+        let result = provider.generate_command("anything", "OS: linux").await;
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("did not respond"));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_covers_stdin_write() {
+        // stdin을 읽지 않는 CLI에 파이프 버퍼보다 큰 프롬프트를 보내도 타임아웃이 적용되어야 함
+        let mut cfg = config("sleep", &["5"], PromptInput::Stdin);
+        cfg.timeout_secs = Some(1);
+
+        let provider = GenericCliProvider::new("slow", cfg);
+        let result = provider.run(&"x".repeat(1 << 20)).await;
+        assert!(result.unwrap_err().to_string().contains("did not respond"));
+    }
+
+    #[tokio::test]
+    async fn test_large_output_before_reading_stdin() {
+        // stdin을 읽기 전에 파이프 버퍼보다 많이 출력하는 CLI도 교착 없이 끝나야 함
+        let mut cfg = config("sh", &["-c", "head -c 200000 /dev/zero | tr '\\0' a; cat > /dev/null"], PromptInput::Stdin);
+        cfg.timeout_secs = Some(10);
+
+        let provider = GenericCliProvider::new("chatty", cfg);
+        let output = provider.run(&"x".repeat(200_000)).await.unwrap();
+        assert_eq!(output.len(), 200_000);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::Result;
+use crate::ai::response_processor::CommandDetails;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -17,6 +18,15 @@ pub struct CommandHistory {
     pub executed: bool,
     /// AI provider
     pub provider: String,
+    /// askai가 직접 실행한 경우의 exit code (`--exec`, eval용으로 출력한 경우에는 없음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// 사용자가 확인 단계에서 고치기 전의 명령어 (고치지 않았으면 없음)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_from: Option<String>,
+    /// AI가 함께 돌려준 부가 정보 (설명, sudo 필요 여부, 가정, 자신감)
+    #[serde(default, flatten)]
+    pub details: CommandDetails,
 }
 
 /// 명령어 히스토리 저장소
@@ -135,11 +145,21 @@ impl HistoryStore {
         let mut context = String::from("\n\nRelevant past commands:\n");
 
         for (i, entry) in history.iter().enumerate() {
+            // 실패한 명령어는 다시 제안하지 않도록 표시
+            let mut outcome = match entry.exit_code {
+                Some(code) if code != 0 => format!(" (failed with exit code {})", code),
+                _ => String::new(),
+            };
+            // 사용자가 고친 명령어는 원래 제안과 함께 보내 같은 실수를 반복하지 않게 함
+            if let Some(original) = &entry.edited_from {
+                outcome.push_str(&format!(" (user corrected it from \"{}\")", original));
+            }
             context.push_str(&format!(
-                "{}. Prompt: \"{}\" → Command: \"{}\"\n",
+                "{}. Prompt: \"{}\" → Command: \"{}\"{}\n",
                 i + 1,
                 entry.prompt,
-                entry.command
+                entry.command,
+                outcome
             ));
         }
 
@@ -163,6 +183,9 @@ mod tests {
                 timestamp: Utc::now(),
                 executed: true,
                 provider: "gemini".to_string(),
+                exit_code: None,
+                edited_from: None,
+                details: CommandDetails::default(),
             },
             CommandHistory {
                 prompt: "git 상태 확인".to_string(),
@@ -170,6 +193,9 @@ mod tests {
                 timestamp: Utc::now(),
                 executed: true,
                 provider: "gemini".to_string(),
+                exit_code: None,
+                edited_from: None,
+                details: CommandDetails::default(),
             },
         ];
 
@@ -194,6 +220,33 @@ mod tests {
         assert!(score > 0);
     }
 
+    #[test]
+    fn test_details_roundtrip_and_legacy_entries() {
+        let entry = CommandHistory {
+            prompt: "패키지 업데이트".to_string(),
+            command: "sudo apt update".to_string(),
+            timestamp: Utc::now(),
+            executed: true,
+            provider: "gemini".to_string(),
+            exit_code: None,
+            edited_from: None,
+            details: CommandDetails {
+                requires_sudo: true,
+                confidence: Some(0.7),
+                ..CommandDetails::default()
+            },
+        };
+
+        let json = serde_json::to_string(&entry).unwrap();
+        let loaded: CommandHistory = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.details, entry.details);
+
+        // 부가 정보가 없던 이전 버전의 히스토리도 읽을 수 있어야 함
+        let legacy = r#"{"prompt":"p","command":"ls","timestamp":"2025-01-01T00:00:00Z","executed":true,"provider":"gemini"}"#;
+        let loaded: CommandHistory = serde_json::from_str(legacy).unwrap();
+        assert_eq!(loaded.details, CommandDetails::default());
+    }
+
     #[test]
     fn test_format_context() {
         let store = HistoryStore::new();
@@ -205,6 +258,9 @@ mod tests {
                 timestamp: Utc::now(),
                 executed: true,
                 provider: "gemini".to_string(),
+                exit_code: None,
+                edited_from: None,
+                details: CommandDetails::default(),
             },
         ];
 
@@ -212,5 +268,14 @@ mod tests {
         assert!(context.contains("Prompt:"));
         assert!(context.contains("Command:"));
         assert!(context.contains("ls -la"));
+        assert!(!context.contains("failed"));
+
+        // 직접 실행해서 실패한 명령어는 결과를 함께 보냄
+        let failed = vec![CommandHistory { exit_code: Some(127), ..history[0].clone() }];
+        assert!(store.format_as_context(&failed).contains("(failed with exit code 127)"));
+
+        // 사용자가 고친 명령어는 원래 제안을 함께 보냄
+        let corrected = vec![CommandHistory { edited_from: Some("ls -l".to_string()), ..history[0].clone() }];
+        assert!(store.format_as_context(&corrected).contains("Command: \"ls -la\" (user corrected it from \"ls -l\")"));
     }
 }
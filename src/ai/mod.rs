@@ -1,13 +1,24 @@
 pub mod gemini;
 pub mod claude;
 pub mod codex;
+pub mod openai;
+pub mod ollama;
+pub mod generic;
+pub mod fallback;
+pub mod consensus;
+pub mod explanation;
 pub mod factory;
 pub mod history;
 pub mod response_processor;
 pub mod prompt_template;
 
-use crate::error::Result;
+#[cfg(test)]
+mod stub_server;
+
+use crate::error::{AskAiError, Result};
 use async_trait::async_trait;
+use explanation::CommandExplanation;
+use response_processor::{GeneratedCommand, PlannedTask};
 
 /// AI provider trait for extensible CLI integration
 #[async_trait]
@@ -15,7 +26,7 @@ pub trait AiProvider: Send + Sync {
     /// Provider name (e.g., "gemini", "claude", "codex")
     fn name(&self) -> &str;
 
-    /// CLI command name (e.g., "gemini", "claude", "codex")
+    /// CLI command name (e.g., "gemini", "claude", "codex"; HTTP providers return their endpoint)
     fn cli_command(&self) -> &str;
 
     /// Check if the CLI is installed on the system
@@ -23,4 +34,144 @@ pub trait AiProvider: Send + Sync {
 
     /// Generate a bash command from natural language prompt
     async fn generate_command(&self, prompt: &str, context: &str) -> Result<String>;
+
+    /// Generate a command together with the structured details of the JSON response contract
+    /// (explanation, requires_sudo, assumptions, confidence)
+    ///
+    /// The default implementation has no details and wraps `generate_command`.
+    async fn generate_structured(&self, prompt: &str, context: &str) -> Result<GeneratedCommand> {
+        self.generate_command(prompt, context)
+            .await
+            .map(GeneratedCommand::from_command)
+    }
+
+    /// Generate up to `n` distinct candidate commands
+    ///
+    /// The default implementation calls `generate_command` `n` times concurrently
+    /// (CLI providers have no native multi-sample option) and drops duplicates.
+    /// Fails only if every call fails.
+    async fn generate_candidates(&self, prompt: &str, context: &str, n: usize) -> Result<Vec<String>> {
+        let results = futures::future::join_all(
+            (0..n.max(1)).map(|_| self.generate_command(prompt, context))
+        ).await;
+
+        collect_candidates(results)
+    }
+
+    /// Generate a multi-line bash script (heredocs, functions and control flow preserved)
+    async fn generate_script(&self, prompt: &str, context: &str) -> Result<String> {
+        let _ = (prompt, context);
+        Err(AskAiError::AiCliError(format!(
+            "{} provider does not support script generation",
+            self.name()
+        )))
+    }
+
+    /// Explain what a bash command does, part by part
+    ///
+    /// The response is prose, so it is parsed with `CommandExplanation::parse`
+    /// instead of `ResponseProcessor::process`.
+    async fn explain_command(&self, command: &str, context: &str) -> Result<CommandExplanation> {
+        let _ = (command, context);
+        Err(AskAiError::AiCliError(format!(
+            "{} provider does not support explaining commands",
+            self.name()
+        )))
+    }
+
+    /// Suggest a corrected command after `failure.command` failed for the original `prompt`
+    ///
+    /// The response uses the same JSON contract as `generate_structured`.
+    async fn repair_command(&self, prompt: &str, failure: &CommandFailure, context: &str) -> Result<GeneratedCommand> {
+        let _ = (prompt, failure, context);
+        Err(AskAiError::AiCliError(format!(
+            "{} provider does not support repairing commands",
+            self.name()
+        )))
+    }
+
+    /// Split a multi-step request into commands with explicit dependencies
+    ///
+    /// Independent steps may run in parallel; see `ResponseProcessor::process_plan`.
+    async fn plan_tasks(&self, prompt: &str, context: &str) -> Result<Vec<PlannedTask>> {
+        let _ = (prompt, context);
+        Err(AskAiError::AiCliError(format!(
+            "{} provider does not support planning",
+            self.name()
+        )))
+    }
+
+    /// Name of the provider that actually answered the last request
+    /// (differs from `name()` only for wrappers such as the fallback chain)
+    fn answered_by(&self) -> String {
+        self.name().to_string()
+    }
+}
+
+/// 실행에 실패한 명령어 (복구 프롬프트의 입력)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandFailure {
+    /// 실패한 명령어
+    pub command: String,
+    /// 종료 코드
+    pub exit_code: i32,
+    /// stderr 마지막 부분
+    pub stderr: String,
+}
+
+/// 여러 생성 결과에서 중복을 제거한 후보 목록 생성
+///
+/// 성공한 결과가 하나도 없으면 첫 번째 에러를 반환합니다.
+pub fn collect_candidates(results: Vec<Result<String>>) -> Result<Vec<String>> {
+    let mut candidates: Vec<String> = Vec::new();
+    let mut first_error = None;
+
+    for result in results {
+        match result {
+            Ok(command) => {
+                if !candidates.contains(&command) {
+                    candidates.push(command);
+                }
+            }
+            Err(e) => {
+                if first_error.is_none() {
+                    first_error = Some(e);
+                }
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) if candidates.is_empty() => Err(e),
+        _ => Ok(candidates),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collect_candidates_dedup() {
+        let results = vec![
+            Ok("ls -la".to_string()),
+            Err(AskAiError::AiCliError("timeout".to_string())),
+            Ok("ls -la".to_string()),
+            Ok("ls -lah".to_string()),
+        ];
+
+        let candidates = collect_candidates(results).unwrap();
+        assert_eq!(candidates, vec!["ls -la", "ls -lah"]);
+    }
+
+    #[test]
+    fn test_collect_candidates_all_failed() {
+        let results = vec![
+            Err(AskAiError::AiCliError("first".to_string())),
+            Err(AskAiError::AiCliError("second".to_string())),
+        ];
+
+        let error = collect_candidates(results).unwrap_err();
+        assert!(error.to_string().contains("first"));
+    }
 }
@@ -0,0 +1,249 @@
+use crate::error::{AskAiError, Result};
+use crate::ai::{AiProvider, CommandFailure, explanation::CommandExplanation, response_processor::{GeneratedCommand, PlannedTask, ResponseProcessor}, prompt_template::PromptTemplate};
+use crate::config::OllamaConfig;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// `/api/generate` 요청 옵션
+#[derive(Debug, Serialize)]
+struct GenerateOptions {
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_ctx: Option<u32>,
+}
+
+/// `/api/generate` 요청 본문
+#[derive(Debug, Serialize)]
+struct GenerateRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+    stream: bool,
+    /// "json"이면 서버가 JSON 출력을 강제 (명령어 생성 시 사용)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'a str>,
+    options: GenerateOptions,
+}
+
+/// `/api/generate` 응답 본문 (stream: false)
+#[derive(Debug, Deserialize)]
+struct GenerateResponse {
+    #[serde(default)]
+    response: String,
+}
+
+/// 로컬 Ollama 서버를 호출하는 provider
+///
+/// 외부 네트워크 없이 동작하므로 air-gapped 환경에서도 사용할 수 있습니다.
+pub struct OllamaProvider {
+    client: reqwest::Client,
+    host: String,
+    model: String,
+    temperature: f32,
+    num_ctx: Option<u32>,
+}
+
+impl OllamaProvider {
+    pub fn new(config: &OllamaConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            host: config.host.trim_end_matches('/').to_string(),
+            model: config.model.clone(),
+            temperature: config.temperature,
+            num_ctx: config.num_ctx,
+        }
+    }
+
+    /// generate endpoint 전체 URL
+    fn endpoint(&self) -> String {
+        format!("{}/api/generate", self.host)
+    }
+
+    /// generate endpoint에 요청을 보내고 원본 응답을 반환
+    ///
+    /// # Arguments
+    /// * `format` - 응답 형식 강제 (`Some("json")`), 자유 형식이면 None
+    async fn request(&self, full_prompt: &str, format: Option<&str>) -> Result<String> {
+        self.check_installation().await?;
+
+        let body = GenerateRequest {
+            model: &self.model,
+            prompt: full_prompt,
+            stream: false,
+            format,
+            options: GenerateOptions {
+                temperature: self.temperature,
+                num_ctx: self.num_ctx,
+            },
+        };
+
+        let response = self.client
+            .post(self.endpoint())
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_connect() {
+                    AskAiError::AiApiError(format!(
+                        "Could not connect to Ollama at {}.\n\
+                         Start the server with: ollama serve",
+                        self.host
+                    ))
+                } else {
+                    AskAiError::AiApiError(e.to_string())
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            // 모델이 없으면 404와 함께 {"error": "model 'x' not found"} 반환
+            let error = response.text().await.unwrap_or_default();
+            return Err(AskAiError::AiApiError(format!(
+                "HTTP {}: {}\n\
+                 Pull the model with: ollama pull {}",
+                status, error, self.model
+            )));
+        }
+
+        let generated: GenerateResponse = response
+            .json()
+            .await
+            .map_err(|e| AskAiError::AiApiError(format!("Invalid response body: {}", e)))?;
+
+        Ok(generated.response.trim().to_string())
+    }
+}
+
+#[async_trait]
+impl AiProvider for OllamaProvider {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn cli_command(&self) -> &str {
+        // HTTP provider는 CLI가 없으므로 endpoint를 대신 노출
+        &self.host
+    }
+
+    async fn check_installation(&self) -> Result<()> {
+        // 설치할 CLI가 없으므로 설정값만 검증
+        if !self.host.starts_with("http://") && !self.host.starts_with("https://") {
+            return Err(AskAiError::ConfigError(format!(
+                "Invalid Ollama host: '{}'\n\
+                 Set [ollama] host in ~/.askai/config.toml (e.g. http://localhost:11434)",
+                self.host
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn generate_command(&self, prompt: &str, context: &str) -> Result<String> {
+        Ok(self.generate_structured(prompt, context).await?.command)
+    }
+
+    async fn generate_structured(&self, prompt: &str, context: &str) -> Result<GeneratedCommand> {
+        // 공통 프롬프트 템플릿 사용
+        let full_prompt = PromptTemplate::for_ollama(prompt, context);
+
+        // JSON 응답 계약을 서버 측에서도 강제
+        let raw_output = self.request(&full_prompt, Some("json")).await?;
+
+        // 후처리: JSON 응답 계약 파싱, 실패 시 휴리스틱으로 명령어만 추출 (공통 ResponseProcessor 사용)
+        ResponseProcessor::process_structured(&raw_output)
+    }
+
+    async fn generate_script(&self, prompt: &str, context: &str) -> Result<String> {
+        let full_prompt = PromptTemplate::script_for_ollama(prompt, context);
+        let raw_output = self.request(&full_prompt, None).await?;
+
+        // 여러 줄을 보존하는 스크립트 전용 후처리
+        ResponseProcessor::process_script(&raw_output)
+    }
+
+    async fn repair_command(&self, prompt: &str, failure: &CommandFailure, context: &str) -> Result<GeneratedCommand> {
+        let full_prompt = PromptTemplate::repair_prompt(prompt, failure, context);
+        let raw_output = self.request(&full_prompt, Some("json")).await?;
+
+        // 생성과 같은 JSON 응답 계약
+        ResponseProcessor::process_structured(&raw_output)
+    }
+
+    async fn plan_tasks(&self, prompt: &str, context: &str) -> Result<Vec<PlannedTask>> {
+        let full_prompt = PromptTemplate::plan_prompt(prompt, context);
+        let raw_output = self.request(&full_prompt, Some("json")).await?;
+
+        // 작업 목록 JSON 계약 (휴리스틱 fallback 없음)
+        ResponseProcessor::process_plan(&raw_output)
+    }
+
+    async fn explain_command(&self, command: &str, context: &str) -> Result<CommandExplanation> {
+        let full_prompt = PromptTemplate::explain_for_ollama(command, context);
+        let raw_output = self.request(&full_prompt, None).await?;
+
+        // 설명문은 ResponseProcessor가 거부하므로 전용 파서 사용
+        CommandExplanation::parse(&raw_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::stub_server::spawn_stub_server;
+
+    fn test_config(host: &str) -> OllamaConfig {
+        OllamaConfig {
+            host: host.to_string(),
+            model: "test-model".to_string(),
+            num_ctx: Some(2048),
+            ..OllamaConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_provider_creation() {
+        let provider = OllamaProvider::new(&test_config("http://localhost:11434/"));
+        assert_eq!(provider.name(), "ollama");
+        assert_eq!(provider.endpoint(), "http://localhost:11434/api/generate");
+    }
+
+    #[tokio::test]
+    async fn test_generate_command_against_stub_server() {
+        let (host, handle) = spawn_stub_server(
+            "200 OK",
+            r#"{"model":"test-model","response":"df -h","done":true}"#,
+        ).await;
+
+        let provider = OllamaProvider::new(&test_config(&host));
+        let command = provider.generate_command("디스크 사용량", "OS: linux").await.unwrap();
+        assert_eq!(command, "df -h");
+
+        let request = handle.await.unwrap();
+        assert!(request.starts_with("POST /api/generate"));
+        assert!(request.contains("\"stream\":false"));
+        assert!(request.contains("\"num_ctx\":2048"));
+        assert!(request.contains("\"format\":\"json\""));
+    }
+
+    #[tokio::test]
+    async fn test_missing_model_is_reported() {
+        let (host, handle) = spawn_stub_server(
+            "404 Not Found",
+            r#"{"error":"model 'test-model' not found"}"#,
+        ).await;
+
+        let provider = OllamaProvider::new(&test_config(&host));
+        let result = provider.generate_command("디스크 사용량", "OS: linux").await;
+        handle.await.unwrap();
+
+        match result {
+            Err(AskAiError::AiApiError(msg)) => assert!(msg.contains("ollama pull test-model")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use crate::error::{AskAiError, Result};
+use crate::ai::{AiProvider, CommandFailure, collect_candidates, explanation::CommandExplanation, response_processor::{GeneratedCommand, PlannedTask, ResponseProcessor}, prompt_template::PromptTemplate};
+use crate::config::OpenAiConfig;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// `/v1/chat/completions` 요청 메시지
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// `/v1/chat/completions` 요청 본문
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    /// 생성할 응답 개수 (None이면 서버 기본값 1)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    n: Option<usize>,
+}
+
+/// `/v1/chat/completions` 응답 본문 (필요한 필드만)
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatChoice {
+    message: ChatResponseMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponseMessage {
+    #[serde(default)]
+    content: Option<String>,
+}
+
+/// OpenAI 호환 HTTP API를 직접 호출하는 provider
+///
+/// CLI를 spawn하지 않고 `/v1/chat/completions` endpoint로 요청을 보냅니다.
+/// OpenAI뿐 아니라 사내 gateway, llama.cpp, vLLM 등 호환 서버에서 동작합니다.
+pub struct OpenAiCompatibleProvider {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    api_key_env: String,
+    temperature: f32,
+}
+
+impl OpenAiCompatibleProvider {
+    pub fn new(config: &OpenAiConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            model: config.model.clone(),
+            api_key_env: config.api_key_env.clone(),
+            temperature: config.temperature,
+        }
+    }
+
+    /// chat completions endpoint 전체 URL
+    fn endpoint(&self) -> String {
+        format!("{}/chat/completions", self.base_url)
+    }
+
+    /// chat completions 요청을 보내고 각 choice의 원본 응답을 반환
+    async fn request_completions(&self, full_prompt: &str, n: Option<usize>) -> Result<Vec<String>> {
+        self.check_installation().await?;
+
+        let body = ChatCompletionRequest {
+            model: &self.model,
+            messages: vec![ChatMessage { role: "user", content: full_prompt }],
+            temperature: self.temperature,
+            n,
+        };
+
+        let mut request = self.client.post(self.endpoint()).json(&body);
+        if let Some(key) = self.api_key() {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AskAiError::AiApiError(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = response.text().await.unwrap_or_default();
+            return Err(AskAiError::AiApiError(format!("HTTP {}: {}", status, error)));
+        }
+
+        let completion: ChatCompletionResponse = response
+            .json()
+            .await
+            .map_err(|e| AskAiError::AiApiError(format!("Invalid response body: {}", e)))?;
+
+        Ok(completion
+            .choices
+            .into_iter()
+            .map(|choice| choice.message.content.unwrap_or_default().trim().to_string())
+            .collect())
+    }
+
+    /// 환경 변수에서 API 키 읽기 (없으면 None)
+    fn api_key(&self) -> Option<String> {
+        if self.api_key_env.is_empty() {
+            return None;
+        }
+        std::env::var(&self.api_key_env)
+            .ok()
+            .filter(|key| !key.is_empty())
+    }
+}
+
+#[async_trait]
+impl AiProvider for OpenAiCompatibleProvider {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn cli_command(&self) -> &str {
+        // HTTP provider는 CLI가 없으므로 endpoint를 대신 노출
+        &self.base_url
+    }
+
+    async fn check_installation(&self) -> Result<()> {
+        // 설치할 CLI가 없으므로 설정값만 검증
+        if !self.base_url.starts_with("http://") && !self.base_url.starts_with("https://") {
+            return Err(AskAiError::ConfigError(format!(
+                "Invalid OpenAI-compatible base_url: '{}'\n\
+                 Set [openai] base_url in ~/.askai/config.toml (e.g. http://localhost:8000/v1)",
+                self.base_url
+            )));
+        }
+
+        if self.model.is_empty() {
+            return Err(AskAiError::ConfigError(
+                "[openai] model is not set in ~/.askai/config.toml".to_string()
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn generate_command(&self, prompt: &str, context: &str) -> Result<String> {
+        Ok(self.generate_structured(prompt, context).await?.command)
+    }
+
+    async fn generate_structured(&self, prompt: &str, context: &str) -> Result<GeneratedCommand> {
+        // 공통 프롬프트 템플릿 사용
+        let full_prompt = PromptTemplate::for_openai(prompt, context);
+
+        let raw_output = self.request_completions(&full_prompt, None)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        // 후처리: JSON 응답 계약 파싱, 실패 시 휴리스틱으로 명령어만 추출 (공통 ResponseProcessor 사용)
+        ResponseProcessor::process_structured(&raw_output)
+    }
+
+    async fn generate_candidates(&self, prompt: &str, context: &str, n: usize) -> Result<Vec<String>> {
+        // chat completions API의 `n` 파라미터로 한 번에 여러 응답 생성
+        let full_prompt = PromptTemplate::for_openai(prompt, context);
+        let raw_outputs = self.request_completions(&full_prompt, Some(n.max(1))).await?;
+
+        collect_candidates(
+            raw_outputs
+                .iter()
+                .map(|raw| ResponseProcessor::process_structured(raw).map(|g| g.command))
+                .collect()
+        )
+    }
+
+    async fn generate_script(&self, prompt: &str, context: &str) -> Result<String> {
+        let full_prompt = PromptTemplate::script_prompt(prompt, context);
+        let raw_output = self.request_completions(&full_prompt, None)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        // 여러 줄을 보존하는 스크립트 전용 후처리
+        ResponseProcessor::process_script(&raw_output)
+    }
+
+    async fn repair_command(&self, prompt: &str, failure: &CommandFailure, context: &str) -> Result<GeneratedCommand> {
+        let full_prompt = PromptTemplate::repair_prompt(prompt, failure, context);
+        let raw_output = self.request_completions(&full_prompt, None)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        // 생성과 같은 JSON 응답 계약
+        ResponseProcessor::process_structured(&raw_output)
+    }
+
+    async fn plan_tasks(&self, prompt: &str, context: &str) -> Result<Vec<PlannedTask>> {
+        let full_prompt = PromptTemplate::plan_prompt(prompt, context);
+        let raw_output = self.request_completions(&full_prompt, None)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        // 작업 목록 JSON 계약 (휴리스틱 fallback 없음)
+        ResponseProcessor::process_plan(&raw_output)
+    }
+
+    async fn explain_command(&self, command: &str, context: &str) -> Result<CommandExplanation> {
+        let full_prompt = PromptTemplate::explain_prompt(command, context);
+        let raw_output = self.request_completions(&full_prompt, None)
+            .await?
+            .into_iter()
+            .next()
+            .unwrap_or_default();
+
+        // 설명문은 ResponseProcessor가 거부하므로 전용 파서 사용
+        CommandExplanation::parse(&raw_output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::stub_server::spawn_stub_server;
+
+    fn test_config(base_url: &str) -> OpenAiConfig {
+        OpenAiConfig {
+            base_url: base_url.to_string(),
+            model: "test-model".to_string(),
+            api_key_env: "ASKAI_TEST_OPENAI_KEY".to_string(),
+            ..OpenAiConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_provider_creation() {
+        let provider = OpenAiCompatibleProvider::new(&test_config("http://localhost:8000/v1/"));
+        assert_eq!(provider.name(), "openai");
+        assert_eq!(provider.endpoint(), "http://localhost:8000/v1/chat/completions");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_base_url() {
+        let provider = OpenAiCompatibleProvider::new(&test_config("localhost:8000"));
+        assert!(provider.check_installation().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generate_command_against_stub_server() {
+        let (base_url, handle) = spawn_stub_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"role":"assistant","content":"```bash\nls -la\n```"}}]}"#,
+        ).await;
+
+        let provider = OpenAiCompatibleProvider::new(&test_config(&format!("{}/v1", base_url)));
+        let command = provider.generate_command("파일 목록", "OS: linux").await.unwrap();
+        assert_eq!(command, "ls -la");
+
+        let request = handle.await.unwrap();
+        assert!(request.starts_with("POST /v1/chat/completions"));
+        assert!(request.contains("\"model\":\"test-model\""));
+        assert!(request.contains("파일 목록"));
+    }
+
+    #[tokio::test]
+    async fn test_generate_structured_against_stub_server() {
+        let (base_url, handle) = spawn_stub_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"content":"{\"command\": \"du -sh *\", \"explanation\": \"Shows the size of each entry\", \"requires_sudo\": false, \"assumptions\": [], \"confidence\": 0.8}"}}]}"#,
+        ).await;
+
+        let provider = OpenAiCompatibleProvider::new(&test_config(&format!("{}/v1", base_url)));
+        let generated = provider.generate_structured("폴더 크기", "OS: linux").await.unwrap();
+        handle.await.unwrap();
+
+        assert_eq!(generated.command, "du -sh *");
+        assert_eq!(generated.details.explanation.as_deref(), Some("Shows the size of each entry"));
+        assert_eq!(generated.details.confidence, Some(0.8));
+    }
+
+    #[tokio::test]
+    async fn test_http_error_is_reported() {
+        let (base_url, handle) = spawn_stub_server(
+            "500 Internal Server Error",
+            r#"{"error":"model not loaded"}"#,
+        ).await;
+
+        let provider = OpenAiCompatibleProvider::new(&test_config(&format!("{}/v1", base_url)));
+        let result = provider.generate_command("파일 목록", "OS: linux").await;
+        handle.await.unwrap();
+
+        match result {
+            Err(AskAiError::AiApiError(msg)) => assert!(msg.contains("model not loaded")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generate_candidates_uses_native_n() {
+        let (base_url, handle) = spawn_stub_server(
+            "200 OK",
+            r#"{"choices":[
+                {"message":{"content":"find . -name '*.log'"}},
+                {"message":{"content":"ls *.log"}},
+                {"message":{"content":"ls *.log"}}
+            ]}"#,
+        ).await;
+
+        let provider = OpenAiCompatibleProvider::new(&test_config(&format!("{}/v1", base_url)));
+        let candidates = provider.generate_candidates("로그 파일", "OS: linux", 3).await.unwrap();
+        assert_eq!(candidates, vec!["find . -name '*.log'", "ls *.log"]);
+
+        let request = handle.await.unwrap();
+        assert!(request.contains("\"n\":3"));
+    }
+
+    #[tokio::test]
+    async fn test_explain_command_keeps_prose() {
+        let (base_url, handle) = spawn_stub_server(
+            "200 OK",
+            r#"{"choices":[{"message":{"content":"SUMMARY: Shows disk usage.\ndf => report file system usage\n-h => human-readable sizes"}}]}"#,
+        ).await;
+
+        let provider = OpenAiCompatibleProvider::new(&test_config(&format!("{}/v1", base_url)));
+        let explanation = provider.explain_command("df -h", "OS: linux").await.unwrap();
+        assert_eq!(explanation.summary, "Shows disk usage.");
+        assert_eq!(explanation.parts.len(), 2);
+
+        let request = handle.await.unwrap();
+        assert!(request.contains("Command: df -h"));
+    }
+}
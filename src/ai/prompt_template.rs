@@ -1,3 +1,5 @@
+use crate::ai::CommandFailure;
+
 /// AI provider용 프롬프트 템플릿 생성기
 ///
 /// 모든 provider가 일관된 프롬프트 형식을 사용하도록 중앙 집중화된 템플릿 관리를 제공합니다.
@@ -30,11 +32,14 @@ impl PromptTemplate {
         context: &str,
         provider_rules: Option<&str>,
     ) -> String {
-        let mut template = format!(
+        let mut template = String::from(
             "You are a bash command generator. Convert natural language to a single bash command.\n\n\
              RULES:\n\
-             - Output ONLY the bash command (no explanations, no markdown)\n\
-             - Do NOT say \"I cannot\" or similar - just output the command\n\
+             - Respond with ONLY a JSON object (no markdown, no text outside the object):\n\
+             {\"command\": \"<single-line bash command>\", \"explanation\": \"<one sentence>\", \
+             \"requires_sudo\": <true|false>, \"assumptions\": [\"<assumption>\"], \"confidence\": <0.0-1.0>}\n\
+             - Do NOT say \"I cannot\" or similar - always provide a command\n\
+             - List anything you had to guess (OS flavor, tool versions, paths) in assumptions\n\
              - Be precise and accurate\n"
         );
 
@@ -47,11 +52,187 @@ impl PromptTemplate {
             "\nContext: {}\n\
              Request: {}\n\n\
              Examples:\n\
-             \"파일 목록\" → ls -la\n\
-             \"git 상태\" → git status\n\
-             \"txt 파일 찾기\" → find . -name \"*.txt\"\n\
-             \"현재 시간\" → date\n\n\
-             Command:",
+             \"파일 목록\" → {{\"command\": \"ls -la\", \"explanation\": \"Lists all files including hidden ones\", \"requires_sudo\": false, \"assumptions\": [], \"confidence\": 0.95}}\n\
+             \"txt 파일 찾기\" → {{\"command\": \"find . -name '*.txt'\", \"explanation\": \"Finds .txt files under the current directory\", \"requires_sudo\": false, \"assumptions\": [], \"confidence\": 0.9}}\n\
+             \"패키지 업데이트\" → {{\"command\": \"sudo apt update\", \"explanation\": \"Refreshes the package index\", \"requires_sudo\": true, \"assumptions\": [\"Debian/Ubuntu (apt)\"], \"confidence\": 0.7}}\n\n\
+             JSON:",
+            context, prompt
+        ));
+
+        template
+    }
+
+    /// 여러 줄 bash 스크립트 생성을 위한 프롬프트 템플릿 생성
+    ///
+    /// 한 줄 명령어 템플릿과 달리 JSON이 아닌 스크립트 본문을 요구하며,
+    /// 응답은 `ResponseProcessor::process_script`로 해석합니다.
+    ///
+    /// # Arguments
+    /// * `prompt` - 사용자 프롬프트 (자연어)
+    /// * `context` - 실행 환경 컨텍스트 (현재 디렉토리, OS 등)
+    /// * `provider_rules` - Provider별 추가 규칙 (옵션)
+    pub fn build_script_generation_prompt(
+        prompt: &str,
+        context: &str,
+        provider_rules: Option<&str>,
+    ) -> String {
+        let mut template = String::from(
+            "You are a bash script generator. Convert natural language to a complete bash script.\n\n\
+             RULES:\n\
+             - Output ONLY the script inside a single ```bash code block (no text before or after)\n\
+             - Start with #!/usr/bin/env bash and set -euo pipefail\n\
+             - Use functions, loops, conditionals and heredocs where they make the script clearer\n\
+             - Quote variables and report failures to stderr instead of aborting silently\n\
+             - Do NOT say \"I cannot\" or similar - always provide a script\n"
+        );
+
+        // Provider별 추가 규칙이 있으면 추가
+        if let Some(rules) = provider_rules {
+            template.push_str(&format!("{}\n", rules));
+        }
+
+        template.push_str(&format!(
+            "\nContext: {}\n\
+             Request: {}\n\n\
+             Script:",
+            context, prompt
+        ));
+
+        template
+    }
+
+    /// 명령어 설명을 위한 프롬프트 템플릿 생성
+    ///
+    /// 생성 템플릿과 달리 설명문을 요구하며, 응답은 `CommandExplanation::parse`로 해석합니다.
+    ///
+    /// # Arguments
+    /// * `command` - 설명할 bash 명령어
+    /// * `context` - 실행 환경 컨텍스트 (현재 디렉토리, OS 등)
+    /// * `provider_rules` - Provider별 추가 규칙 (옵션)
+    pub fn build_command_explanation_prompt(
+        command: &str,
+        context: &str,
+        provider_rules: Option<&str>,
+    ) -> String {
+        let mut template = String::from(
+            "You are a shell expert explaining a bash command to a junior engineer.\n\n\
+             RULES:\n\
+             - First line: SUMMARY: <one sentence describing what the whole command does>\n\
+             - Then one line per token, flag or pipeline stage, in command order: <part> => <what it does>\n\
+             - Mention side effects (deleted files, network access, sudo) explicitly\n\
+             - No markdown, no code blocks\n"
+        );
+
+        // Provider별 추가 규칙이 있으면 추가
+        if let Some(rules) = provider_rules {
+            template.push_str(&format!("{}\n", rules));
+        }
+
+        template.push_str(&format!(
+            "\nContext: {}\n\
+             Command: {}\n\n\
+             Example:\n\
+             SUMMARY: Lists all files in the current directory, including hidden ones.\n\
+             ls => list directory contents\n\
+             -l => long format (permissions, owner, size, date)\n\
+             -a => include entries starting with a dot\n\n\
+             Explanation:",
+            context, command
+        ));
+
+        template
+    }
+
+    /// 실패한 명령어를 고치기 위한 프롬프트 템플릿 생성
+    ///
+    /// 원래 요청, 실패한 명령어, 종료 코드와 stderr를 함께 보내고
+    /// 생성 템플릿과 같은 JSON 응답 계약으로 고친 명령어를 요구합니다.
+    ///
+    /// # Arguments
+    /// * `prompt` - 원래 사용자 프롬프트 (자연어)
+    /// * `failure` - 실패한 명령어와 에러
+    /// * `context` - 실행 환경 컨텍스트 (현재 디렉토리, OS 등)
+    /// * `provider_rules` - Provider별 추가 규칙 (옵션)
+    pub fn build_repair_prompt(
+        prompt: &str,
+        failure: &CommandFailure,
+        context: &str,
+        provider_rules: Option<&str>,
+    ) -> String {
+        let mut template = String::from(
+            "You are a bash command generator. A command you generated failed; fix it.\n\n\
+             RULES:\n\
+             - Respond with ONLY a JSON object (no markdown, no text outside the object):\n\
+             {\"command\": \"<single-line bash command>\", \"explanation\": \"<what was wrong and what changed>\", \
+             \"requires_sudo\": <true|false>, \"assumptions\": [\"<assumption>\"], \"confidence\": <0.0-1.0>}\n\
+             - Read the error: use a different tool or flag if one is missing or unknown on this system\n\
+             - Do NOT repeat the failed command unchanged\n\
+             - If the request can't be done on this system, return a command that reports why (e.g. an echo to stderr) with low confidence\n"
+        );
+
+        // Provider별 추가 규칙이 있으면 추가
+        if let Some(rules) = provider_rules {
+            template.push_str(&format!("{}\n", rules));
+        }
+
+        let stderr = if failure.stderr.trim().is_empty() {
+            "(no output)"
+        } else {
+            failure.stderr.trim()
+        };
+        template.push_str(&format!(
+            "\nContext: {}\n\
+             Request: {}\n\
+             Failed command: {}\n\
+             Exit code: {}\n\
+             Error output:\n{}\n\n\
+             JSON:",
+            context, prompt, failure.command, failure.exit_code, stderr
+        ));
+
+        template
+    }
+
+    /// 여러 단계 작업을 의존성이 있는 작업 목록으로 나누기 위한 프롬프트 템플릿 생성
+    ///
+    /// 응답은 `ResponseProcessor::process_plan`으로 해석하며, 작업 사이의 순서는
+    /// `depends_on`으로만 표현하도록 요구합니다 (서로 독립적인 작업은 병렬 실행).
+    ///
+    /// # Arguments
+    /// * `prompt` - 사용자 프롬프트 (자연어)
+    /// * `context` - 실행 환경 컨텍스트 (현재 디렉토리, OS 등)
+    /// * `provider_rules` - Provider별 추가 규칙 (옵션)
+    pub fn build_plan_prompt(
+        prompt: &str,
+        context: &str,
+        provider_rules: Option<&str>,
+    ) -> String {
+        let mut template = String::from(
+            "You are a task planner. Split the request into bash commands that can run as separate steps.\n\n\
+             RULES:\n\
+             - Respond with ONLY a JSON object (no markdown, no text outside the object):\n\
+             {\"tasks\": [{\"id\": <number>, \"description\": \"<short step name>\", \"command\": \"<single-line bash command>\", \
+             \"working_dir\": <\"<directory relative to the current directory>\" | null>, \"depends_on\": [<ids of steps that must succeed first>]}]}\n\
+             - One step per independent unit of work; steps without a dependency between them run in parallel\n\
+             - Use depends_on for ordering, NOT `&&` chains or `cd` inside the command\n\
+             - Each step runs in a fresh shell: do not rely on variables or directory changes from other steps\n\
+             - Use at most 12 steps\n"
+        );
+
+        // Provider별 추가 규칙이 있으면 추가
+        if let Some(rules) = provider_rules {
+            template.push_str(&format!("{}\n", rules));
+        }
+
+        template.push_str(&format!(
+            "\nContext: {}\n\
+             Request: {}\n\n\
+             Example:\n\
+             \"build the frontend and backend, then run the integration tests\" → \
+             {{\"tasks\": [{{\"id\": 1, \"description\": \"Build frontend\", \"command\": \"npm run build\", \"working_dir\": \"frontend\", \"depends_on\": []}}, \
+             {{\"id\": 2, \"description\": \"Build backend\", \"command\": \"cargo build\", \"working_dir\": \"backend\", \"depends_on\": []}}, \
+             {{\"id\": 3, \"description\": \"Run integration tests\", \"command\": \"make integration-test\", \"working_dir\": null, \"depends_on\": [1, 2]}}]}}\n\n\
+             JSON:",
             context, prompt
         ));
 
@@ -84,6 +265,64 @@ impl PromptTemplate {
             Some("- Be concise and use standard Unix commands"), // Codex 전용 규칙
         )
     }
+
+    /// OpenAI 호환 HTTP provider용 프롬프트 생성
+    pub fn for_openai(prompt: &str, context: &str) -> String {
+        Self::build_command_generation_prompt(
+            prompt,
+            context,
+            None, // 모델이 다양하므로 기본 규칙만 사용
+        )
+    }
+
+    /// Ollama (로컬 모델) provider용 프롬프트 생성
+    pub fn for_ollama(prompt: &str, context: &str) -> String {
+        Self::build_command_generation_prompt(
+            prompt,
+            context,
+            Some("- Output the JSON object on a single line"), // 작은 로컬 모델은 부연 설명을 덧붙이는 경향이 있음
+        )
+    }
+
+    /// 스크립트 프롬프트 생성 (provider별 규칙 없음)
+    pub fn script_prompt(prompt: &str, context: &str) -> String {
+        Self::build_script_generation_prompt(prompt, context, None)
+    }
+
+    /// Ollama (로컬 모델) provider용 스크립트 프롬프트 생성 (짧은 스크립트 규칙 추가)
+    pub fn script_for_ollama(prompt: &str, context: &str) -> String {
+        Self::build_script_generation_prompt(
+            prompt,
+            context,
+            Some("- Keep the script short and avoid clever one-liners"), // 작은 로컬 모델의 실수 방지
+        )
+    }
+
+    /// 복구 프롬프트 생성 (provider별 규칙 없음)
+    pub fn repair_prompt(prompt: &str, failure: &CommandFailure, context: &str) -> String {
+        Self::build_repair_prompt(prompt, failure, context, None)
+    }
+
+    /// 계획 프롬프트 생성 (provider별 규칙 없음)
+    pub fn plan_prompt(prompt: &str, context: &str) -> String {
+        Self::build_plan_prompt(prompt, context, None)
+    }
+
+    /// 설명 프롬프트 생성 (provider별 규칙 없음)
+    ///
+    /// 생성 규칙(`Be concise` 등)은 설명에 맞지 않으므로 모든 provider가 같은 템플릿을 사용합니다.
+    pub fn explain_prompt(command: &str, context: &str) -> String {
+        Self::build_command_explanation_prompt(command, context, None)
+    }
+
+    /// Ollama (로컬 모델) provider용 설명 프롬프트 생성 (짧은 줄 규칙 추가)
+    pub fn explain_for_ollama(command: &str, context: &str) -> String {
+        Self::build_command_explanation_prompt(
+            command,
+            context,
+            Some("- Keep each line short"), // 작은 로컬 모델은 장황해지는 경향이 있음
+        )
+    }
 }
 
 #[cfg(test)]
@@ -102,6 +341,10 @@ mod tests {
         assert!(template.contains("Current directory: /home"));
         assert!(template.contains("RULES:"));
         assert!(template.contains("Examples:"));
+        // JSON 응답 계약의 모든 필드를 요청
+        for field in ["command", "explanation", "requires_sudo", "assumptions", "confidence"] {
+            assert!(template.contains(&format!("\"{}\"", field)));
+        }
     }
 
     #[test]
@@ -129,6 +372,78 @@ mod tests {
         assert!(template.contains("standard Unix commands"));
     }
 
+    #[test]
+    fn test_openai_template() {
+        let template = PromptTemplate::for_openai("디스크 사용량", "OS: linux");
+
+        assert!(template.contains("디스크 사용량"));
+        assert!(template.contains("OS: linux"));
+    }
+
+    #[test]
+    fn test_ollama_template() {
+        let template = PromptTemplate::for_ollama("포트 확인", "OS: linux");
+
+        assert!(template.contains("포트 확인"));
+        assert!(template.contains("single line"));
+    }
+
+    #[test]
+    fn test_explanation_template() {
+        let template = PromptTemplate::explain_prompt("ls -la", "OS: linux");
+
+        assert!(template.contains("Command: ls -la"));
+        assert!(template.contains("SUMMARY:"));
+        assert!(template.contains("=>"));
+        assert!(!template.contains("bash command generator"));
+    }
+
+    #[test]
+    fn test_script_template() {
+        let template = PromptTemplate::script_prompt("jpeg를 jpg로 이름 변경", "OS: linux");
+
+        assert!(template.contains("jpeg를 jpg로 이름 변경"));
+        assert!(template.contains("```bash"));
+        assert!(template.contains("heredocs"));
+        assert!(!template.contains("JSON"));
+    }
+
+    #[test]
+    fn test_repair_template() {
+        let failure = CommandFailure {
+            command: "fd -e log".to_string(),
+            exit_code: 127,
+            stderr: "bash: line 1: fd: command not found\n".to_string(),
+        };
+        let template = PromptTemplate::repair_prompt("로그 파일 찾기", &failure, "OS: linux");
+
+        assert!(template.contains("Request: 로그 파일 찾기"));
+        assert!(template.contains("Failed command: fd -e log"));
+        assert!(template.contains("Exit code: 127"));
+        assert!(template.contains("fd: command not found"));
+        assert!(template.contains("\"command\""));
+
+        let silent = CommandFailure { stderr: String::new(), ..failure };
+        assert!(PromptTemplate::repair_prompt("x", &silent, "").contains("(no output)"));
+    }
+
+    #[test]
+    fn test_plan_template() {
+        let template = PromptTemplate::plan_prompt("프론트엔드와 백엔드 빌드 후 통합 테스트", "OS: linux");
+
+        assert!(template.contains("Request: 프론트엔드와 백엔드 빌드 후 통합 테스트"));
+        assert!(template.contains("\"depends_on\""));
+        assert!(template.contains("\"depends_on\": [1, 2]"));
+    }
+
+    #[test]
+    fn test_ollama_explanation_template() {
+        let template = PromptTemplate::explain_for_ollama("df -h", "OS: linux");
+
+        assert!(template.contains("Command: df -h"));
+        assert!(template.contains("Keep each line short"));
+    }
+
     #[test]
     fn test_template_with_provider_rules() {
         let template = PromptTemplate::build_command_generation_prompt(
@@ -1,12 +1,96 @@
 use crate::error::{AskAiError, Result};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 
 /// 사전 컴파일된 정규표현식 (성능 최적화)
 static CODE_BLOCK_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"```(?:bash|sh)?\n(.*?)\n```").unwrap()
 });
 
+/// 여러 줄 코드 블록 (스크립트 모드)
+static SCRIPT_BLOCK_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)```(?:bash|sh|shell)?[ \t]*\n(.*?)\n?```").unwrap()
+});
+
+/// 스크립트에 shebang이 없을 때 추가하는 기본값
+const DEFAULT_SHEBANG: &str = "#!/usr/bin/env bash";
+
+/// 계획 하나에 허용하는 최대 작업 수 (계획 프롬프트의 규칙과 같음)
+const MAX_PLAN_TASKS: usize = 12;
+
+/// AI가 명령어와 함께 돌려주는 부가 정보 (JSON 응답 계약의 나머지 필드)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandDetails {
+    /// 명령어가 하는 일 (한 문장)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    /// root 권한이 필요한지
+    #[serde(default)]
+    pub requires_sudo: bool,
+    /// AI가 가정한 사항 (예: "GNU find", "현재 디렉토리가 git 저장소")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assumptions: Vec<String>,
+    /// AI의 자신감 (0.0 ~ 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// 생성된 명령어와 부가 정보
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratedCommand {
+    pub command: String,
+    pub details: CommandDetails,
+}
+
+impl GeneratedCommand {
+    /// 부가 정보 없이 명령어만 있는 경우 (캐시, 데몬, 휴리스틱 fallback)
+    pub fn from_command(command: String) -> Self {
+        Self {
+            command,
+            details: CommandDetails::default(),
+        }
+    }
+}
+
+/// 계획 모드에서 AI가 나눈 작업 하나
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PlannedTask {
+    /// 작업 ID (`depends_on`에서 참조)
+    pub id: usize,
+    /// 짧은 작업 이름
+    #[serde(default)]
+    pub description: String,
+    /// 실행할 한 줄 명령어
+    pub command: String,
+    /// 작업 디렉토리 (현재 디렉토리 기준, 없으면 현재 디렉토리)
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    /// 먼저 성공해야 하는 작업 ID
+    #[serde(default)]
+    pub depends_on: Vec<usize>,
+}
+
+/// 계획 JSON 응답 원본 (검증 전)
+#[derive(Debug, Deserialize)]
+struct RawPlanResponse {
+    tasks: Vec<PlannedTask>,
+}
+
+/// JSON 응답 원본 (검증 전)
+#[derive(Debug, Deserialize)]
+struct RawStructuredResponse {
+    command: String,
+    #[serde(default)]
+    explanation: Option<String>,
+    #[serde(default)]
+    requires_sudo: bool,
+    #[serde(default)]
+    assumptions: Vec<String>,
+    #[serde(default)]
+    confidence: Option<f32>,
+}
+
 /// AI provider 응답을 후처리하여 실제 bash 명령어만 추출하는 공통 모듈
 pub struct ResponseProcessor;
 
@@ -113,6 +197,182 @@ impl ResponseProcessor {
 
         Ok(command)
     }
+
+    /// 스크립트 모드 응답에서 스크립트 본문을 추출
+    ///
+    /// `process`와 달리 여러 줄을 그대로 보존합니다 (heredoc, 함수, 제어문).
+    /// 코드 블록이 있으면 첫 번째 블록만 사용하고, shebang이 없으면 추가합니다.
+    pub fn process_script(raw: &str) -> Result<String> {
+        // 1. 메타 응답 감지 (코드 블록이 없을 때만: 스크립트 안의 문자열은 허용)
+        let script = match SCRIPT_BLOCK_REGEX.captures(raw) {
+            Some(caps) => caps[1].to_string(),
+            None => {
+                let lower = raw.to_lowercase();
+                if ["i cannot", "i can't", "i'm sorry", "as an ai"].iter().any(|p| lower.starts_with(p)) {
+                    return Err(AskAiError::AiCliError(format!(
+                        "AI returned an explanation instead of a script: {}\n\
+                         Please try again with a clearer prompt.",
+                        raw
+                    )));
+                }
+                raw.to_string()
+            }
+        };
+
+        // 2. 앞뒤 빈 줄만 제거 (들여쓰기는 보존)
+        let script = script.trim_matches('\n').trim_end().to_string();
+
+        if script.trim().is_empty() {
+            return Err(AskAiError::AiCliError(
+                "AI returned an empty script. Please try again.".to_string()
+            ));
+        }
+
+        // 3. shebang 추가 (파일로 저장해 바로 실행할 수 있도록)
+        if script.starts_with("#!") {
+            Ok(script)
+        } else {
+            Ok(format!("{}\n{}", DEFAULT_SHEBANG, script))
+        }
+    }
+
+    /// JSON 응답 계약에 따라 명령어와 부가 정보를 추출
+    ///
+    /// JSON 파싱이나 검증에 실패하면 기존 휴리스틱(`process`)으로 fallback합니다.
+    ///
+    /// # Examples
+    /// ```
+    /// use askai::ai::response_processor::ResponseProcessor;
+    ///
+    /// let result = ResponseProcessor::process_structured(r#"{"command": "ls -la", "confidence": 0.9}"#);
+    /// assert_eq!(result.unwrap().command, "ls -la");
+    /// ```
+    pub fn process_structured(raw: &str) -> Result<GeneratedCommand> {
+        match Self::parse_json(raw) {
+            Some(generated) => generated,
+            None => Self::process(raw).map(GeneratedCommand::from_command),
+        }
+    }
+
+    /// 계획 모드 응답에서 작업 목록을 추출하고 검증
+    ///
+    /// 한 줄 명령어와 달리 휴리스틱 fallback이 없으므로 JSON 계약을 어기면 에러를 반환합니다.
+    /// ID 중복, 없는 작업에 대한 의존성, 자기 자신에 대한 의존성을 거부합니다
+    /// (순환 의존성은 `ExecutionPlan::check_dependencies`에서 검사).
+    pub fn process_plan(raw: &str) -> Result<Vec<PlannedTask>> {
+        let invalid = |reason: String| AskAiError::AiCliError(format!(
+            "AI returned an invalid plan: {}\n\
+             Please try again with a clearer prompt.",
+            reason
+        ));
+
+        // ```json 코드 블록이나 앞뒤 설명이 붙어도 객체 부분만 사용
+        let (Some(start), Some(end)) = (raw.find('{'), raw.rfind('}')) else {
+            return Err(invalid("no JSON object in the response".to_string()));
+        };
+        if end < start {
+            return Err(invalid("no JSON object in the response".to_string()));
+        }
+        let parsed: RawPlanResponse = serde_json::from_str(&raw[start..=end])
+            .map_err(|e| invalid(e.to_string()))?;
+
+        if parsed.tasks.is_empty() {
+            return Err(invalid("the plan has no steps".to_string()));
+        }
+        if parsed.tasks.len() > MAX_PLAN_TASKS {
+            return Err(invalid(format!("{} steps (at most {})", parsed.tasks.len(), MAX_PLAN_TASKS)));
+        }
+
+        let ids: Vec<usize> = parsed.tasks.iter().map(|task| task.id).collect();
+        let mut tasks = Vec::with_capacity(parsed.tasks.len());
+
+        for (index, mut task) in parsed.tasks.into_iter().enumerate() {
+            if ids[..index].contains(&task.id) {
+                return Err(invalid(format!("step {} appears twice", task.id)));
+            }
+
+            task.command = task.command.trim().to_string();
+            if task.command.is_empty() || task.command.contains('\n') {
+                return Err(invalid(format!("step {} needs a single-line command", task.id)));
+            }
+
+            if let Some(dep) = task.depends_on.iter().find(|dep| !ids.contains(dep) || **dep == task.id) {
+                return Err(invalid(format!("step {} depends on unknown step {}", task.id, dep)));
+            }
+            task.depends_on.sort_unstable();
+            task.depends_on.dedup();
+
+            task.description = task.description.trim().to_string();
+            if task.description.is_empty() {
+                task.description = task.command.clone();
+            }
+            // 빈 문자열이나 "."은 현재 디렉토리
+            task.working_dir = task.working_dir
+                .map(|dir| dir.trim().to_string())
+                .filter(|dir| !dir.is_empty() && dir != ".");
+
+            tasks.push(task);
+        }
+
+        Ok(tasks)
+    }
+
+    /// 응답에서 JSON 객체를 찾아 파싱하고 검증
+    ///
+    /// JSON이 아니면 None, JSON이지만 계약을 어기면 `Some(Err)`를 반환합니다.
+    fn parse_json(raw: &str) -> Option<Result<GeneratedCommand>> {
+        // ```json 코드 블록이나 앞뒤 설명이 붙어도 객체 부분만 사용
+        let start = raw.find('{')?;
+        let end = raw.rfind('}')?;
+        if end < start {
+            return None;
+        }
+
+        let parsed: RawStructuredResponse = serde_json::from_str(&raw[start..=end]).ok()?;
+
+        // 명령어는 비어 있지 않은 한 줄이어야 함
+        let command = parsed.command.trim().to_string();
+        if command.is_empty() {
+            return Some(Err(AskAiError::AiCliError(
+                "AI returned an empty command. Please try again.".to_string()
+            )));
+        }
+        if command.contains('\n') {
+            return Some(Err(AskAiError::AiCliError(format!(
+                "AI returned a multi-line command: {}\n\
+                 Please try again with a clearer prompt.",
+                command
+            ))));
+        }
+
+        let explanation = parsed.explanation
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty());
+
+        let assumptions = parsed.assumptions
+            .into_iter()
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect();
+
+        // 모델이 퍼센트(0~100)로 답하는 경우 보정, 범위를 벗어나면 버림
+        let confidence = parsed.confidence
+            .map(|c| if c > 1.0 && c <= 100.0 { c / 100.0 } else { c })
+            .filter(|c| (0.0..=1.0).contains(c));
+
+        // sudo가 명령어에 있으면 AI 응답과 무관하게 표시
+        let requires_sudo = parsed.requires_sudo || command.split_whitespace().any(|t| t == "sudo");
+
+        Some(Ok(GeneratedCommand {
+            command,
+            details: CommandDetails {
+                explanation,
+                requires_sudo,
+                assumptions,
+                confidence,
+            },
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -189,4 +449,102 @@ mod tests {
         assert!(cmd.contains("find"));
         assert!(cmd.contains("*.txt"));
     }
+
+    #[test]
+    fn test_structured_response() {
+        let raw = r#"{"command": "find . -name '*.log' -mtime +7", "explanation": "Finds logs older than a week",
+                      "requires_sudo": false, "assumptions": ["GNU find"], "confidence": 0.85}"#;
+
+        let generated = ResponseProcessor::process_structured(raw).unwrap();
+        assert_eq!(generated.command, "find . -name '*.log' -mtime +7");
+        assert_eq!(generated.details.explanation.as_deref(), Some("Finds logs older than a week"));
+        assert_eq!(generated.details.assumptions, vec!["GNU find"]);
+        assert_eq!(generated.details.confidence, Some(0.85));
+        assert!(!generated.details.requires_sudo);
+    }
+
+    #[test]
+    fn test_structured_response_in_code_block() {
+        let raw = "```json\n{\"command\": \"sudo apt update\", \"confidence\": 90}\n```";
+
+        let generated = ResponseProcessor::process_structured(raw).unwrap();
+        assert_eq!(generated.command, "sudo apt update");
+        // 명령어에 sudo가 있으면 requires_sudo 보정, 퍼센트 confidence 보정
+        assert!(generated.details.requires_sudo);
+        assert_eq!(generated.details.confidence, Some(0.9));
+    }
+
+    #[test]
+    fn test_structured_falls_back_to_heuristics() {
+        // JSON이 아니면 기존 휴리스틱 사용
+        let generated = ResponseProcessor::process_structured("```bash\ngit status\n```").unwrap();
+        assert_eq!(generated.command, "git status");
+        assert_eq!(generated.details, CommandDetails::default());
+
+        // JSON이지만 명령어가 비어 있으면 fallback하지 않고 에러
+        assert!(ResponseProcessor::process_structured(r#"{"command": ""}"#).is_err());
+    }
+
+    #[test]
+    fn test_plan_response() {
+        let raw = r#"```json
+{"tasks": [
+  {"id": 1, "description": "Build frontend", "command": "npm run build", "working_dir": "frontend", "depends_on": []},
+  {"id": 2, "description": "", "command": "cargo build", "working_dir": ".", "depends_on": []},
+  {"id": 3, "description": "Run integration tests", "command": "make integration-test", "depends_on": [2, 1, 2]}
+]}
+```"#;
+
+        let tasks = ResponseProcessor::process_plan(raw).unwrap();
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].working_dir.as_deref(), Some("frontend"));
+        // 설명이 없으면 명령어, "."은 현재 디렉토리
+        assert_eq!(tasks[1].description, "cargo build");
+        assert_eq!(tasks[1].working_dir, None);
+        assert_eq!(tasks[2].depends_on, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_invalid_plan_response() {
+        let step = |id: usize, command: &str, deps: &str| {
+            format!(r#"{{"id": {}, "command": "{}", "depends_on": [{}]}}"#, id, command, deps)
+        };
+        let plan = |steps: Vec<String>| format!(r#"{{"tasks": [{}]}}"#, steps.join(","));
+
+        assert!(ResponseProcessor::process_plan("ls -la").is_err());
+        assert!(ResponseProcessor::process_plan(r#"{"tasks": []}"#).is_err());
+        // ID 중복, 없는 작업/자기 자신에 대한 의존성, 빈 명령어
+        assert!(ResponseProcessor::process_plan(&plan(vec![step(1, "ls", ""), step(1, "pwd", "")])).is_err());
+        assert!(ResponseProcessor::process_plan(&plan(vec![step(1, "ls", "7")])).is_err());
+        assert!(ResponseProcessor::process_plan(&plan(vec![step(1, "ls", "1")])).is_err());
+        assert!(ResponseProcessor::process_plan(&plan(vec![step(1, " ", "")])).is_err());
+
+        let too_many = (0..=MAX_PLAN_TASKS).map(|id| step(id, "ls", "")).collect();
+        assert!(ResponseProcessor::process_plan(&plan(too_many)).is_err());
+    }
+
+    #[test]
+    fn test_script_preserves_multiline_body() {
+        let raw = "Here is the script:\n```bash\n#!/usr/bin/env bash\nset -euo pipefail\n\nrename() {\n  mv \"$1\" \"${1%.jpeg}.jpg\"\n}\n\nfor f in *.jpeg; do\n  rename \"$f\" || echo \"failed: $f\" >&2\ndone\n```\nThis renames files.";
+
+        let script = ResponseProcessor::process_script(raw).unwrap();
+        assert!(script.starts_with("#!/usr/bin/env bash\nset -euo pipefail"));
+        assert!(script.contains("rename() {\n  mv"));
+        assert!(script.ends_with("done"));
+        assert!(!script.contains("This renames files"));
+    }
+
+    #[test]
+    fn test_script_adds_shebang_and_keeps_heredoc() {
+        let raw = "cat <<EOF > out.txt\nhello\nEOF";
+
+        let script = ResponseProcessor::process_script(raw).unwrap();
+        assert_eq!(script, "#!/usr/bin/env bash\ncat <<EOF > out.txt\nhello\nEOF");
+    }
+
+    #[test]
+    fn test_script_rejects_refusal() {
+        assert!(ResponseProcessor::process_script("I cannot write scripts").is_err());
+        assert!(ResponseProcessor::process_script("```bash\n\n```").is_err());
+    }
 }
@@ -0,0 +1,608 @@
+//! 승인되고 실행된 명령어의 감사 로그
+//!
+//! `~/.askai/audit/<UTC 날짜>.jsonl`에 한 줄에 하나씩 기록을 덧붙입니다.
+//! 각 기록은 직전 기록의 SHA-256(`prev_hash`)을 담고 있어서, 중간 기록을 고치거나 지우면
+//! 사슬이 끊어집니다. 마지막 기록의 번호와 해시는 `HEAD`에 따로 저장해 끝부분이 잘린 것도 찾습니다.
+//!
+//! 로그 전체를 처음부터 다시 계산해 쓰는 변조는 찾을 수 없으므로,
+//! 엄격한 감사가 필요하면 `HEAD`를 주기적으로 다른 곳에 보관하세요.
+
+use crate::error::{AskAiError, Result};
+use crate::executor::validator::{DangerLevel, RiskCategory, ValidationReport};
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 첫 기록의 `prev_hash`
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// 마지막 기록의 번호와 해시를 담은 파일
+const HEAD_FILE: &str = "HEAD";
+
+/// 동시에 실행된 askai가 사슬을 갈라놓지 않도록 잡는 잠금 파일
+const LOCK_FILE: &str = ".lock";
+
+/// 명령어를 누가 어떻게 승인했는지
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Approval {
+    /// 확인 화면에서 사용자가 직접 승인
+    Interactive,
+    /// `--yes` 플래그로 확인 생략
+    YesFlag,
+    /// `auto_approve_safe_commands` 설정으로 Low 명령어 자동 승인
+    AutoApprove,
+    /// 승인되지 않음 (취소, 거부, 시뮬레이션)
+    None,
+}
+
+impl Approval {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Approval::Interactive => "interactive",
+            Approval::YesFlag => "--yes",
+            Approval::AutoApprove => "auto-approved (safe command)",
+            Approval::None => "not approved",
+        }
+    }
+}
+
+/// 승인 이후의 결과
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "kebab-case")]
+pub enum Outcome {
+    /// stdout으로 출력해 셸(`eval`)에 넘김
+    Printed,
+    /// 스크립트 파일로 저장
+    Written { path: String },
+    /// 클립보드로 복사 (`askai chat`)
+    Copied,
+    /// askai가 직접 실행해 성공
+    Succeeded,
+    /// askai가 직접 실행해 실패
+    Failed { error: String },
+    /// `--dry-run` 샌드박스에서만 실행
+    Simulated { exit_code: i32 },
+    /// 사용자가 취소
+    Cancelled,
+    /// 자동 승인 거부 (Critical 명령어, quiet 모드에서 `--yes` 없음)
+    Refused { reason: String },
+}
+
+impl Outcome {
+    /// 명령어를 넘긴 결과 (`None`이면 eval용으로 출력, 있으면 askai가 직접 실행한 exit code)
+    pub fn from_exit_code(exit_code: Option<i32>) -> Self {
+        match exit_code {
+            None => Outcome::Printed,
+            Some(0) => Outcome::Succeeded,
+            Some(code) => Outcome::Failed { error: format!("exit code {}", code) },
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Outcome::Printed => "printed for the shell".to_string(),
+            Outcome::Written { path } => format!("written to {}", path),
+            Outcome::Copied => "copied to the clipboard".to_string(),
+            Outcome::Succeeded => "executed successfully".to_string(),
+            Outcome::Failed { error } => format!("failed: {}", error.lines().next().unwrap_or_default()),
+            Outcome::Simulated { exit_code } => format!("simulated in the sandbox (exit {})", exit_code),
+            Outcome::Cancelled => "cancelled".to_string(),
+            Outcome::Refused { reason } => format!("refused: {}", reason),
+        }
+    }
+}
+
+/// 검증기가 찾은 위험 (감사 로그용 요약)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditFinding {
+    pub level: DangerLevel,
+    pub rule: String,
+    pub reason: String,
+    pub category: RiskCategory,
+}
+
+/// 감사 로그 한 줄
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// 1부터 시작하는 일련번호
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    /// 실행한 사용자 (`$USER`)
+    pub user: String,
+    pub cwd: String,
+    /// 프로젝트 루트 (`.git`이 있는 가장 가까운 상위 디렉토리, 없으면 cwd)
+    pub project: String,
+    pub prompt: String,
+    pub command: String,
+    pub provider: String,
+    pub level: DangerLevel,
+    pub findings: Vec<AuditFinding>,
+    pub approval: Approval,
+    pub outcome: Outcome,
+    /// 직전 기록의 해시
+    pub prev_hash: String,
+    /// 이 기록의 해시 (`hash`를 뺀 나머지 필드의 SHA-256)
+    pub hash: String,
+}
+
+impl AuditRecord {
+    /// 현재 사용자와 디렉토리로 새 기록 생성 (번호와 해시는 `AuditLog::append`에서 채움)
+    pub fn new(
+        prompt: &str,
+        command: &str,
+        provider: &str,
+        report: &ValidationReport,
+        approval: Approval,
+        outcome: Outcome,
+    ) -> Self {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("LOGNAME"))
+            .unwrap_or_else(|_| "unknown".to_string());
+
+        Self {
+            seq: 0,
+            timestamp: Utc::now(),
+            user,
+            project: project_root(&cwd).display().to_string(),
+            cwd: cwd.display().to_string(),
+            prompt: prompt.to_string(),
+            command: command.to_string(),
+            provider: provider.to_string(),
+            level: report.level,
+            findings: report
+                .findings
+                .iter()
+                .map(|f| AuditFinding {
+                    level: f.level,
+                    rule: f.rule.clone(),
+                    reason: f.reason.clone(),
+                    category: f.category,
+                })
+                .collect(),
+            approval,
+            outcome,
+            prev_hash: String::new(),
+            hash: String::new(),
+        }
+    }
+
+    /// 다른 디렉토리에서 실행된 명령어의 기록 (배치 모드의 프로젝트별 작업)
+    pub fn with_cwd(mut self, cwd: &Path) -> Self {
+        self.project = project_root(cwd).display().to_string();
+        self.cwd = cwd.display().to_string();
+        self
+    }
+
+    /// `hash`를 뺀 나머지 필드의 해시
+    fn compute_hash(&self) -> Result<String> {
+        hash_value(serde_json::to_value(self)?)
+    }
+}
+
+/// 감사 로그에 기록 (실패해도 명령어 처리는 막지 않고 경고만 출력)
+pub fn log(record: AuditRecord) {
+    if let Err(e) = AuditLog::new().append(record) {
+        eprintln!("{} Failed to write the audit log: {}", "[!]".yellow(), e);
+    }
+}
+
+/// 무결성 검사에서 찾은 문제
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// 로그 파일 (`HEAD` 문제면 HEAD 파일)
+    pub file: PathBuf,
+    /// 1부터 시작하는 줄 번호 (파일 전체의 문제면 None)
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// 무결성 검사 결과
+#[derive(Debug, Default)]
+pub struct Verification {
+    /// 읽은 기록 수
+    pub records: usize,
+    pub files: usize,
+    pub problems: Vec<Problem>,
+}
+
+impl Verification {
+    pub fn is_intact(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// 감사 로그 디렉토리
+pub struct AuditLog {
+    dir: PathBuf,
+}
+
+impl AuditLog {
+    /// `~/.askai/audit`
+    pub fn new() -> Self {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        Self::with_dir(PathBuf::from(home).join(".askai").join("audit"))
+    }
+
+    pub fn with_dir(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// 기록에 번호와 해시 사슬을 채워 덧붙임
+    pub fn append(&self, mut record: AuditRecord) -> Result<AuditRecord> {
+        fs::create_dir_all(&self.dir)?;
+
+        // 1. 다른 askai 프로세스와 겹치지 않도록 잠금
+        let lock = open_private(&self.dir.join(LOCK_FILE), false)?;
+        lock.lock()?;
+
+        // 2. 직전 기록에 이어 붙임
+        let (seq, prev_hash) = self.head()?;
+        record.seq = seq + 1;
+        record.prev_hash = prev_hash;
+        record.hash = record.compute_hash()?;
+
+        let path = self.dir.join(format!("{}.jsonl", record.timestamp.format("%Y-%m-%d")));
+        let mut file = open_private(&path, true)?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        file.sync_data()?;
+
+        // 3. HEAD 갱신 (임시 파일에 쓰고 이름을 바꿔 중간 상태가 남지 않게 함)
+        let temp = self.dir.join(format!("{}.tmp", HEAD_FILE));
+        let mut head = open_private(&temp, false)?;
+        head.set_len(0)?;
+        writeln!(head, "{} {}", record.seq, record.hash)?;
+        head.sync_data()?;
+        fs::rename(&temp, self.dir.join(HEAD_FILE))?;
+
+        Ok(record)
+    }
+
+    /// 마지막 기록의 (번호, 해시) - HEAD가 없으면 마지막 로그 줄에서 읽음
+    fn head(&self) -> Result<(u64, String)> {
+        if let Some(head) = self.read_head()? {
+            return Ok(head);
+        }
+
+        let last = self
+            .log_files()?
+            .last()
+            .map(fs::read_to_string)
+            .transpose()?
+            .and_then(|content| content.lines().rev().find(|l| !l.trim().is_empty()).map(str::to_string));
+
+        match last {
+            Some(line) => {
+                let record: AuditRecord = serde_json::from_str(&line)?;
+                Ok((record.seq, record.hash))
+            }
+            None => Ok((0, GENESIS_HASH.to_string())),
+        }
+    }
+
+    fn read_head(&self) -> Result<Option<(u64, String)>> {
+        let path = self.dir.join(HEAD_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)?;
+        let parsed = content
+            .split_once(' ')
+            .and_then(|(seq, hash)| Some((seq.parse().ok()?, hash.trim().to_string())));
+        parsed
+            .map(Some)
+            .ok_or_else(|| AskAiError::ExecutionError(format!("corrupt audit HEAD file: {}", path.display())))
+    }
+
+    /// 날짜 순서의 로그 파일 목록
+    fn log_files(&self) -> Result<Vec<PathBuf>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut files: Vec<PathBuf> = fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+            .collect();
+        files.sort();
+        Ok(files)
+    }
+
+    /// 해시 사슬, 일련번호, HEAD를 검사해 고치거나 지운 기록 찾기
+    pub fn verify(&self) -> Result<Verification> {
+        let mut verification = Verification::default();
+        let mut expected_seq = 1;
+        let mut prev_hash = GENESIS_HASH.to_string();
+
+        for file in self.log_files()? {
+            verification.files += 1;
+            let content = fs::read_to_string(&file)?;
+
+            for (index, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let mut problem = |message: String| {
+                    verification.problems.push(Problem {
+                        file: file.clone(),
+                        line: Some(index + 1),
+                        message,
+                    })
+                };
+
+                // 1. 기록 자체의 해시
+                let value: serde_json::Value = match serde_json::from_str(line) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        problem(format!("not a valid record ({})", e));
+                        continue;
+                    }
+                };
+                let stored_hash = value.get("hash").and_then(|h| h.as_str()).unwrap_or_default().to_string();
+                let seq = value.get("seq").and_then(|s| s.as_u64()).unwrap_or(0);
+                if hash_value(value.clone())? != stored_hash {
+                    problem(format!("record #{} was modified (hash mismatch)", seq));
+                }
+
+                // 2. 직전 기록과의 연결
+                if seq != expected_seq {
+                    if seq > expected_seq {
+                        problem(format!("{} missing", describe_range(expected_seq, seq - 1, "is", "are")));
+                    } else {
+                        problem(format!("record #{} is out of order or duplicated", seq));
+                    }
+                } else if value.get("prev_hash").and_then(|h| h.as_str()) != Some(prev_hash.as_str()) {
+                    problem(format!("record #{} doesn't follow the previous record (chain broken)", seq));
+                }
+
+                verification.records += 1;
+                expected_seq = seq + 1;
+                prev_hash = stored_hash;
+            }
+        }
+
+        // 3. 끝부분이 잘리지 않았는지
+        let head_path = self.dir.join(HEAD_FILE);
+        match self.read_head() {
+            Ok(Some((seq, hash))) => {
+                let last_seq = expected_seq - 1;
+                if seq > last_seq {
+                    verification.problems.push(Problem {
+                        file: head_path,
+                        line: None,
+                        message: format!(
+                            "{} deleted from the end of the log",
+                            describe_range(last_seq + 1, seq, "was", "were")
+                        ),
+                    });
+                } else if seq < last_seq || hash != prev_hash {
+                    verification.problems.push(Problem {
+                        file: head_path,
+                        line: None,
+                        message: format!("HEAD points to record #{} but the log ends at #{}", seq, last_seq),
+                    });
+                }
+            }
+            Ok(None) if verification.records > 0 => verification.problems.push(Problem {
+                file: head_path,
+                line: None,
+                message: "HEAD file is missing".to_string(),
+            }),
+            Ok(None) => {}
+            Err(e) => verification.problems.push(Problem {
+                file: head_path,
+                line: None,
+                message: e.to_string(),
+            }),
+        }
+
+        Ok(verification)
+    }
+
+    /// 조건에 맞는 기록 (오래된 것부터, 읽을 수 없는 줄은 건너뜀)
+    pub fn query(&self, filter: &AuditFilter) -> Result<Vec<AuditRecord>> {
+        let mut records = Vec::new();
+        for file in self.log_files()? {
+            // 파일 이름의 날짜로 범위 밖의 파일은 읽지 않음
+            let date = file
+                .file_stem()
+                .and_then(|stem| NaiveDate::parse_from_str(&stem.to_string_lossy(), "%Y-%m-%d").ok());
+            if date.is_some_and(|date| !filter.includes_date(date)) {
+                continue;
+            }
+
+            for line in fs::read_to_string(&file)?.lines() {
+                if let Ok(record) = serde_json::from_str::<AuditRecord>(line) {
+                    if filter.matches(&record) {
+                        records.push(record);
+                    }
+                }
+            }
+        }
+        Ok(records)
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `askai audit show` 조건 (날짜는 UTC 기준, 양 끝 포함)
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    /// 프로젝트 경로나 디렉토리 이름
+    pub project: Option<String>,
+}
+
+impl AuditFilter {
+    fn includes_date(&self, date: NaiveDate) -> bool {
+        self.since.is_none_or(|since| date >= since) && self.until.is_none_or(|until| date <= until)
+    }
+
+    fn matches(&self, record: &AuditRecord) -> bool {
+        let in_project = self.project.as_deref().is_none_or(|project| {
+            let project_path = Path::new(&record.project);
+            let wanted = std::fs::canonicalize(project).unwrap_or_else(|_| PathBuf::from(project));
+            project_path == wanted
+                || Path::new(&record.cwd).starts_with(&wanted)
+                || project_path.file_name().is_some_and(|name| name == project)
+        });
+
+        in_project && self.includes_date(record.timestamp.date_naive())
+    }
+}
+
+/// 필드 순서와 상관없는 해시 (`hash` 필드는 제외)
+fn hash_value(mut value: serde_json::Value) -> Result<String> {
+    if let Some(object) = value.as_object_mut() {
+        object.remove("hash");
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(serde_json::to_string(&value)?.as_bytes());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// 빠진 기록 범위 설명 (`record #3 is`, `records #3..#5 are`)
+fn describe_range(first: u64, last: u64, singular: &str, plural: &str) -> String {
+    if first == last {
+        format!("record #{} {}", first, singular)
+    } else {
+        format!("records #{}..#{} {}", first, last, plural)
+    }
+}
+
+/// `.git`이 있는 가장 가까운 상위 디렉토리 (없으면 cwd)
+fn project_root(cwd: &Path) -> PathBuf {
+    cwd.ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .unwrap_or(cwd)
+        .to_path_buf()
+}
+
+/// 소유자만 읽고 쓸 수 있는 파일 열기
+fn open_private(path: &Path, append: bool) -> Result<File> {
+    let mut options = OpenOptions::new();
+    options.create(true);
+    if append {
+        options.append(true);
+    } else {
+        options.write(true);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    Ok(options.open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::CommandValidator;
+
+    fn temp_log(name: &str) -> AuditLog {
+        let dir = std::env::temp_dir().join(format!("askai-audit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        AuditLog::with_dir(dir)
+    }
+
+    fn record(command: &str, approval: Approval, outcome: Outcome) -> AuditRecord {
+        let report = CommandValidator::new().assess(command);
+        AuditRecord::new("test", command, "fake", &report, approval, outcome)
+    }
+
+    /// 기록 세 개를 쓰고 (로그 파일, 줄 목록) 반환
+    fn write_three(log: &AuditLog) -> (PathBuf, Vec<String>) {
+        log.append(record("ls", Approval::AutoApprove, Outcome::Printed)).unwrap();
+        log.append(record("rm -rf build", Approval::Interactive, Outcome::Succeeded)).unwrap();
+        let last = log.append(record("sudo reboot", Approval::None, Outcome::Cancelled)).unwrap();
+
+        let file = log.dir.join(format!("{}.jsonl", last.timestamp.format("%Y-%m-%d")));
+        let lines = fs::read_to_string(&file).unwrap().lines().map(str::to_string).collect();
+        (file, lines)
+    }
+
+    #[test]
+    fn test_append_chains_records() {
+        let log = temp_log("chain");
+        let (_, lines) = write_three(&log);
+
+        let records: Vec<AuditRecord> = lines.iter().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(records.iter().map(|r| r.seq).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_eq!(records[0].prev_hash, GENESIS_HASH);
+        assert_eq!(records[1].prev_hash, records[0].hash);
+        assert_eq!(records[1].level, DangerLevel::High);
+        assert!(!records[1].findings.is_empty());
+
+        let verification = log.verify().unwrap();
+        assert!(verification.is_intact(), "{:?}", verification.problems);
+        assert_eq!(verification.records, 3);
+
+        fs::remove_dir_all(&log.dir).unwrap();
+    }
+
+    #[test]
+    fn test_verify_detects_edits_and_deletions() {
+        // 중간 기록 수정
+        let log = temp_log("edit");
+        let (file, lines) = write_three(&log);
+        let edited = lines[1].replace("rm -rf build", "ls build");
+        fs::write(&file, format!("{}\n{}\n{}\n", lines[0], edited, lines[2])).unwrap();
+        let problems = log.verify().unwrap().problems;
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("#2 was modified"));
+        fs::remove_dir_all(&log.dir).unwrap();
+
+        // 중간 기록 삭제
+        let log = temp_log("delete");
+        let (file, lines) = write_three(&log);
+        fs::write(&file, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        let problems = log.verify().unwrap().problems;
+        assert!(problems[0].message.contains("record #2 is missing"), "{:?}", problems);
+        fs::remove_dir_all(&log.dir).unwrap();
+
+        // 마지막 기록 삭제
+        let log = temp_log("truncate");
+        let (file, lines) = write_three(&log);
+        fs::write(&file, format!("{}\n{}\n", lines[0], lines[1])).unwrap();
+        let problems = log.verify().unwrap().problems;
+        assert!(problems[0].message.contains("deleted from the end"), "{:?}", problems);
+        fs::remove_dir_all(&log.dir).unwrap();
+    }
+
+    #[test]
+    fn test_query_by_date_and_project() {
+        let log = temp_log("query");
+        write_three(&log);
+        let today = Utc::now().date_naive();
+
+        let all = log.query(&AuditFilter::default()).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let filter = AuditFilter { since: today.succ_opt(), ..AuditFilter::default() };
+        assert!(log.query(&filter).unwrap().is_empty());
+
+        let project = Path::new(&all[0].project).file_name().unwrap().to_string_lossy().to_string();
+        let filter = AuditFilter { until: Some(today), project: Some(project), ..AuditFilter::default() };
+        assert_eq!(log.query(&filter).unwrap().len(), 3);
+
+        let filter = AuditFilter { project: Some("no-such-project".to_string()), ..AuditFilter::default() };
+        assert!(log.query(&filter).unwrap().is_empty());
+
+        fs::remove_dir_all(&log.dir).unwrap();
+    }
+}
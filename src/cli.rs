@@ -1,15 +1,23 @@
-use clap::Parser;
+use chrono::NaiveDate;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use std::ffi::OsString;
 
 #[derive(Parser, Debug)]
 #[command(name = "askai")]
 #[command(version)]
 #[command(about = "AI-powered terminal automation", long_about = None)]
+#[command(subcommand_negates_reqs = true, disable_help_subcommand = true)]
 pub struct Cli {
+    /// 하위 명령어 (생성 대신 다른 작업 수행)
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+
     /// 자연어 프롬프트
     #[arg(required_unless_present_any = ["clear_cache", "prewarm_cache", "daemon_start", "daemon_stop", "daemon_status"])]
     pub prompt: Vec<String>,
 
-    /// AI 제공자 선택 (gemini, claude, codex). 미지정시 설정 파일의 default_provider 사용
+    /// AI 제공자 선택 (gemini, claude, codex, openai, ollama). 미지정시 설정 파일의 default_provider 사용
     #[arg(short = 'p', long)]
     pub provider: Option<String>,
 
@@ -21,14 +29,29 @@ pub struct Cli {
     #[arg(short = 'q', long)]
     pub quiet: bool,
 
-    /// 명령어만 출력하고 실행하지 않음
+    /// 격리된 샌드박스에서 실행해 보고 출력과 파일 변경만 보여줌 (실제로는 실행하지 않음)
     #[arg(long)]
     pub dry_run: bool,
 
+    /// askai가 명령어를 직접 실행 (출력을 실시간으로 보여주고 exit code를 그대로 반환).
+    /// cd, export처럼 현재 셸을 바꾸는 명령어는 eval용으로 출력.
+    /// --script에서는 스크립트 전체를 직접 실행 (-o, --run이 우선).
+    /// --dry-run, --batch, --plan에서는 무시 (각자의 실행 방식을 따름)
+    #[arg(long)]
+    pub exec: bool,
+
+    /// --exec에서 eval해야 하는 명령어를 stdout 대신 이 파일에 기록 (셸 함수용)
+    #[arg(long, value_name = "FILE", requires = "exec")]
+    pub eval_file: Option<std::path::PathBuf>,
+
     /// 디버그 모드
     #[arg(short = 'd', long)]
     pub debug: bool,
 
+    /// provider로 보낼 컨텍스트(비밀 값을 가린 뒤)를 출력하고 종료 (AI를 호출하지 않음)
+    #[arg(long)]
+    pub show_context: bool,
+
     /// 캐시 무시하고 항상 AI에 새로 요청
     #[arg(long)]
     pub no_cache: bool,
@@ -49,7 +72,7 @@ pub struct Cli {
     #[arg(long)]
     pub targets: Option<String>,
 
-    /// 최대 병렬 실행 개수 (Phase 3용)
+    /// 최대 병렬 실행 개수 (--batch, --plan)
     #[arg(long)]
     pub max_parallel: Option<usize>,
 
@@ -57,6 +80,38 @@ pub struct Cli {
     #[arg(long)]
     pub project_type: Option<String>,
 
+    /// 실행 확인 전에 명령어의 토큰/플래그별 설명 출력
+    #[arg(long)]
+    pub explain: bool,
+
+    /// 스크립트 모드: 여러 줄 bash 스크립트 생성 (기본: eval용으로 stdout 출력, --exec이면 직접 실행)
+    #[arg(long)]
+    pub script: bool,
+
+    /// 스크립트 모드에서 생성된 스크립트를 파일로 저장 (실행 권한 부여)
+    #[arg(short = 'o', long, value_name = "FILE", requires = "script")]
+    pub output: Option<std::path::PathBuf>,
+
+    /// 스크립트 모드에서 생성된 스크립트를 askai가 직접 실행
+    #[arg(long, requires = "script", conflicts_with = "output")]
+    pub run: bool,
+
+    /// 후보 명령어 N개를 생성하여 그중 하나를 선택 (캐시/데몬 미사용)
+    #[arg(long, value_name = "N")]
+    pub candidates: Option<usize>,
+
+    /// 합의 모드: 여러 provider에 동시에 요청하여 결과 비교
+    #[arg(long)]
+    pub consensus: bool,
+
+    /// 합의 모드에서 사용할 provider 목록 (예: gemini,claude). 미지정시 설정 파일의 consensus_providers 사용
+    #[arg(long, value_delimiter = ',', requires = "consensus")]
+    pub consensus_providers: Option<Vec<String>>,
+
+    /// 계획 모드: 여러 단계 요청을 의존성이 있는 작업으로 나누어 실행 (DAG 출력, 작업별 검사, 병렬 실행)
+    #[arg(long)]
+    pub plan: bool,
+
     /// 데몬 모드: 데몬 서버에 요청 전송 (빠른 응답)
     #[arg(long)]
     pub daemon: bool,
@@ -74,8 +129,156 @@ pub struct Cli {
     pub daemon_status: bool,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum Commands {
+    /// 기존 셸 명령어를 설명하고 위험도와 그 근거를 출력 (실행하지 않음)
+    Explain {
+        /// 설명할 셸 명령어 (따옴표로 감싸는 것을 권장)
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+
+    /// 셸 hook이 기록한 마지막으로 실패한 명령어를 고침 (생성 → 검사 → 확인 과정은 같음)
+    Fix {
+        /// 실패한 명령어를 샌드박스에서 다시 실행해 에러 출력을 함께 보냄 (Low 명령어만)
+        #[arg(long)]
+        rerun: bool,
+    },
+
+    /// 대화형 모드: 요청마다 명령어를 만들고, 다음 요청으로 앞의 결과를 고쳐 나감
+    Chat,
+
+    /// `askai fix`용 셸 hook 출력 (예: eval "$(askai hook zsh)")
+    Hook {
+        /// 대상 셸
+        #[arg(value_enum)]
+        shell: HookShell,
+    },
+
+    /// 감사 로그 검사와 조회 (~/.askai/audit)
+    Audit {
+        #[command(subcommand)]
+        action: AuditCommand,
+    },
+}
+
+/// `askai hook`이 지원하는 셸
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookShell {
+    Bash,
+    Zsh,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuditCommand {
+    /// 해시 사슬을 검사하여 고치거나 지운 기록을 찾음 (문제가 있으면 exit code 1)
+    Verify,
+
+    /// 기록 조회 (날짜는 UTC 기준 YYYY-MM-DD)
+    Show {
+        /// 이 날짜부터
+        #[arg(long, value_name = "DATE")]
+        since: Option<NaiveDate>,
+
+        /// 이 날짜까지
+        #[arg(long, value_name = "DATE")]
+        until: Option<NaiveDate>,
+
+        /// 이 날짜 하루만
+        #[arg(long, value_name = "DATE", conflicts_with_all = ["since", "until"])]
+        date: Option<NaiveDate>,
+
+        /// 프로젝트 경로 또는 디렉토리 이름
+        #[arg(long)]
+        project: Option<String>,
+
+        /// 최근 N개만 출력
+        #[arg(short = 'n', long, value_name = "N")]
+        limit: Option<usize>,
+    },
+}
+
 impl Cli {
+    /// 명령줄 파싱 (`Cli::parse` 대신 사용)
+    pub fn parse_args() -> Self {
+        Self::parse_args_from(std::env::args_os())
+    }
+
+    /// `askai fix the broken symlinks`처럼 하위 명령어 이름으로 시작하는 평범한 프롬프트는
+    /// 하위 명령어 인자로 해석할 수 없으면 프롬프트로 다시 해석합니다.
+    fn parse_args_from(args: impl IntoIterator<Item = impl Into<OsString>>) -> Self {
+        let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+        let error = match Self::try_parse_from(&args) {
+            Ok(cli) => return cli,
+            Err(e) => e,
+        };
+
+        let is_help = matches!(
+            error.kind(),
+            ErrorKind::DisplayHelp | ErrorKind::DisplayVersion | ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand
+        );
+        if !is_help {
+            if let Some(cli) = Self::parse_as_prompt(&args) {
+                return cli;
+            }
+        }
+        error.exit()
+    }
+
+    /// 하위 명령어 이름을 프롬프트의 첫 단어로 보고 다시 파싱 (뒤에 단어가 더 있을 때만)
+    fn parse_as_prompt(args: &[OsString]) -> Option<Self> {
+        let command = Self::command();
+        let index = args.iter().skip(1).position(|arg| {
+            arg.to_str()
+                .is_some_and(|arg| command.get_subcommands().any(|sub| sub.get_name() == arg))
+        })? + 1;
+        if index + 1 >= args.len() {
+            return None;
+        }
+
+        // 첫 위치 인자만 하위 명령어로 해석되므로, 그 단어만 하위 명령어 이름과 다르게 바꿔서 파싱
+        let word = args[index].clone();
+        let mut escaped = args.to_vec();
+        let mut marked = OsString::from(" ");
+        marked.push(&word);
+        escaped[index] = marked;
+
+        let mut cli = Self::try_parse_from(escaped).ok()?;
+        cli.prompt[0] = word.to_string_lossy().into_owned();
+        Some(cli)
+    }
+
     pub fn prompt_text(&self) -> String {
         self.prompt.join(" ")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Cli {
+        Cli::parse_args_from(std::iter::once("askai").chain(args.split(' ')))
+    }
+
+    #[test]
+    fn test_subcommand_name_as_prompt() {
+        let cli = parse("fix the broken symlinks in this dir -y");
+        assert!(cli.command.is_none());
+        assert_eq!(cli.prompt_text(), "fix the broken symlinks in this dir");
+        assert!(cli.yes);
+
+        assert_eq!(parse("-p gemini chat with server").prompt_text(), "chat with server");
+        assert_eq!(parse("audit disk usage").prompt_text(), "audit disk usage");
+        assert_eq!(parse("-- explain tar flags").prompt_text(), "explain tar flags");
+    }
+
+    #[test]
+    fn test_subcommands_still_parse() {
+        assert!(matches!(parse("fix --rerun").command, Some(Commands::Fix { rerun: true })));
+        assert!(matches!(parse("chat").command, Some(Commands::Chat)));
+        assert!(matches!(parse("audit verify").command, Some(Commands::Audit { action: AuditCommand::Verify })));
+        assert!(matches!(parse("explain rm -rf build").command, Some(Commands::Explain { .. })));
+        assert!(parse("list big files").command.is_none());
+    }
+}
@@ -0,0 +1,79 @@
+use crate::audit::{AuditFilter, AuditLog, AuditRecord};
+use crate::cli::AuditCommand;
+use crate::error::Result;
+use crate::ui::ConfirmPrompt;
+use colored::*;
+
+/// `askai audit verify|show`: 감사 로그 검사와 조회
+pub fn execute_audit_command(action: &AuditCommand) -> Result<()> {
+    let log = AuditLog::new();
+
+    match action {
+        AuditCommand::Verify => {
+            let verification = log.verify()?;
+            if verification.is_intact() {
+                eprintln!(
+                    "{} Audit log intact: {} records in {} files.",
+                    "[OK]".green(),
+                    verification.records,
+                    verification.files
+                );
+                return Ok(());
+            }
+
+            eprintln!("{} Audit log has been modified:", "[X]".red().bold());
+            for problem in &verification.problems {
+                let location = match problem.line {
+                    Some(line) => format!("{}:{}", problem.file.display(), line),
+                    None => problem.file.display().to_string(),
+                };
+                eprintln!("  - {} {}", location.dimmed(), problem.message);
+            }
+            std::process::exit(1);
+        }
+        AuditCommand::Show { since, until, date, project, limit } => {
+            let filter = AuditFilter {
+                since: date.or(*since),
+                until: date.or(*until),
+                project: project.clone(),
+            };
+
+            let records = log.query(&filter)?;
+            if records.is_empty() {
+                eprintln!("{} No audit records found.", "[i]".cyan());
+                return Ok(());
+            }
+
+            let skip = limit.map_or(0, |limit| records.len().saturating_sub(limit));
+            for record in &records[skip..] {
+                print_record(record);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// 기록 한 개 출력
+fn print_record(record: &AuditRecord) {
+    println!(
+        "{} {} {}  {}",
+        format!("#{}", record.seq).dimmed(),
+        record.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+        ConfirmPrompt::danger_label(record.level).0,
+        record.command.lines().next().unwrap_or_default().bold()
+    );
+    println!(
+        "    {} by {}, {} ({})",
+        record.approval.describe(),
+        record.user,
+        record.outcome.describe(),
+        record.provider
+    );
+    println!("    {} {}", "cwd:".dimmed(), record.cwd);
+    if !record.prompt.is_empty() {
+        println!("    {} {}", "prompt:".dimmed(), record.prompt);
+    }
+    for finding in &record.findings {
+        println!("    - {} [rule: {}]", finding.reason, finding.rule);
+    }
+}
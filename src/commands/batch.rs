@@ -2,11 +2,13 @@ use crate::cli::Cli;
 use crate::config::Config;
 use crate::error::Result;
 use crate::ai::factory::ProviderFactory;
-use crate::context::{ProjectScanner, ScanResult, ProjectType};
-use crate::executor::{planner::{ExecutionPlan, Task}, batch::BatchExecutor, DangerLevel};
+use crate::audit::{self, Approval, AuditRecord, Outcome};
+use crate::context::{self, ProjectScanner, ScanResult, ProjectType};
+use crate::executor::{planner::{ExecutionPlan, Task}, batch::BatchExecutor, CommandValidator, DangerLevel};
 use crate::ui::ConfirmPrompt;
 use colored::*;
 use std::env;
+use std::path::Path;
 use once_cell::sync::Lazy;
 use std::sync::Mutex;
 use crate::cache::ResponseCache;
@@ -93,7 +95,7 @@ pub async fn execute_batch_mode(cli: &Cli, config: &Config, response_cache: &Laz
 
     // 2. Provider 선택
     let provider_name = cli.provider.as_deref().unwrap_or(&config.default_provider);
-    let provider = ProviderFactory::create(provider_name)?;
+    let provider = ProviderFactory::create_with_fallback(provider_name, config)?;
 
     if !cli.quiet {
         eprintln!(
@@ -107,7 +109,7 @@ pub async fn execute_batch_mode(cli: &Cli, config: &Config, response_cache: &Laz
     let mut tasks = Vec::new();
 
     for (idx, project) in scan_result.projects.iter().enumerate() {
-        let project_context = project.to_context_string();
+        let project_context = context::redact(&project.to_context_string());
 
         // 캐시 확인
         let command = if !cli.no_cache {
@@ -176,7 +178,22 @@ pub async fn execute_batch_mode(cli: &Cli, config: &Config, response_cache: &Laz
     let mut plan = ExecutionPlan::new(tasks);
     plan.can_parallelize = true;
 
-    // 5. 사용자 확인 (--yes 플래그가 없으면)
+    // 5. 가장 위험한 작업으로 확인 (Critical 작업은 --yes로 승인할 수 없음)
+    let validator = CommandValidator::load()?;
+    let riskiest = plan.tasks
+        .iter()
+        .map(|task| (task.command.clone(), validator.assess(&task.command)))
+        .rev()
+        .max_by_key(|(_, report)| report.level);
+
+    if let Some((_, report)) = &riskiest {
+        if report.level == DangerLevel::Critical && (cli.yes || cli.quiet) && !cli.dry_run {
+            eprintln!("{}", "[X] A task is critical; refusing to auto-approve. Run without --yes to confirm it.".red());
+            std::process::exit(1);
+        }
+    }
+
+    // 5-1. 사용자 확인 (--yes 플래그가 없으면)
     if !cli.yes && !cli.dry_run {
         if !cli.quiet {
             eprintln!("\n{} Execute the following tasks?", "[?]".cyan());
@@ -185,9 +202,8 @@ pub async fn execute_batch_mode(cli: &Cli, config: &Config, response_cache: &Laz
         }
 
         let prompt = ConfirmPrompt::new();
-        // 간단히 첫 번째 명령어로 확인
-        if !plan.tasks.is_empty() {
-            if !prompt.confirm_execution(&plan.tasks[0].command, DangerLevel::Low)? {
+        if let Some((command, report)) = &riskiest {
+            if !prompt.confirm_execution(command, report)? {
                 if !cli.quiet {
                     eprintln!("{}", "[X] User cancelled.".yellow());
                 }
@@ -210,6 +226,30 @@ pub async fn execute_batch_mode(cli: &Cli, config: &Config, response_cache: &Laz
     }
     let batch_result = executor.execute(&plan).await;
 
+    // 6-1. 작업별 결과를 감사 로그에 기록
+    let approval = if cli.yes { Approval::YesFlag } else { Approval::Interactive };
+    for task_result in &batch_result.task_results {
+        let Some(task) = plan.tasks.iter().find(|task| task.id == task_result.task_id) else {
+            continue;
+        };
+        let outcome = match &task_result.error {
+            Some(error) => Outcome::Failed { error: error.clone() },
+            None => Outcome::Succeeded,
+        };
+        let mut record = AuditRecord::new(
+            &cli.prompt_text(),
+            &task.command,
+            provider.name(),
+            &validator.assess(&task.command),
+            approval,
+            outcome,
+        );
+        if let Some(dir) = &task.working_dir {
+            record = record.with_cwd(Path::new(dir));
+        }
+        audit::log(record);
+    }
+
     // 7. 결과 출력
     if !cli.quiet {
         eprintln!("\n{} Batch execution complete!", "[OK]".green().bold());
@@ -0,0 +1,131 @@
+use crate::cli::Cli;
+use crate::commands::execute_or_print;
+use crate::config::Config;
+use crate::error::{AskAiError, Result};
+use crate::ai::factory::ProviderFactory;
+use crate::ai::history::{CommandHistory, HistoryStore};
+use crate::ai::response_processor::CommandDetails;
+use crate::audit::{self, Approval, AuditRecord, Outcome};
+use crate::executor::{CommandValidator, DangerLevel};
+use crate::ui::{CandidateChoice, ConfirmPrompt, create_spinner};
+use chrono::Utc;
+use colored::*;
+
+/// 후보 모드: 여러 명령어 후보를 생성하고 사용자가 선택
+pub async fn execute_candidates_mode(cli: &Cli, config: &Config, ctx: &str, count: usize) -> Result<()> {
+    let provider_name = cli.provider.as_deref().unwrap_or(&config.default_provider);
+    let provider = ProviderFactory::create_with_fallback(provider_name, config)?;
+
+    // 1. 후보 생성
+    let spinner = if !cli.quiet {
+        create_spinner(&format!(
+            "Generating {} candidates with {} provider...",
+            count,
+            provider.name()
+        ))
+    } else {
+        create_spinner("")
+    };
+
+    let candidates = provider.generate_candidates(&cli.prompt_text(), ctx, count).await?;
+
+    spinner.finish_and_clear();
+
+    // 2. 후보별 안전성 검사 (차단된 후보는 제외)
+    let validator = CommandValidator::load()?;
+    let mut validated: Vec<(String, DangerLevel)> = Vec::new();
+
+    for candidate in candidates {
+        match validator.validate(&candidate) {
+            Ok(report) => validated.push((candidate, report.level)),
+            Err(e) => {
+                if !cli.quiet {
+                    eprintln!("{} Blocked candidate: {} ({})", "[X]".red(), candidate.dimmed(), e);
+                }
+            }
+        }
+    }
+
+    if validated.is_empty() {
+        return Err(AskAiError::DangerousCommand(
+            "All generated candidates were blocked".to_string()
+        ));
+    }
+
+    if !cli.quiet {
+        eprintln!(
+            "{} {} distinct candidate(s) generated.",
+            "[v]".green(),
+            validated.len()
+        );
+    }
+
+    // 3. 후보 선택
+    let command = if cli.yes {
+        // 자동 승인: 가장 위험도가 낮은 후보 (동률이면 먼저 생성된 것)
+        let (command, level) = validated
+            .iter()
+            .min_by_key(|(_, level)| *level)
+            .cloned()
+            .unwrap_or_default();
+
+        // Critical 후보만 남았으면 자동 승인하지 않음
+        if level == DangerLevel::Critical {
+            eprintln!("{}", "[X] Every candidate is critical; refusing to auto-approve. Run without --yes to confirm one.".red());
+            std::process::exit(1);
+        }
+        command
+    } else if cli.quiet {
+        eprintln!("{}", "[X] --yes flag required in quiet mode.".red());
+        std::process::exit(1);
+    } else {
+        let prompt = ConfirmPrompt::new();
+        match prompt.select_candidate(&validated)? {
+            CandidateChoice::Selected(index) => validated[index].0.clone(),
+            CandidateChoice::Edited(edited) => {
+                // 수정된 명령어는 다시 검사 후 확인 (확인 단계에서 또 고칠 수 있음)
+                let report = validator.validate(&edited)?;
+                let Some(edited) = prompt.confirm_with_edits(&edited, report, &validator)? else {
+                    eprintln!("{}", "[X] User cancelled.".yellow());
+                    std::process::exit(1);
+                };
+                edited
+            }
+            CandidateChoice::Rejected => {
+                eprintln!("{}", "[X] User cancelled.".yellow());
+                std::process::exit(1);
+            }
+        }
+    };
+
+    // 4. 명령어를 stdout에 출력 (eval용, --exec이면 직접 실행)
+    let exit_code = execute_or_print(cli, &command).await?.map(|result| result.exit_code);
+    let approval = if cli.yes { Approval::YesFlag } else { Approval::Interactive };
+    let report = validator.assess(&command);
+    audit::log(AuditRecord::new(&cli.prompt_text(), &command, &provider.answered_by(), &report, approval, Outcome::from_exit_code(exit_code)));
+
+    // 5. 히스토리 저장 (RAG)
+    let store = HistoryStore::new();
+    let history_entry = CommandHistory {
+        prompt: cli.prompt_text(),
+        command,
+        timestamp: Utc::now(),
+        executed: true,
+        provider: provider.answered_by(),
+        exit_code,
+        edited_from: None,
+        details: CommandDetails::default(),
+    };
+
+    if let Err(e) = store.add(history_entry) {
+        if cli.debug {
+            eprintln!("{} Failed to save history: {}", "DEBUG:".yellow(), e);
+        }
+    }
+
+    if let Some(code) = exit_code.filter(|code| *code != 0) {
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
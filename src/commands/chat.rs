@@ -0,0 +1,414 @@
+use crate::ai::factory::ProviderFactory;
+use crate::ai::history::{CommandHistory, HistoryStore};
+use crate::ai::response_processor::{CommandDetails, GeneratedCommand};
+use crate::ai::AiProvider;
+use crate::audit::{self, Approval, AuditRecord, Outcome};
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::context;
+use crate::error::{AskAiError, Result};
+use crate::executor::runner::{self, CommandRunner};
+use crate::executor::validator::ValidationReport;
+use crate::executor::{CommandValidator, DangerLevel};
+use crate::ui::{create_spinner, ChatAction, ConfirmPrompt};
+use chrono::Utc;
+use colored::*;
+use dialoguer::Input;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// provider에 보낼 최근 턴 수 (오래된 턴부터 빠짐)
+const MAX_TURNS: usize = 8;
+
+/// 턴마다 provider에 보낼 실행 출력 최대 줄 수 (마지막 부분)
+const MAX_OUTPUT_LINES: usize = 20;
+
+/// 클립보드 도구 (먼저 찾은 것을 사용)
+const CLIPBOARD_TOOLS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+/// 한 턴의 명령어가 어떻게 끝났는지
+#[derive(Debug, Clone, PartialEq)]
+enum TurnOutcome {
+    /// 실행하지 않음 (복사했거나 다음 요청으로 넘어감)
+    NotRun,
+    /// 안전성 검사에서 차단됨
+    Blocked(String),
+    /// askai가 실행한 결과 (출력은 마지막 부분만)
+    Ran { exit_code: i32, output: String },
+}
+
+/// 대화 한 턴: 요청, 마지막으로 고른 명령어, 결과
+#[derive(Debug, Clone, PartialEq)]
+struct ChatTurn {
+    prompt: String,
+    command: String,
+    outcome: TurnOutcome,
+}
+
+/// `askai chat`의 대화 상태
+///
+/// 이전 요청, 명령어, 실행 결과를 매 턴 컨텍스트에 붙여 보내므로
+/// "7일 넘은 것만", "이번엔 하위 폴더까지"처럼 앞의 결과를 고쳐 달라고 할 수 있습니다.
+#[derive(Debug, Default)]
+struct ChatSession {
+    turns: Vec<ChatTurn>,
+}
+
+impl ChatSession {
+    fn push(&mut self, turn: ChatTurn) {
+        self.turns.push(turn);
+        if self.turns.len() > MAX_TURNS {
+            self.turns.remove(0);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.turns.clear();
+    }
+
+    /// provider로 보낼 대화 상태 (비밀 값은 가려짐, 턴이 없으면 빈 문자열)
+    fn to_context(&self) -> String {
+        if self.turns.is_empty() {
+            return String::new();
+        }
+
+        let mut context = String::from("\n\nConversation so far (the new request may refine the last command):\n");
+        for (i, turn) in self.turns.iter().enumerate() {
+            context.push_str(&format!(
+                "{}. Request: \"{}\" → Command: \"{}\"\n",
+                i + 1,
+                turn.prompt,
+                turn.command
+            ));
+            match &turn.outcome {
+                TurnOutcome::NotRun => {}
+                TurnOutcome::Blocked(reason) => {
+                    context.push_str(&format!("   Blocked by the safety check: {}\n", reason));
+                }
+                TurnOutcome::Ran { exit_code, output } => {
+                    context.push_str(&format!("   Exit code: {}\n", exit_code));
+                    if !output.trim().is_empty() {
+                        context.push_str("   Output:\n");
+                        for line in output.lines() {
+                            context.push_str(&format!("   | {}\n", line));
+                        }
+                    }
+                }
+            }
+        }
+
+        context::redact(&context)
+    }
+}
+
+/// `askai chat`: 한 턴씩 명령어를 만들고 실행 / 수정 / 설명 / 복사하는 대화형 모드
+///
+/// 생성된 명령어는 한 번만 쓰는 모드와 같은 안전성 검사와 Critical 확인을 거칩니다.
+/// 실행은 askai 안에서 하므로 `cd`, `export` 같은 명령어는 복사해서 셸에서 실행해야 합니다.
+pub async fn execute_chat_command(cli: &Cli, config: &Config) -> Result<()> {
+    let provider_name = cli.provider.as_deref().unwrap_or(&config.default_provider);
+    let provider = ProviderFactory::create_with_fallback(provider_name, config)?;
+    let validator = CommandValidator::load()?;
+    let prompt = ConfirmPrompt::new();
+    let mut session = ChatSession::default();
+
+    eprintln!(
+        "{} Chatting with {} provider. Each request can refine the previous command.",
+        "[i]".cyan(),
+        provider.name()
+    );
+    eprintln!("{} /reset starts a new conversation; exit or Ctrl-D quits.", "[TIP]".cyan());
+
+    // 1. 요청 입력 (Ctrl-D, Ctrl-C면 종료)
+    while let Ok(request) = Input::<String>::new()
+        .with_prompt("askai")
+        .allow_empty(true)
+        .interact_text()
+    {
+        let request = request.trim().to_string();
+
+        match request.as_str() {
+            "" => continue,
+            "exit" | "quit" | "/exit" | "/quit" => break,
+            "/reset" => {
+                session.clear();
+                eprintln!("{} Started a new conversation.", "[OK]".green());
+                continue;
+            }
+            _ => {}
+        }
+
+        // 2. 기본 컨텍스트 + 대화 상태로 명령어 생성
+        let ctx = format!("{}{}", context::get_context_with_history(&request), session.to_context());
+        if cli.debug {
+            eprintln!("{} {}", "DEBUG Context:".yellow(), ctx);
+        }
+
+        let spinner = create_spinner(&format!("Generating command with {} provider...", provider.name()));
+        let result = provider.generate_structured(&request, &ctx).await;
+        spinner.finish_and_clear();
+
+        let generated = match result {
+            Ok(generated) => generated,
+            Err(e) => {
+                eprintln!("{} {}", "[X]".red(), e);
+                continue;
+            }
+        };
+
+        // 3. 검사, 선택, 실행 (입력창에서 취소하면 이 턴만 버림)
+        let turn = Turn {
+            cli,
+            provider: provider.as_ref(),
+            answered_by: provider.answered_by(),
+            validator: &validator,
+            prompt: &prompt,
+            ctx: &ctx,
+            request: &request,
+        };
+        match turn.run(generated).await {
+            Ok(turn) => session.push(turn),
+            Err(AskAiError::UserCancelled) => eprintln!("{}", "[X] User cancelled.".yellow()),
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(())
+}
+
+/// 한 턴을 처리하는 데 필요한 것들
+struct Turn<'a> {
+    cli: &'a Cli,
+    provider: &'a dyn AiProvider,
+    answered_by: String,
+    validator: &'a CommandValidator,
+    prompt: &'a ConfirmPrompt,
+    ctx: &'a str,
+    request: &'a str,
+}
+
+impl Turn<'_> {
+    /// 사용자가 실행, 복사, 넘어가기 중 하나를 고를 때까지 반복 (수정, 설명은 다시 선택)
+    async fn run(&self, generated: GeneratedCommand) -> Result<ChatTurn> {
+        let original = generated.command;
+        let mut command = original.clone();
+        let mut details = generated.details;
+
+        loop {
+            let report = match self.validator.validate(&command) {
+                Ok(report) => report,
+                Err(e) => {
+                    // 차단 이유를 대화 상태에 남겨 다음 요청에서 고칠 수 있게 함
+                    eprintln!("{} {}", "[X]".red(), e);
+                    return Ok(self.finish(command, TurnOutcome::Blocked(e.to_string())));
+                }
+            };
+
+            match self.prompt.choose_chat_action(&command, &report, &details)? {
+                ChatAction::Run => {
+                    if let Some(effect) = runner::parent_shell_effect(&command) {
+                        eprintln!("{} `{}` changes the current shell, so running it inside chat has no effect.", "[!]".yellow(), effect);
+                        eprintln!("{} Copy it with `c` and run it in your shell.", "[TIP]".cyan());
+                        continue;
+                    }
+                    if report.level == DangerLevel::Critical && !self.prompt.confirm_critical(&report)? {
+                        self.audit(&command, &report, Approval::None, Outcome::Cancelled);
+                        eprintln!("{}", "[X] User cancelled.".yellow());
+                        continue;
+                    }
+                    return self.execute(command, &original, &report, details).await;
+                }
+                ChatAction::Edit => {
+                    let edited = self.prompt.edit_command(&command)?;
+                    if !edited.is_empty() && edited != command {
+                        command = edited;
+                        // 고친 명령어에는 AI의 설명이 맞지 않음
+                        details = CommandDetails::default();
+                    }
+                }
+                ChatAction::Explain => {
+                    crate::commands::show_explanation(self.provider, &command, self.ctx).await;
+                }
+                ChatAction::Copy => {
+                    let target = copy_to_clipboard(&command);
+                    eprintln!("{} Copied to the clipboard ({}).", "[OK]".green(), target);
+                    self.audit(&command, &report, Approval::Interactive, Outcome::Copied);
+                    self.record_history(&command, &original, false, None, details);
+                    return Ok(self.finish(command, TurnOutcome::NotRun));
+                }
+                ChatAction::Next => return Ok(self.finish(command, TurnOutcome::NotRun)),
+            }
+        }
+    }
+
+    /// 명령어를 실행하고 출력 마지막 부분을 대화 상태로 남김
+    async fn execute(
+        &self,
+        command: String,
+        original: &str,
+        report: &ValidationReport,
+        details: CommandDetails,
+    ) -> Result<ChatTurn> {
+        eprintln!("{} {}", "[>] Executing:".cyan(), command);
+        let result = CommandRunner::new().with_stdout_capture(true).stream(&command).await?;
+        if result.exit_code != 0 {
+            eprintln!("{} Command exited with code {}.", "[X]".red(), result.exit_code);
+        }
+
+        self.audit(&command, report, Approval::Interactive, Outcome::from_exit_code(Some(result.exit_code)));
+        self.record_history(&command, original, true, Some(result.exit_code), details);
+
+        let output = tail_lines(&format!("{}{}", result.stdout, result.stderr), MAX_OUTPUT_LINES);
+        Ok(self.finish(command, TurnOutcome::Ran { exit_code: result.exit_code, output }))
+    }
+
+    fn finish(&self, command: String, outcome: TurnOutcome) -> ChatTurn {
+        ChatTurn {
+            prompt: self.request.to_string(),
+            command,
+            outcome,
+        }
+    }
+
+    fn audit(&self, command: &str, report: &ValidationReport, approval: Approval, outcome: Outcome) {
+        audit::log(AuditRecord::new(self.request, command, &self.answered_by, report, approval, outcome));
+    }
+
+    /// 히스토리 저장 (RAG, 사용자가 고쳤으면 생성된 명령어도 함께, 실패는 치명적이지 않으므로 무시)
+    fn record_history(
+        &self,
+        command: &str,
+        original: &str,
+        executed: bool,
+        exit_code: Option<i32>,
+        details: CommandDetails,
+    ) {
+        let entry = CommandHistory {
+            prompt: self.request.to_string(),
+            command: command.to_string(),
+            timestamp: Utc::now(),
+            executed,
+            provider: self.answered_by.clone(),
+            exit_code,
+            edited_from: (command != original).then(|| original.to_string()),
+            details,
+        };
+        if let Err(e) = HistoryStore::new().add(entry) {
+            if self.cli.debug {
+                eprintln!("{} Failed to save history: {}", "DEBUG:".yellow(), e);
+            }
+        }
+    }
+}
+
+/// 마지막 `max_lines`줄만 남김
+fn tail_lines(text: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    lines[start..].join("\n")
+}
+
+/// 클립보드로 복사하고 사용한 방법을 반환
+///
+/// 클립보드 도구가 없으면 터미널이 처리하는 OSC 52 escape를 보냅니다 (SSH 너머에서도 동작).
+fn copy_to_clipboard(text: &str) -> &'static str {
+    for (tool, args) in CLIPBOARD_TOOLS {
+        let Ok(mut child) = Command::new(tool)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        if child.wait().map(|status| status.success()).unwrap_or(false) {
+            return tool;
+        }
+    }
+
+    let mut stderr = std::io::stderr();
+    let _ = write!(stderr, "\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    let _ = stderr.flush();
+    "terminal"
+}
+
+/// OSC 52용 base64 인코딩 (패딩 포함)
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, b)| n | (*b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(prompt: &str, command: &str, outcome: TurnOutcome) -> ChatTurn {
+        ChatTurn {
+            prompt: prompt.to_string(),
+            command: command.to_string(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_session_context() {
+        let mut session = ChatSession::default();
+        assert_eq!(session.to_context(), "");
+
+        session.push(turn(
+            "find large log files",
+            "find . -name '*.log' -size +10M",
+            TurnOutcome::Ran { exit_code: 0, output: "./app.log\n./old/app.log".to_string() },
+        ));
+        session.push(turn("only older than 7 days", "rm -rf /", TurnOutcome::Blocked("blocked".to_string())));
+
+        let context = session.to_context();
+        assert!(context.contains("1. Request: \"find large log files\" → Command: \"find . -name '*.log' -size +10M\""));
+        assert!(context.contains("   Exit code: 0\n   Output:\n   | ./app.log\n   | ./old/app.log\n"));
+        assert!(context.contains("2. Request: \"only older than 7 days\""));
+        assert!(context.contains("Blocked by the safety check: blocked"));
+
+        // 오래된 턴부터 빠짐
+        for i in 0..MAX_TURNS {
+            session.push(turn(&format!("request {}", i), "ls", TurnOutcome::NotRun));
+        }
+        assert_eq!(session.turns.len(), MAX_TURNS);
+        assert!(!session.to_context().contains("find large log files"));
+
+        session.clear();
+        assert_eq!(session.to_context(), "");
+    }
+
+    #[test]
+    fn test_tail_lines_and_base64() {
+        assert_eq!(tail_lines("a\nb\nc\n", 2), "b\nc");
+        assert_eq!(tail_lines("a", 5), "a");
+
+        assert_eq!(base64(b"ls -la"), "bHMgLWxh");
+        assert_eq!(base64(b"ls"), "bHM=");
+        assert_eq!(base64(b"l"), "bA==");
+        assert_eq!(base64(b""), "");
+    }
+}
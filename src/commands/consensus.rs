@@ -0,0 +1,227 @@
+use crate::cli::Cli;
+use crate::commands::execute_or_print;
+use crate::config::Config;
+use crate::error::{AskAiError, Result};
+use crate::ai::factory::ProviderFactory;
+use crate::ai::consensus::run_consensus;
+use crate::ai::history::{CommandHistory, HistoryStore};
+use crate::ai::response_processor::CommandDetails;
+use crate::audit::{self, Approval, AuditRecord, Outcome};
+use crate::executor::{CommandValidator, DangerLevel, ValidationReport};
+use crate::ui::{CandidateChoice, ConfirmPrompt, create_spinner};
+use chrono::Utc;
+use colored::*;
+use std::time::Duration;
+
+/// 합의 모드: 여러 provider의 답을 비교하여 신뢰도를 표시
+pub async fn execute_consensus_mode(cli: &Cli, config: &Config, ctx: &str) -> Result<()> {
+    let names = cli.consensus_providers
+        .clone()
+        .unwrap_or_else(|| config.consensus_providers.clone());
+
+    if names.len() < 2 {
+        return Err(AskAiError::ConfigError(
+            "Consensus mode needs at least two providers (set consensus_providers or --consensus-providers)".to_string()
+        ));
+    }
+
+    let providers = names
+        .iter()
+        .map(|name| {
+            let provider = ProviderFactory::create_with_config(name, config)?;
+            Ok((provider, Duration::from_secs(config.timeout_for(name))))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // 1. 모든 provider에 동시 요청
+    let spinner = if !cli.quiet {
+        create_spinner(&format!("Asking {} providers ({})...", names.len(), names.join(", ")))
+    } else {
+        create_spinner("")
+    };
+
+    let result = run_consensus(&providers, &cli.prompt_text(), ctx).await;
+
+    spinner.finish_and_clear();
+
+    if !cli.quiet {
+        for answer in result.answers.iter().filter(|a| a.command.is_none()) {
+            eprintln!(
+                "{} {} failed: {}",
+                "[!]".yellow(),
+                answer.provider,
+                answer.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+    }
+
+    if result.answered_count() == 0 {
+        return Err(AskAiError::AiCliError("No provider answered in consensus mode".to_string()));
+    }
+
+    // 2. 그룹별 안전성 검사 (차단된 명령어는 제외)
+    let validator = CommandValidator::load()?;
+    let mut groups: Vec<(String, ValidationReport, Vec<String>)> = Vec::new();
+
+    for group in &result.groups {
+        match validator.validate(&group.command) {
+            Ok(report) => groups.push((group.command.clone(), report, group.providers.clone())),
+            Err(e) => {
+                if !cli.quiet {
+                    eprintln!(
+                        "{} Blocked answer from {}: {} ({})",
+                        "[X]".red(),
+                        group.providers.join(", "),
+                        group.command.dimmed(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    if groups.is_empty() {
+        return Err(AskAiError::DangerousCommand(
+            "All provider answers were blocked".to_string()
+        ));
+    }
+
+    // 3. 합의 여부에 따라 확인 방식 결정 (거부/취소도 감사 로그에 남김)
+    let prompt = ConfirmPrompt::new();
+    let log_not_run = |command: &str, report: &ValidationReport, providers: &[String], outcome: Outcome| {
+        audit::log(AuditRecord::new(&cli.prompt_text(), command, &providers.join("+"), report, Approval::None, outcome));
+    };
+    let refused = |reason: &str| Outcome::Refused { reason: reason.to_string() };
+    // 사용자가 확인 단계에서 고쳤으면 원래 명령어 (히스토리에 수정 신호로 남김)
+    let mut edited_from = None;
+    let (command, agreed_by) = if groups.len() == 1 {
+        let (command, report, providers) = groups.remove(0);
+
+        if !cli.quiet {
+            if result.is_unanimous() {
+                eprintln!(
+                    "\n{} High confidence: all {} responding providers agree ({}).",
+                    "[OK]".green().bold(),
+                    providers.len(),
+                    providers.join(", ")
+                );
+            } else {
+                eprintln!(
+                    "\n{} Only {} answered; no second opinion available.",
+                    "[!]".yellow(),
+                    providers.join(", ")
+                );
+            }
+        }
+
+        // 두 provider 이상이 동의하지 않았으면 합의가 아니므로 자동 승인하지 않음
+        if !result.is_unanimous() && (cli.yes || cli.quiet) {
+            log_not_run(&command, &report, &providers, refused("fewer than two providers agree"));
+            eprintln!("{}", "[X] Fewer than two providers agree; refusing to auto-approve. Run without --yes to confirm it.".red());
+            std::process::exit(1);
+        }
+
+        // Critical 명령어는 모든 provider가 동의해도 자동 승인하지 않음
+        if report.level == DangerLevel::Critical && (cli.yes || cli.quiet) {
+            log_not_run(&command, &report, &providers, refused("critical command can't be auto-approved"));
+            eprintln!("{}", "[X] Critical command requires typed confirmation; refusing to auto-approve. Run without --yes to confirm it.".red());
+            std::process::exit(1);
+        }
+
+        if !cli.yes && !cli.quiet {
+            let Some(confirmed) = prompt.confirm_with_edits(&command, report.clone(), &validator)? else {
+                log_not_run(&command, &report, &providers, Outcome::Cancelled);
+                eprintln!("{}", "[X] User cancelled.".yellow());
+                std::process::exit(1);
+            };
+            if confirmed != command {
+                edited_from = Some(command);
+            }
+            (confirmed, providers)
+        } else if !cli.yes && cli.quiet {
+            log_not_run(&command, &report, &providers, refused("--yes flag required in quiet mode"));
+            eprintln!("{}", "[X] --yes flag required in quiet mode.".red());
+            std::process::exit(1);
+        } else {
+            (command, providers)
+        }
+    } else {
+        if !cli.quiet {
+            eprintln!("\n{} Providers disagree:", "[!]".yellow().bold());
+            for (idx, (command, report, providers)) in groups.iter().enumerate() {
+                eprintln!(
+                    "  [{}] {}  <- {} ({:?})",
+                    idx + 1,
+                    command,
+                    providers.join(", ").cyan(),
+                    report.level
+                );
+            }
+        }
+
+        // 의견이 갈리면 자동 승인하지 않음
+        if cli.yes || cli.quiet {
+            for (command, report, providers) in &groups {
+                log_not_run(command, report, providers, refused("providers disagree"));
+            }
+            eprintln!("{}", "[X] Providers disagree; refusing to auto-approve.".red());
+            std::process::exit(1);
+        }
+
+        let candidates: Vec<(String, DangerLevel)> = groups
+            .iter()
+            .map(|(command, report, _)| (command.clone(), report.level))
+            .collect();
+
+        match prompt.select_candidate(&candidates)? {
+            CandidateChoice::Selected(index) => (groups[index].0.clone(), groups[index].2.clone()),
+            CandidateChoice::Edited(edited) => {
+                let report = validator.validate(&edited)?;
+                let Some(confirmed) = prompt.confirm_with_edits(&edited, report.clone(), &validator)? else {
+                    log_not_run(&edited, &report, &["user".to_string()], Outcome::Cancelled);
+                    eprintln!("{}", "[X] User cancelled.".yellow());
+                    std::process::exit(1);
+                };
+                (confirmed, vec!["user".to_string()])
+            }
+            CandidateChoice::Rejected => {
+                for (command, report, providers) in &groups {
+                    log_not_run(command, report, providers, Outcome::Cancelled);
+                }
+                eprintln!("{}", "[X] User cancelled.".yellow());
+                std::process::exit(1);
+            }
+        }
+    };
+
+    // 4. 명령어를 stdout에 출력 (eval용, --exec이면 직접 실행)
+    let exit_code = execute_or_print(cli, &command).await?.map(|result| result.exit_code);
+    let approval = if cli.yes { Approval::YesFlag } else { Approval::Interactive };
+    let report = validator.assess(&command);
+    audit::log(AuditRecord::new(&cli.prompt_text(), &command, &agreed_by.join("+"), &report, approval, Outcome::from_exit_code(exit_code)));
+
+    // 5. 히스토리 저장 (RAG)
+    let store = HistoryStore::new();
+    let history_entry = CommandHistory {
+        prompt: cli.prompt_text(),
+        command,
+        timestamp: Utc::now(),
+        executed: true,
+        provider: agreed_by.join("+"),
+        exit_code,
+        edited_from,
+        details: CommandDetails::default(),
+    };
+
+    if let Err(e) = store.add(history_entry) {
+        if cli.debug {
+            eprintln!("{} Failed to save history: {}", "DEBUG:".yellow(), e);
+        }
+    }
+
+    if let Some(code) = exit_code.filter(|code| *code != 0) {
+        std::process::exit(code);
+    }
+
+    Ok(())
+}
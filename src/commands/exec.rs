@@ -0,0 +1,53 @@
+use crate::cli::Cli;
+use crate::error::Result;
+use crate::executor::runner::{self, CommandRunner, StreamResult};
+use colored::*;
+use std::io::IsTerminal;
+
+/// 승인된 명령어를 eval용으로 출력하거나, `--exec`이면 askai가 직접 실행
+///
+/// `--exec`에서는 출력이 실시간으로 터미널에 나타나고 exit code는 그대로 반환됩니다.
+/// `cd`, `export`처럼 현재 셸을 바꾸는 명령어는 자식 프로세스에서 실행하면 효과가 없으므로
+/// `--eval-file`(없으면 stdout)에 기록해 셸 함수가 `eval`하도록 넘깁니다.
+///
+/// # Returns
+/// * 직접 실행한 명령어의 종료 코드와 stderr (eval용으로 출력했으면 `None`)
+pub async fn execute_or_print(cli: &Cli, command: &str) -> Result<Option<StreamResult>> {
+    // 사용자는 eval $(askai "프롬프트")로 실행하거나 shell function으로 감싸서 사용
+    if !cli.exec {
+        println!("{}", command);
+        return Ok(None);
+    }
+
+    // 1. 현재 셸을 바꾸는 명령어는 eval로 넘김
+    if let Some(effect) = runner::parent_shell_effect(command) {
+        match &cli.eval_file {
+            Some(path) => {
+                std::fs::write(path, format!("{}\n", command))?;
+                if !cli.quiet {
+                    eprintln!("{} `{}` changes the current shell; handing it to your shell.", "[i]".cyan(), effect);
+                }
+            }
+            None => {
+                println!("{}", command);
+                // 터미널에 출력만 하면 아무 일도 일어나지 않으므로 안내
+                if std::io::stdout().is_terminal() && !cli.quiet {
+                    eprintln!("{} `{}` changes the current shell, so askai can't run it itself.", "[!]".yellow(), effect);
+                    eprintln!("{} Run it with eval \"$(askai ...)\" or the askai shell function from install.sh.", "[TIP]".cyan());
+                }
+            }
+        }
+        return Ok(None);
+    }
+
+    // 2. 직접 실행 (stdin/stdout/stderr를 그대로 연결)
+    if !cli.quiet {
+        eprintln!("{} {}", "[>] Executing:".cyan(), command);
+    }
+    let result = CommandRunner::new().stream(command).await?;
+
+    if result.exit_code != 0 && !cli.quiet {
+        eprintln!("{} Command exited with code {}.", "[X]".red(), result.exit_code);
+    }
+    Ok(Some(result))
+}
@@ -0,0 +1,51 @@
+use crate::ai::AiProvider;
+use crate::ai::factory::ProviderFactory;
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::context;
+use crate::error::Result;
+use crate::executor::CommandValidator;
+use crate::ui::{ConfirmPrompt, create_spinner};
+use colored::*;
+
+/// provider에게 명령어 설명을 요청하여 출력
+///
+/// 설명은 보조 정보이므로 실패해도 에러를 전파하지 않고 경고만 출력합니다.
+pub async fn show_explanation(provider: &dyn AiProvider, command: &str, ctx: &str) {
+    let spinner = create_spinner(&format!(
+        "Explaining command with {} provider...",
+        provider.name()
+    ));
+
+    let result = provider.explain_command(command, ctx).await;
+
+    spinner.finish_and_clear();
+
+    match result {
+        Ok(explanation) => ConfirmPrompt::new().show_explanation(&explanation),
+        Err(e) => eprintln!("{} Could not explain command: {}", "[!]".yellow(), e),
+    }
+}
+
+/// `askai explain '<command>'`: 기존 명령어를 설명하고 위험도와 근거를 출력
+///
+/// 명령어를 생성하거나 실행하지 않으며, 히스토리에도 저장하지 않습니다.
+pub async fn execute_explain_command(cli: &Cli, config: &Config, command: &str) -> Result<()> {
+    // 1. 위험도 평가 (AI 없이 즉시 계산)
+    let validator = CommandValidator::load()?;
+    let assessment = validator.assess(command);
+    ConfirmPrompt::new().show_risk_assessment(command, &assessment);
+
+    // 2. 컨텍스트 수집 (현재 디렉토리, OS 등)
+    let ctx = context::get_current_context();
+    if cli.debug {
+        eprintln!("{} {}", "DEBUG Context:".yellow(), ctx);
+    }
+
+    // 3. provider로 설명 생성
+    let provider_name = cli.provider.as_deref().unwrap_or(&config.default_provider);
+    let provider = ProviderFactory::create_with_fallback(provider_name, config)?;
+    show_explanation(provider.as_ref(), command, &ctx).await;
+
+    Ok(())
+}
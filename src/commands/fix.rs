@@ -0,0 +1,163 @@
+use crate::ai::CommandFailure;
+use crate::cli::{Cli, HookShell};
+use crate::error::Result;
+use crate::executor::sandbox::Sandbox;
+use crate::executor::{CommandValidator, DangerLevel};
+use crate::ui::create_spinner;
+use colored::*;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// zsh용 hook (`eval "$(askai hook zsh)"`)
+const ZSH_HOOK: &str = include_str!("hook.zsh");
+
+/// bash용 hook (`eval "$(askai hook bash)"`)
+const BASH_HOOK: &str = include_str!("hook.bash");
+
+/// `--rerun`에서 실패한 명령어를 다시 실행해 볼 최대 시간
+const RERUN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 셸 hook이 기록한 마지막으로 실패한 명령어
+#[derive(Debug, Clone, PartialEq)]
+pub struct LastFailure {
+    /// 실패한 명령어
+    pub command: String,
+    /// 종료 코드
+    pub exit_code: i32,
+    /// 실패한 디렉토리
+    pub cwd: PathBuf,
+}
+
+impl LastFailure {
+    /// hook이 기록하는 파일 (`~/.askai/last_failure`)
+    pub fn path() -> PathBuf {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        PathBuf::from(home).join(".askai").join("last_failure")
+    }
+
+    /// 기록된 실패 읽기 (기록이 없으면 `None`)
+    pub fn load() -> Result<Option<Self>> {
+        match std::fs::read_to_string(Self::path()) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// hook이 쓰는 형식 해석: 종료 코드, 디렉토리, 명령어(여러 줄 가능) 순서
+    fn parse(content: &str) -> Option<Self> {
+        let mut lines = content.splitn(3, '\n');
+        let exit_code = lines.next()?.trim().parse().ok()?;
+        let cwd = PathBuf::from(lines.next()?);
+        let command = lines.next()?.trim_end().to_string();
+
+        if command.is_empty() {
+            return None;
+        }
+        Some(Self { command, exit_code, cwd })
+    }
+}
+
+/// `askai hook <shell>`: `askai fix`가 읽는 기록을 남기는 셸 hook 출력
+pub fn execute_hook_command(shell: HookShell) -> Result<()> {
+    match shell {
+        HookShell::Zsh => print!("{}", ZSH_HOOK),
+        HookShell::Bash => print!("{}", BASH_HOOK),
+    }
+    Ok(())
+}
+
+/// `askai fix`: 마지막으로 실패한 명령어와 그 에러를 복구 입력으로 준비
+///
+/// `rerun`이면 명령어를 샌드박스에서 다시 실행해 에러 출력을 모읍니다.
+/// 샌드박스 안에서도 위험한 명령어(Low가 아닌 것)는 다시 실행하지 않습니다.
+pub async fn load_last_failure(cli: &Cli, rerun: bool) -> Result<CommandFailure> {
+    let Some(last) = LastFailure::load()? else {
+        eprintln!("{} No failed command recorded yet.", "[X]".red());
+        eprintln!("{} Install the shell hook so askai can see failed commands:", "[TIP]".cyan());
+        eprintln!("  {}", "echo 'eval \"$(askai hook zsh)\"' >> ~/.zshrc".yellow());
+        std::process::exit(1);
+    };
+
+    if !cli.quiet {
+        eprintln!(
+            "{} Last failed command (exit {}): {}",
+            "[i]".cyan(),
+            last.exit_code,
+            last.command.bold()
+        );
+        let cwd = std::env::current_dir()?;
+        if cwd != last.cwd {
+            eprintln!("{} It failed in {}; you are now in {}.", "[!]".yellow(), last.cwd.display(), cwd.display());
+        }
+    }
+
+    let stderr = if rerun { rerun_in_sandbox(cli, &last).await } else { String::new() };
+
+    Ok(CommandFailure {
+        command: last.command,
+        exit_code: last.exit_code,
+        stderr,
+    })
+}
+
+/// 실패한 명령어를 샌드박스에서 다시 실행해 stderr 수집 (실패하면 빈 문자열)
+async fn rerun_in_sandbox(cli: &Cli, last: &LastFailure) -> String {
+    let level = match CommandValidator::load() {
+        Ok(validator) => validator.assess(&last.command).level,
+        Err(_) => DangerLevel::High,
+    };
+    if level != DangerLevel::Low {
+        if !cli.quiet {
+            eprintln!("{} Not re-running a {:?}-risk command; asking without its output.", "[!]".yellow(), level);
+        }
+        return String::new();
+    }
+
+    let spinner = if !cli.quiet {
+        create_spinner("Re-running the command in a sandbox to capture its error...")
+    } else {
+        create_spinner("")
+    };
+    let result = Sandbox::new(&last.cwd).with_timeout(RERUN_TIMEOUT).run(&last.command).await;
+    spinner.finish_and_clear();
+
+    match result {
+        Ok(simulation) => simulation.stderr,
+        Err(e) => {
+            if !cli.quiet {
+                eprintln!("{} Could not re-run the command: {}", "[!]".yellow(), e);
+            }
+            String::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_last_failure() {
+        let failure = LastFailure::parse("127\n/home/user/app\ngti status\n").unwrap();
+        assert_eq!(failure.exit_code, 127);
+        assert_eq!(failure.cwd, PathBuf::from("/home/user/app"));
+        assert_eq!(failure.command, "gti status");
+
+        // 여러 줄 명령어는 그대로 보존
+        let failure = LastFailure::parse("1\n/tmp\nfor f in *; do\n  mv $f\ndone\n").unwrap();
+        assert_eq!(failure.command, "for f in *; do\n  mv $f\ndone");
+
+        assert_eq!(LastFailure::parse(""), None);
+        assert_eq!(LastFailure::parse("abc\n/tmp\nls\n"), None);
+        assert_eq!(LastFailure::parse("2\n/tmp\n\n"), None);
+    }
+
+    #[test]
+    fn test_hooks_record_to_last_failure() {
+        for hook in [ZSH_HOOK, BASH_HOOK] {
+            assert!(hook.contains(".askai/last_failure"));
+            assert!(hook.contains("exit_code -ne 130"));
+        }
+    }
+}
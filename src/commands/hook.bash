@@ -0,0 +1,22 @@
+# askai fix: 마지막으로 실패한 명령어를 ~/.askai/last_failure에 기록
+# 설치: echo 'eval "$(askai hook bash)"' >> ~/.bashrc
+
+_askai_prompt_command() {
+    local exit_code=$?
+    local number command
+    read -r number command <<< "$(HISTTIMEFORMAT= builtin history 1)"
+
+    # 빈 줄(히스토리 번호가 그대로), Ctrl-C로 중단한 명령어, askai 자신은 기록하지 않음
+    if [[ -n "$command" && "$number" != "$_askai_last_number" && $exit_code -ne 0 && $exit_code -ne 130 && "$command" != askai* ]]; then
+        mkdir -p "$HOME/.askai"
+        printf '%s\n%s\n%s\n' "$exit_code" "$PWD" "$command" > "$HOME/.askai/last_failure"
+    fi
+    _askai_last_number="$number"
+
+    # 뒤에 오는 PROMPT_COMMAND가 원래 exit code를 볼 수 있도록 그대로 반환
+    return $exit_code
+}
+
+if [[ "$PROMPT_COMMAND" != *_askai_prompt_command* ]]; then
+    PROMPT_COMMAND="_askai_prompt_command${PROMPT_COMMAND:+; $PROMPT_COMMAND}"
+fi
@@ -1,5 +1,27 @@
+pub mod audit;
 pub mod batch;
 pub mod daemon;
+pub mod candidates;
+pub mod chat;
+pub mod consensus;
+pub mod exec;
+pub mod explain;
+pub mod fix;
+pub mod plan;
+pub mod repair;
+pub mod script;
+pub mod simulate;
 
+pub use audit::execute_audit_command;
 pub use batch::execute_batch_mode;
 pub use daemon::{start_daemon, stop_daemon, check_daemon_status};
+pub use candidates::execute_candidates_mode;
+pub use chat::execute_chat_command;
+pub use consensus::execute_consensus_mode;
+pub use exec::execute_or_print;
+pub use explain::{execute_explain_command, show_explanation};
+pub use fix::{execute_hook_command, load_last_failure};
+pub use plan::execute_plan_mode;
+pub use repair::{ask_for_fix, request_repair};
+pub use script::execute_script_mode;
+pub use simulate::simulate_command;
@@ -0,0 +1,199 @@
+use crate::ai::factory::ProviderFactory;
+use crate::ai::response_processor::PlannedTask;
+use crate::audit::{self, Approval, AuditRecord, Outcome};
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::error::{AskAiError, Result};
+use crate::executor::{batch::BatchExecutor, planner::{ExecutionPlan, Task}, CommandValidator, DangerLevel};
+use crate::ui::{create_spinner, ConfirmPrompt};
+use colored::*;
+use std::env;
+
+/// 계획 모드: 여러 단계 요청을 의존성이 있는 작업으로 나누어 DAG 순서로 실행
+///
+/// 모든 작업은 실행 전에 하나씩 안전성 검사를 거치며, 차단된 작업이 하나라도 있으면
+/// 계획 전체를 실행하지 않습니다. 의존하는 작업이 실패하면 그 뒤의 작업은 건너뜁니다.
+pub async fn execute_plan_mode(cli: &Cli, config: &Config, ctx: &str) -> Result<()> {
+    let provider_name = cli.provider.as_deref().unwrap_or(&config.default_provider);
+    let provider = ProviderFactory::create_with_fallback(provider_name, config)?;
+
+    // 1. 작업 분할
+    let spinner = if !cli.quiet {
+        create_spinner(&format!("Planning tasks with {} provider...", provider.name()))
+    } else {
+        create_spinner("")
+    };
+    let result = provider.plan_tasks(&cli.prompt_text(), ctx).await;
+    spinner.finish_and_clear();
+
+    let plan = build_plan(result?)?;
+    if !cli.quiet {
+        eprintln!("{} Plan from {}!", "[v]".green(), provider.answered_by());
+    }
+
+    // 2. 작업별 안전성 검사 (금지 패턴이 하나라도 있으면 중단)
+    let validator = CommandValidator::load()?;
+    let mut reports = Vec::new();
+    for task in &plan.tasks {
+        match validator.validate(&task.command) {
+            Ok(report) => reports.push((task.id, report)),
+            Err(e) => {
+                eprintln!("{} Blocked task {}: {}", "[X]".red(), task.id, task.command.dimmed());
+                return Err(e);
+            }
+        }
+    }
+
+    let approval = if cli.yes { Approval::YesFlag } else { Approval::Interactive };
+    let audit_record = |task: &Task, approval: Approval, outcome: Outcome| {
+        let report = validator.assess(&task.command);
+        let record = AuditRecord::new(&cli.prompt_text(), &task.command, &provider.answered_by(), &report, approval, outcome);
+        match (&task.working_dir, env::current_dir()) {
+            (Some(dir), Ok(cwd)) => record.with_cwd(&cwd.join(dir)),
+            _ => record,
+        }
+    };
+    let log_all = |approval: Approval, outcome: Outcome| {
+        for task in &plan.tasks {
+            audit::log(audit_record(task, approval, outcome.clone()));
+        }
+    };
+
+    // 3. 사용자 확인 (--dry-run이면 계획만 출력)
+    let prompt = ConfirmPrompt::new();
+    if cli.dry_run {
+        prompt.show_plan(&plan, &reports);
+        eprintln!("\n{} Dry run: the plan was not executed.", "[i]".cyan());
+        return Ok(());
+    }
+
+    // Critical 작업이 있으면 --yes로도 승인할 수 없음
+    let critical = reports.iter().any(|(_, report)| report.level == DangerLevel::Critical);
+    if critical && (cli.yes || cli.quiet) {
+        log_all(Approval::None, Outcome::Refused {
+            reason: "critical command can't be auto-approved".to_string(),
+        });
+        eprintln!("{}", "[X] Plan contains a critical task; refusing to auto-approve. Run without --yes to confirm it.".red());
+        std::process::exit(1);
+    }
+
+    if !cli.yes && !cli.quiet {
+        if !prompt.confirm_plan(&plan, &reports)? {
+            log_all(Approval::None, Outcome::Cancelled);
+            eprintln!("{}", "[X] User cancelled.".yellow());
+            std::process::exit(1);
+        }
+    } else if !cli.yes && cli.quiet {
+        log_all(Approval::None, Outcome::Refused {
+            reason: "--yes flag required in quiet mode".to_string(),
+        });
+        eprintln!("{}", "[X] --yes flag required in quiet mode.".red());
+        std::process::exit(1);
+    } else if !cli.quiet {
+        // --yes로 확인 화면을 건너뛰어도 무엇을 실행하는지는 보여줌
+        prompt.show_plan(&plan, &reports);
+    }
+
+    // 4. 실행 (같은 단계의 작업은 병렬)
+    let executor = BatchExecutor::new(cli.max_parallel.unwrap_or(4));
+    if !cli.quiet {
+        eprintln!("\n{} Running the plan...", "[*]".cyan().bold());
+    }
+    let batch_result = executor.execute(&plan).await;
+
+    // 4-1. 작업별 결과를 감사 로그에 기록
+    for task_result in &batch_result.task_results {
+        let Some(task) = plan.tasks.iter().find(|task| task.id == task_result.task_id) else {
+            continue;
+        };
+        let outcome = match &task_result.error {
+            Some(error) => Outcome::Failed { error: error.clone() },
+            None => Outcome::Succeeded,
+        };
+        audit::log(audit_record(task, approval, outcome));
+    }
+
+    // 5. 결과 출력
+    let failed = batch_result.failed_tasks();
+    if !cli.quiet {
+        if failed.is_empty() {
+            eprintln!("\n{} Plan complete! {} task(s) in {}ms.", "[OK]".green().bold(), batch_result.total, batch_result.total_duration_ms);
+        } else {
+            eprintln!(
+                "\n{} Plan finished with {} of {} task(s) not completed:",
+                "[X]".red(),
+                failed.len(),
+                batch_result.total
+            );
+            for task in &failed {
+                eprintln!("  - [{}] {}: {}", task.task_id, task.description, task.error.as_deref().unwrap_or_default().red());
+            }
+        }
+    }
+
+    if !batch_result.all_succeeded() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// provider가 나눈 작업으로 실행 계획 생성 (알 수 없는 의존성이나 순환이 있으면 에러)
+fn build_plan(planned: Vec<PlannedTask>) -> Result<ExecutionPlan> {
+    if planned.is_empty() {
+        return Err(AskAiError::ParallelExecutionError("the plan has no tasks".to_string()));
+    }
+
+    let mut plan = ExecutionPlan::new(Vec::new());
+    for planned_task in planned {
+        let mut task = Task::new(planned_task.id, planned_task.command).with_description(planned_task.description);
+        if let Some(dir) = planned_task.working_dir {
+            task = task.with_dir(dir);
+        }
+        for dependency in planned_task.depends_on {
+            plan.add_dependency(planned_task.id, dependency);
+        }
+        plan.add_task(task);
+    }
+
+    plan.check_dependencies()?;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planned(id: usize, command: &str, depends_on: Vec<usize>) -> PlannedTask {
+        PlannedTask {
+            id,
+            description: command.to_string(),
+            command: command.to_string(),
+            working_dir: None,
+            depends_on,
+        }
+    }
+
+    #[test]
+    fn test_build_plan() {
+        let plan = build_plan(vec![
+            planned(1, "npm run build", vec![]),
+            planned(2, "cargo build", vec![]),
+            planned(3, "npm test", vec![1, 2]),
+        ])
+        .unwrap();
+
+        assert_eq!(plan.task_count(), 3);
+        assert!(plan.can_parallelize);
+        let stages: Vec<Vec<usize>> = plan
+            .get_parallel_groups()
+            .iter()
+            .map(|group| group.iter().map(|task| task.id).collect())
+            .collect();
+        assert_eq!(stages, vec![vec![1, 2], vec![3]]);
+
+        // 순환 의존성은 실행 전에 거부
+        let result = build_plan(vec![planned(1, "a", vec![2]), planned(2, "b", vec![1])]);
+        assert!(matches!(result, Err(AskAiError::ParallelExecutionError(_))));
+        assert!(build_plan(Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use crate::ai::{factory::ProviderFactory, response_processor::GeneratedCommand, AiProvider, CommandFailure};
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::error::{AskAiError, Result};
+use crate::ui::create_spinner;
+use colored::*;
+
+/// `--exec`로 실행한 명령어가 실패했을 때 provider에 고친 명령어를 요청
+///
+/// 원래 요청, 실패한 명령어, 종료 코드와 stderr를 복구 프롬프트로 보냅니다.
+/// 고친 명령어는 처음 생성한 명령어와 똑같이 안전성 검사와 사용자 확인을 거칩니다.
+///
+/// # Returns
+/// * 고친 명령어와 실제로 응답한 provider (요청이 실패했거나 같은 명령어를 다시 제안하면 `None`)
+pub async fn request_repair(
+    cli: &Cli,
+    config: &Config,
+    provider_name: &str,
+    ctx: &str,
+    failure: &CommandFailure,
+    round: usize,
+) -> Option<(GeneratedCommand, String)> {
+    let provider = match ProviderFactory::create_with_fallback(provider_name, config) {
+        Ok(provider) => provider,
+        Err(e) => {
+            if !cli.quiet {
+                eprintln!("{} Could not ask for a fix: {}", "[X]".red(), e);
+            }
+            return None;
+        }
+    };
+
+    let status = format!(
+        "Asking {} provider to fix the command ({}/{})...",
+        provider.name(),
+        round,
+        config.repair_rounds
+    );
+    match ask_for_fix(cli, provider.as_ref(), ctx, failure, &status).await {
+        Ok(generated) => Some((generated, provider.answered_by())),
+        Err(e) => {
+            if !cli.quiet {
+                eprintln!("{} Could not get a fix: {}", "[X]".red(), e);
+            }
+            None
+        }
+    }
+}
+
+/// provider에 실패한 명령어를 보내 고친 명령어를 받음 (`--exec` 복구와 `askai fix`가 함께 사용)
+///
+/// 같은 명령어를 다시 실행해도 같은 에러가 날 뿐이므로, 같은 명령어를 다시 제안하면 에러를 반환합니다.
+pub async fn ask_for_fix(
+    cli: &Cli,
+    provider: &dyn AiProvider,
+    ctx: &str,
+    failure: &CommandFailure,
+    status: &str,
+) -> Result<GeneratedCommand> {
+    let spinner = if !cli.quiet {
+        create_spinner(status)
+    } else {
+        create_spinner("")
+    };
+    let result = provider.repair_command(&cli.prompt_text(), failure, ctx).await;
+    spinner.finish_and_clear();
+
+    let generated = result?;
+    if generated.command.trim() == failure.command.trim() {
+        return Err(AskAiError::AiCliError(format!(
+            "{} suggested the same command again",
+            provider.answered_by()
+        )));
+    }
+
+    if !cli.quiet {
+        eprintln!("{} Suggested fix from {}:", "[v]".green(), provider.answered_by());
+        // 확인 화면이 없으면 무엇이 바뀌었는지 여기서 보여줌
+        if cli.yes {
+            if let Some(explanation) = &generated.details.explanation {
+                eprintln!("  {}", explanation.dimmed());
+            }
+        }
+    }
+
+    Ok(generated)
+}
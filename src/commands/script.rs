@@ -0,0 +1,183 @@
+use crate::cli::Cli;
+use crate::config::Config;
+use crate::error::Result;
+use crate::ai::factory::ProviderFactory;
+use crate::ai::history::{CommandHistory, HistoryStore};
+use crate::ai::response_processor::CommandDetails;
+use crate::audit::{self, Approval, AuditRecord, Outcome};
+use crate::executor::{CommandValidator, DangerLevel};
+use crate::executor::runner::CommandRunner;
+use crate::ui::{ConfirmPrompt, create_spinner};
+use chrono::Utc;
+use colored::*;
+use std::fs;
+use std::path::Path;
+
+/// 스크립트 모드: 여러 줄 bash 스크립트를 생성하여 출력 / 저장 / 실행
+pub async fn execute_script_mode(cli: &Cli, config: &Config, ctx: &str) -> Result<()> {
+    let provider_name = cli.provider.as_deref().unwrap_or(&config.default_provider);
+    let provider = ProviderFactory::create_with_fallback(provider_name, config)?;
+
+    // 1. 스크립트 생성
+    let spinner = if !cli.quiet {
+        create_spinner(&format!(
+            "Generating script with {} provider...",
+            provider.name()
+        ))
+    } else {
+        create_spinner("")
+    };
+
+    let script = provider.generate_script(&cli.prompt_text(), ctx).await?;
+
+    spinner.finish_and_clear();
+
+    // 2. 모든 명령어 줄 안전성 검사 (금지 패턴이 하나라도 있으면 중단)
+    let validator = CommandValidator::load()?;
+    let checked = validator.validate_script(&script)?;
+
+    let approval = if cli.yes { Approval::YesFlag } else { Approval::Interactive };
+    let audit_record = |approval: Approval, outcome: Outcome| {
+        let report = validator.assess(&script);
+        AuditRecord::new(&cli.prompt_text(), &script, &provider.answered_by(), &report, approval, outcome)
+    };
+
+    // 3. 사용자 확인 (--yes 플래그가 없으면)
+    // Critical 줄이 있으면 --yes로도 승인할 수 없음
+    let critical = checked.iter().any(|(_, level)| *level == DangerLevel::Critical);
+    if critical && (cli.yes || cli.quiet) {
+        audit::log(audit_record(Approval::None, Outcome::Refused {
+            reason: "critical command can't be auto-approved".to_string(),
+        }));
+        eprintln!("{}", "[X] Script contains a critical command; refusing to auto-approve. Run without --yes to confirm it.".red());
+        std::process::exit(1);
+    }
+
+    if !cli.yes && !cli.quiet {
+        let prompt = ConfirmPrompt::new();
+        if !prompt.confirm_script(&script, &checked)? {
+            audit::log(audit_record(Approval::None, Outcome::Cancelled));
+            eprintln!("{}", "[X] User cancelled.".yellow());
+            std::process::exit(1);
+        }
+    } else if !cli.yes && cli.quiet {
+        audit::log(audit_record(Approval::None, Outcome::Refused {
+            reason: "--yes flag required in quiet mode".to_string(),
+        }));
+        eprintln!("{}", "[X] --yes flag required in quiet mode.".red());
+        std::process::exit(1);
+    }
+
+    // 4. 출력 / 저장 / 실행
+    let mut exit_code = None;
+    let executed = if let Some(path) = &cli.output {
+        write_script(path, &script)?;
+        audit::log(audit_record(approval, Outcome::Written { path: path.display().to_string() }));
+        if !cli.quiet {
+            eprintln!("{} Script written to {}", "[OK]".green(), path.display());
+        }
+        false
+    } else if cli.run {
+        let result = CommandRunner::new().execute(&script).await;
+        audit::log(audit_record(approval, match &result {
+            Ok(_) => Outcome::Succeeded,
+            Err(e) => Outcome::Failed { error: e.to_string() },
+        }));
+        result?;
+        true
+    } else if cli.exec {
+        // 셸 함수(`--exec`)로 실행하면 출력이 eval되지 않으므로 자식 bash에서 직접 실행
+        if !cli.quiet {
+            eprintln!("{}", "[>] Running the script...".cyan());
+        }
+        let result = CommandRunner::new().stream(&script).await?;
+        audit::log(audit_record(approval, Outcome::from_exit_code(Some(result.exit_code))));
+        if result.exit_code != 0 && !cli.quiet {
+            eprintln!("{} Script exited with code {}.", "[X]".red(), result.exit_code);
+        }
+        exit_code = Some(result.exit_code);
+        true
+    } else {
+        // eval용으로 stdout에 출력 (스크립트의 `set -e`, `exit`가 사용자 셸에 남지 않도록 서브셸로 감쌈)
+        println!("{}", in_subshell(&script));
+        audit::log(audit_record(approval, Outcome::Printed));
+        true
+    };
+
+    // 5. 히스토리 저장 (RAG)
+    let store = HistoryStore::new();
+    let history_entry = CommandHistory {
+        prompt: cli.prompt_text(),
+        command: script,
+        timestamp: Utc::now(),
+        executed,
+        provider: provider.answered_by(),
+        exit_code,
+        edited_from: None,
+        details: CommandDetails::default(),
+    };
+
+    if let Err(e) = store.add(history_entry) {
+        if cli.debug {
+            eprintln!("{} Failed to save history: {}", "DEBUG:".yellow(), e);
+        }
+    }
+
+    if let Some(code) = exit_code.filter(|code| *code != 0) {
+        std::process::exit(code);
+    }
+    Ok(())
+}
+
+/// eval해도 현재 셸의 옵션과 상태를 바꾸지 않도록 서브셸 `( ... )`로 감싼 스크립트
+fn in_subshell(script: &str) -> String {
+    format!("(\n{}\n)", script.trim_end())
+}
+
+/// 스크립트를 파일로 저장하고 실행 권한 부여
+fn write_script(path: &Path, script: &str) -> Result<()> {
+    fs::write(path, format!("{}\n", script))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_script_is_executable() {
+        let dir = std::env::temp_dir().join(format!("askai-script-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("rename.sh");
+
+        write_script(&path, "#!/usr/bin/env bash\necho hi").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "#!/usr/bin/env bash\necho hi\n");
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o755);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_evaluated_script_keeps_shell_options() {
+        let script = "#!/usr/bin/env bash\nset -euo pipefail\ncat <<EOF\nhi\nEOF\nfalse";
+        let shell = format!("eval \"$(cat <<'SCRIPT'\n{}\nSCRIPT\n)\"; echo \"after $-\"", in_subshell(script));
+
+        let output = tokio::process::Command::new("bash").arg("-c").arg(&shell).output().await.unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.starts_with("hi\nafter "));
+        assert!(!stdout.trim_end().rsplit(' ').next().unwrap().contains('e'));
+    }
+}
@@ -0,0 +1,44 @@
+use crate::cli::Cli;
+use crate::error::{AskAiError, Result};
+use crate::executor::sandbox::Sandbox;
+use crate::ui::{ConfirmPrompt, create_spinner};
+use colored::*;
+
+/// `--dry-run`: 생성된 명령어를 샌드박스에서 실행하고 출력과 파일 변경을 보여줌
+///
+/// 실제 디렉토리는 바뀌지 않고 stdout으로 명령어를 출력하지도 않으므로 `eval`해도 아무 일도 일어나지 않습니다.
+/// 샌드박스를 만들 수 없는 환경에서는 명령어를 실행하지 않습니다.
+/// 위험도 표시와 Critical 명령어 확인은 호출하는 쪽에서 먼저 합니다.
+///
+/// # Returns
+/// * 시뮬레이션한 명령어의 종료 코드 (샌드박스를 만들지 못했으면 0)
+pub async fn simulate_command(cli: &Cli, command: &str) -> Result<i32> {
+    let prompt = ConfirmPrompt::new();
+    let cwd = std::env::current_dir()?;
+    let spinner = if !cli.quiet {
+        create_spinner("Simulating command in a sandbox...")
+    } else {
+        create_spinner("")
+    };
+
+    let result = Sandbox::new(&cwd).run(command).await;
+
+    spinner.finish_and_clear();
+
+    match result {
+        Ok(simulation) => {
+            if !cli.quiet {
+                prompt.show_simulation(&simulation);
+            }
+            Ok(simulation.exit_code.unwrap_or(1))
+        }
+        Err(AskAiError::SandboxUnavailable(reason)) => {
+            if !cli.quiet {
+                eprintln!("\n{} Could not create a sandbox: {}", "[!]".yellow(), reason);
+                eprintln!("{} Showing the command only (not executed).", "[i]".cyan());
+            }
+            Ok(0)
+        }
+        Err(e) => Err(e),
+    }
+}
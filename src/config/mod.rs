@@ -1,5 +1,6 @@
 use crate::error::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -8,7 +9,7 @@ use std::path::PathBuf;
 /// 설정 파일은 ~/.askai/config.toml에 저장됩니다.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
-    /// 기본 AI provider (gemini, claude, codex)
+    /// 기본 AI provider (gemini, claude, codex, openai, ollama)
     #[serde(default = "default_provider")]
     pub default_provider: String,
 
@@ -16,6 +17,26 @@ pub struct Config {
     #[serde(default = "default_auto_approve")]
     pub auto_approve_safe_commands: bool,
 
+    /// 기본 provider 실패 시 순서대로 시도할 provider 목록
+    #[serde(default)]
+    pub fallback_providers: Vec<String>,
+
+    /// provider 한 개당 응답 대기 시간 (초 단위)
+    #[serde(default = "default_provider_timeout_secs")]
+    pub provider_timeout_secs: u64,
+
+    /// provider별 타임아웃 재정의 (예: { ollama = 180 })
+    #[serde(default)]
+    pub provider_timeouts: HashMap<String, u64>,
+
+    /// --consensus 모드에서 비교할 provider 목록
+    #[serde(default = "default_consensus_providers")]
+    pub consensus_providers: Vec<String>,
+
+    /// --exec로 실행한 명령어가 실패했을 때 provider에 고친 명령어를 요청하는 최대 횟수 (0이면 끔)
+    #[serde(default = "default_repair_rounds")]
+    pub repair_rounds: usize,
+
     /// 히스토리 파일 경로
     #[serde(default = "default_history_path")]
     pub history_path: String,
@@ -43,12 +64,184 @@ pub struct Config {
     /// 캐시 최대 항목 수
     #[serde(default = "default_cache_max_entries")]
     pub cache_max_entries: usize,
+
+    /// OpenAI 호환 HTTP provider 설정 ([openai] 테이블)
+    #[serde(default)]
+    pub openai: OpenAiConfig,
+
+    /// 로컬 Ollama provider 설정 ([ollama] 테이블)
+    #[serde(default)]
+    pub ollama: OllamaConfig,
+
+    /// 사용자 정의 CLI provider 목록 ([providers.<name>] 테이블)
+    #[serde(default)]
+    pub providers: HashMap<String, GenericCliConfig>,
+
+    /// provider로 보내는 컨텍스트의 비밀 값 가리기 설정 ([redaction] 테이블)
+    #[serde(default)]
+    pub redaction: RedactionConfig,
+}
+
+/// 프롬프트 전달 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptInput {
+    /// 명령행 인자로 전달 (`{prompt}` 자리표시자 치환)
+    #[default]
+    Argv,
+    /// 표준 입력으로 전달
+    Stdin,
+}
+
+/// 임의의 AI CLI를 provider로 등록하기 위한 설정
+///
+/// ```toml
+/// [providers.llm]
+/// binary = "llm"
+/// args = ["-m", "gpt-4o-mini", "{prompt}"]
+///
+/// [providers.aichat]
+/// binary = "aichat"
+/// prompt_input = "stdin"
+/// env = { AICHAT_THEME = "none" }
+/// timeout_secs = 60
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenericCliConfig {
+    /// 실행할 CLI 바이너리
+    pub binary: String,
+
+    /// 인자 템플릿 (`{prompt}`는 완성된 프롬프트로 치환)
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// 프롬프트 전달 방식 (argv | stdin)
+    #[serde(default)]
+    pub prompt_input: PromptInput,
+
+    /// 추가 환경 변수
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// 실행 타임아웃 (초 단위, None이면 무제한)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+
+    /// CLI가 없을 때 보여줄 설치 안내
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub install_hint: Option<String>,
+
+    /// 프롬프트 템플릿에 추가할 provider별 규칙
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<String>,
+}
+
+/// OpenAI 호환 `/v1/chat/completions` endpoint 설정
+///
+/// 사내 gateway나 로컬 llama.cpp/vLLM 서버를 가리킬 수 있습니다.
+///
+/// ```toml
+/// [openai]
+/// base_url = "http://localhost:8000/v1"
+/// model = "llama-3-8b-instruct"
+/// api_key_env = "OPENAI_API_KEY"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAiConfig {
+    /// API base URL (`/chat/completions`가 뒤에 붙음)
+    #[serde(default = "default_openai_base_url")]
+    pub base_url: String,
+
+    /// 사용할 모델 이름
+    #[serde(default = "default_openai_model")]
+    pub model: String,
+
+    /// API 키를 읽어올 환경 변수 이름 (변수가 없으면 인증 헤더 없이 요청)
+    #[serde(default = "default_openai_api_key_env")]
+    pub api_key_env: String,
+
+    /// 샘플링 temperature
+    #[serde(default = "default_openai_temperature")]
+    pub temperature: f32,
+
+    /// 요청 타임아웃 (초 단위)
+    #[serde(default = "default_openai_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// 로컬 Ollama 서버 설정 (오프라인 환경용)
+///
+/// ```toml
+/// [ollama]
+/// host = "http://localhost:11434"
+/// model = "qwen2.5-coder:7b"
+/// temperature = 0.1
+/// num_ctx = 4096
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaConfig {
+    /// Ollama 서버 주소
+    #[serde(default = "default_ollama_host")]
+    pub host: String,
+
+    /// 사용할 모델 이름
+    #[serde(default = "default_ollama_model")]
+    pub model: String,
+
+    /// 샘플링 temperature
+    #[serde(default = "default_ollama_temperature")]
+    pub temperature: f32,
+
+    /// 컨텍스트 윈도우 크기 (None이면 모델 기본값)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_ctx: Option<u32>,
+
+    /// 요청 타임아웃 (초 단위, 로컬 모델은 첫 로딩이 느림)
+    #[serde(default = "default_ollama_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+/// provider로 보내는 컨텍스트(현재 디렉토리, 히스토리, 프로젝트 정보)의 비밀 값 가리기 설정
+///
+/// 내장 탐지기(GitHub/AWS/Slack 토큰, `Authorization` 헤더, `*_TOKEN=` 할당 등)에 더해
+/// 정규표현식으로 탐지기를 추가할 수 있습니다. `secret` 그룹이 있으면 그 부분만 가립니다.
+///
+/// ```toml
+/// [redaction]
+/// entropy_threshold = 4.2
+/// detectors = { internal-token = "itk_[0-9a-f]{32}", db-url = "postgres://[^:]+:(?P<secret>[^@]+)@" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// 가리기 사용 여부
+    #[serde(default = "default_redaction_enabled")]
+    pub enabled: bool,
+
+    /// 이 값 이상의 Shannon 엔트로피(문자당 비트)를 가진 문자열은 비밀 값으로 취급
+    #[serde(default = "default_entropy_threshold")]
+    pub entropy_threshold: f64,
+
+    /// 엔트로피 검사를 할 최소 문자열 길이
+    #[serde(default = "default_min_entropy_length")]
+    pub min_entropy_length: usize,
+
+    /// 추가 탐지기 (이름 = 정규표현식)
+    #[serde(default)]
+    pub detectors: HashMap<String, String>,
 }
 
 fn default_provider() -> String {
     "gemini".to_string()
 }
 
+fn default_provider_timeout_secs() -> u64 {
+    60
+}
+
+fn default_consensus_providers() -> Vec<String> {
+    vec!["gemini".to_string(), "claude".to_string(), "codex".to_string()]
+}
+
 fn default_auto_approve() -> bool {
     false
 }
@@ -62,6 +255,10 @@ fn default_enable_rag() -> bool {
     true
 }
 
+fn default_repair_rounds() -> usize {
+    2
+}
+
 fn default_max_parallel() -> usize {
     4
 }
@@ -82,11 +279,99 @@ fn default_cache_max_entries() -> usize {
     1000
 }
 
+fn default_openai_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+fn default_openai_model() -> String {
+    "gpt-4o-mini".to_string()
+}
+
+fn default_openai_api_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
+fn default_openai_temperature() -> f32 {
+    0.2
+}
+
+fn default_openai_timeout_secs() -> u64 {
+    30
+}
+
+fn default_ollama_host() -> String {
+    "http://localhost:11434".to_string()
+}
+
+fn default_ollama_model() -> String {
+    "llama3.1".to_string()
+}
+
+fn default_ollama_temperature() -> f32 {
+    0.1
+}
+
+fn default_ollama_timeout_secs() -> u64 {
+    120
+}
+
+fn default_redaction_enabled() -> bool {
+    true
+}
+
+fn default_entropy_threshold() -> f64 {
+    4.0
+}
+
+fn default_min_entropy_length() -> usize {
+    20
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_redaction_enabled(),
+            entropy_threshold: default_entropy_threshold(),
+            min_entropy_length: default_min_entropy_length(),
+            detectors: HashMap::new(),
+        }
+    }
+}
+
+impl Default for OllamaConfig {
+    fn default() -> Self {
+        Self {
+            host: default_ollama_host(),
+            model: default_ollama_model(),
+            temperature: default_ollama_temperature(),
+            num_ctx: None,
+            timeout_secs: default_ollama_timeout_secs(),
+        }
+    }
+}
+
+impl Default for OpenAiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_openai_base_url(),
+            model: default_openai_model(),
+            api_key_env: default_openai_api_key_env(),
+            temperature: default_openai_temperature(),
+            timeout_secs: default_openai_timeout_secs(),
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             default_provider: default_provider(),
+            fallback_providers: Vec::new(),
+            provider_timeout_secs: default_provider_timeout_secs(),
+            provider_timeouts: HashMap::new(),
+            consensus_providers: default_consensus_providers(),
             auto_approve_safe_commands: default_auto_approve(),
+            repair_rounds: default_repair_rounds(),
             history_path: default_history_path(),
             enable_rag: default_enable_rag(),
             max_parallel_jobs: default_max_parallel(),
@@ -94,11 +379,40 @@ impl Default for Config {
             default_debug_mode: default_debug_mode(),
             cache_ttl_days: default_cache_ttl_days(),
             cache_max_entries: default_cache_max_entries(),
+            openai: OpenAiConfig::default(),
+            ollama: OllamaConfig::default(),
+            providers: HashMap::new(),
+            redaction: RedactionConfig::default(),
         }
     }
 }
 
 impl Config {
+    /// 특정 provider에 적용할 타임아웃 (초 단위)
+    ///
+    /// `provider_timeouts` 재정의가 있으면 그 값을 쓰고 (이름은 대소문자 무시),
+    /// 없으면 `provider_timeout_secs`와 provider 자체 `timeout_secs` 중 큰 값을 씁니다.
+    /// 그래서 `[ollama] timeout_secs = 120`이 기본 60초에 잘리지 않습니다.
+    pub fn timeout_for(&self, provider_name: &str) -> u64 {
+        let normalized = provider_name.to_lowercase();
+        if let Some((_, secs)) = self.provider_timeouts
+            .iter()
+            .find(|(name, _)| name.to_lowercase() == normalized)
+        {
+            return *secs;
+        }
+
+        let own = match self.providers.iter().find(|(name, _)| name.to_lowercase() == normalized) {
+            Some((_, cli_config)) => cli_config.timeout_secs,
+            None => match normalized.as_str() {
+                "openai" => Some(self.openai.timeout_secs),
+                "ollama" => Some(self.ollama.timeout_secs),
+                _ => None,
+            },
+        };
+        own.map_or(self.provider_timeout_secs, |secs| secs.max(self.provider_timeout_secs))
+    }
+
     /// 설정 파일 경로 가져오기
     fn config_path() -> PathBuf {
         let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
@@ -194,6 +508,7 @@ mod tests {
         assert_eq!(config.default_debug_mode, false);
         assert_eq!(config.cache_ttl_days, 7);
         assert_eq!(config.cache_max_entries, 1000);
+        assert_eq!(config.repair_rounds, 2);
     }
 
     #[test]
@@ -216,6 +531,7 @@ mod tests {
             default_debug_mode = true
             cache_ttl_days = 14
             cache_max_entries = 2000
+            repair_rounds = 0
         "#;
 
         let config: Config = toml::from_str(toml_str).unwrap();
@@ -227,5 +543,130 @@ mod tests {
         assert_eq!(config.default_debug_mode, true);
         assert_eq!(config.cache_ttl_days, 14);
         assert_eq!(config.cache_max_entries, 2000);
+        assert_eq!(config.repair_rounds, 0);
+    }
+
+    #[test]
+    fn test_openai_config_deserialization() {
+        let toml_str = r#"
+            default_provider = "openai"
+
+            [openai]
+            base_url = "http://localhost:8000/v1"
+            model = "llama-3-8b-instruct"
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.default_provider, "openai");
+        assert_eq!(config.openai.base_url, "http://localhost:8000/v1");
+        assert_eq!(config.openai.model, "llama-3-8b-instruct");
+        // 지정하지 않은 필드는 기본값
+        assert_eq!(config.openai.api_key_env, "OPENAI_API_KEY");
+        assert_eq!(config.openai.timeout_secs, 30);
+    }
+
+    #[test]
+    fn test_ollama_config_deserialization() {
+        let toml_str = r#"
+            [ollama]
+            host = "http://gpu-box:11434"
+            model = "qwen2.5-coder:7b"
+            num_ctx = 8192
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ollama.host, "http://gpu-box:11434");
+        assert_eq!(config.ollama.model, "qwen2.5-coder:7b");
+        assert_eq!(config.ollama.num_ctx, Some(8192));
+        assert_eq!(config.ollama.timeout_secs, 120);
+    }
+
+    #[test]
+    fn test_fallback_config_deserialization() {
+        let toml_str = r#"
+            default_provider = "gemini"
+            fallback_providers = ["claude", "ollama"]
+            provider_timeout_secs = 20
+
+            [provider_timeouts]
+            ollama = 180
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.fallback_providers, vec!["claude", "ollama"]);
+        assert_eq!(config.timeout_for("claude"), 20);
+        assert_eq!(config.timeout_for("ollama"), 180);
+    }
+
+    #[test]
+    fn test_timeout_for_respects_provider_timeouts() {
+        let toml_str = r#"
+            [provider_timeouts]
+            Claude = 15
+
+            [ollama]
+            timeout_secs = 120
+
+            [providers.llm]
+            binary = "llm"
+            timeout_secs = 300
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        // 재정의 키는 대소문자를 무시
+        assert_eq!(config.timeout_for("claude"), 15);
+        // provider 자체 타임아웃이 기본값보다 길면 그대로 유지
+        assert_eq!(config.timeout_for("ollama"), 120);
+        assert_eq!(config.timeout_for("LLM"), 300);
+        // 기본값보다 짧은 provider 타임아웃은 기본값으로
+        assert_eq!(config.timeout_for("openai"), 60);
+        assert_eq!(config.timeout_for("gemini"), 60);
+    }
+
+    #[test]
+    fn test_generic_provider_deserialization() {
+        let toml_str = r#"
+            [providers.llm]
+            binary = "llm"
+            args = ["-m", "gpt-4o-mini", "{prompt}"]
+
+            [providers.aichat]
+            binary = "aichat"
+            prompt_input = "stdin"
+            env = { AICHAT_THEME = "none" }
+            timeout_secs = 60
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.providers.len(), 2);
+
+        let llm = &config.providers["llm"];
+        assert_eq!(llm.binary, "llm");
+        assert_eq!(llm.args, vec!["-m", "gpt-4o-mini", "{prompt}"]);
+        assert_eq!(llm.prompt_input, PromptInput::Argv);
+        assert_eq!(llm.timeout_secs, None);
+
+        let aichat = &config.providers["aichat"];
+        assert_eq!(aichat.prompt_input, PromptInput::Stdin);
+        assert_eq!(aichat.env.get("AICHAT_THEME").map(String::as_str), Some("none"));
+        assert_eq!(aichat.timeout_secs, Some(60));
+    }
+
+    #[test]
+    fn test_redaction_deserialization() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.redaction.enabled);
+        assert_eq!(config.redaction.min_entropy_length, 20);
+
+        let toml_str = r#"
+            [redaction]
+            entropy_threshold = 4.5
+            detectors = { internal-token = "itk_[0-9a-f]{32}" }
+        "#;
+
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.redaction.enabled);
+        assert_eq!(config.redaction.entropy_threshold, 4.5);
+        assert_eq!(config.redaction.detectors["internal-token"], "itk_[0-9a-f]{32}");
     }
 }
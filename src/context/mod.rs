@@ -1,12 +1,33 @@
 pub mod project;
 pub mod detector;
 pub mod scanner;
+pub mod redact;
 
 use std::env;
+use colored::*;
+use once_cell::sync::Lazy;
 use crate::ai::history::HistoryStore;
+use crate::config::Config;
 pub use project::{ProjectInfo, ProjectType};
 pub use detector::ProjectDetector;
 pub use scanner::{ProjectScanner, ScanResult};
+pub use redact::{redaction_summary, Redactor};
+
+/// 설정 파일의 `[redaction]`으로 만든 전역 redactor (추가 탐지기가 잘못되면 내장 탐지기만 사용)
+static REDACTOR: Lazy<Redactor> = Lazy::new(|| {
+    let config = Config::load().unwrap_or_default();
+    Redactor::new(&config.redaction).unwrap_or_else(|e| {
+        eprintln!("{} {} (using built-in detectors only)", "[!]".yellow(), e);
+        Redactor::default()
+    })
+});
+
+/// provider로 보내기 전에 비밀 값 가리기
+///
+/// 컨텍스트 문자열은 모두 이 함수를 거쳐 만들어집니다.
+pub fn redact(text: &str) -> String {
+    REDACTOR.redact(text)
+}
 
 /// 현재 실행 환경의 컨텍스트 정보를 수집
 pub fn get_current_context() -> String {
@@ -16,18 +37,20 @@ pub fn get_current_context() -> String {
 
     let shell = env::var("SHELL").unwrap_or_else(|_| "bash".to_string());
 
-    format!(
+    redact(&format!(
         "Current directory: {}\n\
          Shell: {}\n\
          OS: {}",
         cwd,
         shell,
         std::env::consts::OS
-    )
+    ))
 }
 
 /// RAG를 사용하여 관련 히스토리를 포함한 향상된 컨텍스트 생성
 ///
+/// 과거 프롬프트와 명령어에 들어 있는 토큰은 `[REDACTED:<탐지기>]`로 가려집니다.
+///
 /// # Arguments
 /// * `prompt` - 사용자 프롬프트
 ///
@@ -44,7 +67,7 @@ pub fn get_context_with_history(prompt: &str) -> String {
 
     // 관련 히스토리가 있으면 컨텍스트에 추가
     if !relevant_history.is_empty() {
-        let history_context = store.format_as_context(&relevant_history);
+        let history_context = redact(&store.format_as_context(&relevant_history));
         format!("{}{}", base_context, history_context)
     } else {
         base_context
@@ -68,7 +91,7 @@ pub fn get_context_with_project(prompt: &str) -> String {
         // Unknown이 아니면 프로젝트 정보 추가
         if project_info.primary_type() != &ProjectType::Unknown {
             context.push('\n');
-            context.push_str(&project_info.to_context_string());
+            context.push_str(&redact(&project_info.to_context_string()));
         }
     }
 
@@ -79,7 +102,7 @@ pub fn get_context_with_project(prompt: &str) -> String {
         .unwrap_or_else(|_| Vec::new());
 
     if !relevant_history.is_empty() {
-        let history_context = store.format_as_context(&relevant_history);
+        let history_context = redact(&store.format_as_context(&relevant_history));
         context.push_str(&history_context);
     }
 
@@ -5,12 +5,18 @@ pub enum AskAiError {
     #[error("Failed to execute AI CLI: {0}")]
     AiCliError(String),
 
+    #[error("AI API request failed: {0}")]
+    AiApiError(String),
+
     #[error("Dangerous command detected: {0}")]
     DangerousCommand(String),
 
     #[error("Command execution failed: {0}")]
     ExecutionError(String),
 
+    #[error("Sandbox unavailable: {0}")]
+    SandboxUnavailable(String),
+
     #[error("User cancelled")]
     UserCancelled,
 
@@ -37,7 +43,6 @@ pub enum AskAiError {
     ConfigError(String),
 
     #[error("Parallel execution error: {0}")]
-    #[allow(dead_code)]  // Reserved for parallel execution errors
     ParallelExecutionError(String),
 }
 
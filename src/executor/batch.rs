@@ -3,6 +3,7 @@ use crate::executor::planner::{ExecutionPlan, Task};
 use crate::executor::runner::CommandRunner;
 use crate::ui::BatchProgressDisplay;
 use colored::*;
+use std::collections::HashSet;
 use std::time::Instant;
 
 /// 작업 실행 결과
@@ -44,6 +45,11 @@ impl TaskResult {
             duration_ms,
         }
     }
+
+    /// 의존하는 작업이 실패해서 실행하지 않은 작업 (실패로 셈)
+    pub fn skipped(task: &Task, failed_dependency: usize) -> Self {
+        Self::failure(task, format!("Skipped: task {} failed", failed_dependency), 0)
+    }
 }
 
 /// 배치 실행 결과
@@ -115,20 +121,39 @@ impl BatchExecutor {
         let progress = BatchProgressDisplay::new(total, "Batch execution");
 
         let mut all_results = Vec::new();
+        // 실패했거나 건너뛴 작업 (이 작업들에 의존하는 작업은 건너뜀)
+        let mut failed: HashSet<usize> = HashSet::new();
+
+        // 의존성 순서대로 그룹을 실행 (병렬 실행이 꺼져 있으면 작업 순서 그대로 한 그룹)
+        for group in plan.get_parallel_groups() {
+            if plan.can_parallelize && self.max_parallel > 1 {
+                // 병렬 실행
+                let mut runnable = Vec::new();
+                for task in group {
+                    match Self::failed_dependency(plan, task, &failed) {
+                        Some(dep) => {
+                            failed.insert(task.id);
+                            all_results.push(Self::skip_task_with_progress(task, dep, &progress));
+                        }
+                        None => runnable.push(task),
+                    }
+                }
 
-        if plan.can_parallelize && self.max_parallel > 1 {
-            // 병렬 실행
-            let groups = plan.get_parallel_groups();
-
-            for group in groups.iter() {
-                let group_results = self.execute_parallel_with_progress(group, &progress).await;
+                let group_results = self.execute_parallel_with_progress(&runnable, &progress).await;
+                failed.extend(group_results.iter().filter(|r| !r.success).map(|r| r.task_id));
                 all_results.extend(group_results);
-            }
-        } else {
-            // 순차 실행
-            for task in &plan.tasks {
-                let result = self.execute_task_with_progress(task, &progress).await;
-                all_results.push(result);
+            } else {
+                // 순차 실행
+                for task in group {
+                    let result = match Self::failed_dependency(plan, task, &failed) {
+                        Some(dep) => Self::skip_task_with_progress(task, dep, &progress),
+                        None => self.execute_task_with_progress(task, &progress).await,
+                    };
+                    if !result.success {
+                        failed.insert(task.id);
+                    }
+                    all_results.push(result);
+                }
             }
         }
 
@@ -149,6 +174,19 @@ impl BatchExecutor {
         }
     }
 
+    /// 작업이 의존하는 작업 중 실패한 것
+    fn failed_dependency(plan: &ExecutionPlan, task: &Task, failed: &HashSet<usize>) -> Option<usize> {
+        plan.dependencies_of(task.id).iter().copied().find(|dep| failed.contains(dep))
+    }
+
+    /// 실행하지 않고 건너뛴 작업 (진행률 표시 포함)
+    fn skip_task_with_progress(task: &Task, failed_dependency: usize, progress: &BatchProgressDisplay) -> TaskResult {
+        let result = TaskResult::skipped(task, failed_dependency);
+        let spinner = progress.add_task(&task.description);
+        progress.fail_task(&spinner, result.error.as_deref().unwrap_or_default());
+        result
+    }
+
     /// 단일 작업 실행 (진행률 표시 포함)
     async fn execute_task_with_progress(&self, task: &Task, progress: &BatchProgressDisplay) -> TaskResult {
         let start_time = Instant::now();
@@ -157,14 +195,7 @@ impl BatchExecutor {
         let spinner = progress.add_task(&task.description);
         spinner.set_message("Executing...".to_string());
 
-        // working_dir이 있으면 cd를 포함한 명령어 생성
-        let command = if let Some(dir) = &task.working_dir {
-            format!("cd {} && {}", dir, task.command)
-        } else {
-            task.command.clone()
-        };
-
-        match self.runner.execute(&command).await {
+        match self.runner.execute(&task_command(task)).await {
             Ok(output) => {
                 let duration = start_time.elapsed().as_millis();
                 progress.finish_task(&spinner, duration);
@@ -202,14 +233,7 @@ impl BatchExecutor {
 
                     spinner.set_message("Executing...".to_string());
 
-                    // working_dir이 있으면 cd를 포함한 명령어 생성
-                    let command = if let Some(dir) = &task.working_dir {
-                        format!("cd {} && {}", dir, task.command)
-                    } else {
-                        task.command.clone()
-                    };
-
-                    let result = match runner.execute(&command).await {
+                    let result = match runner.execute(&task_command(&task)).await {
                         Ok(output) => {
                             let duration = start_time.elapsed().as_millis();
                             spinner.finish_with_message(format!("{} ({}ms)", "Complete".green(), duration));
@@ -237,6 +261,14 @@ impl BatchExecutor {
     }
 }
 
+/// working_dir이 있으면 cd를 포함한 명령어 생성 (디렉토리는 작은따옴표로 감쌈)
+fn task_command(task: &Task) -> String {
+    match &task.working_dir {
+        Some(dir) => format!("cd '{}' && {}", dir.replace('\'', r"'\''"), task.command),
+        None => task.command.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -326,4 +358,38 @@ mod tests {
 
         assert_eq!(result.total, 2);
     }
+
+    #[test]
+    fn test_task_command_quotes_dir() {
+        let task = Task::new(0, "ls".to_string()).with_dir("/tmp/my dir; rm -rf x".to_string());
+        assert_eq!(task_command(&task), "cd '/tmp/my dir; rm -rf x' && ls");
+
+        let task = Task::new(0, "ls".to_string()).with_dir("it's".to_string());
+        assert_eq!(task_command(&task), r"cd 'it'\''s' && ls");
+    }
+
+    #[tokio::test]
+    async fn test_batch_executor_skips_dependents_of_failed_tasks() {
+        let tasks = vec![
+            Task::new(1, "exit 1".to_string()),
+            Task::new(2, "true".to_string()),
+            Task::new(3, "true".to_string()),
+            Task::new(4, "true".to_string()),
+        ];
+        let mut plan = ExecutionPlan::new(tasks);
+        plan.add_dependency(3, 1);
+        plan.add_dependency(3, 2);
+        plan.add_dependency(4, 3);
+
+        for max_parallel in [1, 2] {
+            let result = BatchExecutor::new(max_parallel).execute(&plan).await;
+            assert_eq!(result.total, 4);
+            assert_eq!(result.success_count, 1);
+
+            let error = |id: usize| result.task_results.iter().find(|r| r.task_id == id).unwrap().error.clone();
+            assert_eq!(error(2), None);
+            assert_eq!(error(3).as_deref(), Some("Skipped: task 1 failed"));
+            assert_eq!(error(4).as_deref(), Some("Skipped: task 3 failed"));
+        }
+    }
 }
@@ -0,0 +1,599 @@
+//! 실행 전 파일 시스템 영향 미리보기
+//!
+//! `rm`, `mv`, `cp`, `chmod`, `chown`, `find -delete`가 실제로 건드릴 파일을 찾아
+//! 개수와 크기를 계산합니다. glob은 셸처럼 cwd 기준으로 확장하고(숨김 파일 제외),
+//! 변수나 명령어 치환이 들어간 인자는 실행 전에는 알 수 없으므로 따로 모아 둡니다.
+
+use crate::executor::policy::resolve_path;
+use crate::executor::shell::{self, Command, Word};
+use crate::executor::validator::{program_name, strip_wrappers};
+use glob::{MatchOptions, Pattern};
+use std::path::{Path, PathBuf};
+use walkdir::{DirEntry, WalkDir};
+
+/// 미리보기에서 살펴볼 최대 항목 수 (넘으면 "at least"로 표시)
+const MAX_ENTRIES: usize = 100_000;
+
+/// 인자마다 보여줄 경로 예시 수
+const MAX_SAMPLES: usize = 3;
+
+/// 이 개수 이상의 파일을 건드리면 경고
+pub const LARGE_IMPACT: usize = 1_000;
+
+/// 명령어가 대상 파일에 하는 일
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Delete,
+    Move,
+    Copy,
+    ChangeMode,
+    ChangeOwner,
+}
+
+impl Action {
+    pub fn verb(self) -> &'static str {
+        match self {
+            Action::Delete => "deletes",
+            Action::Move => "moves",
+            Action::Copy => "copies",
+            Action::ChangeMode => "changes permissions of",
+            Action::ChangeOwner => "changes ownership of",
+        }
+    }
+}
+
+/// 인자 하나(`find`는 시작 경로 하나)가 가리키는 파일들
+#[derive(Debug, Clone, PartialEq)]
+pub struct Impact {
+    pub action: Action,
+    /// 원래 인자 (`*.log`, `build`) 또는 `find` 명령어 전체
+    pub argument: String,
+    /// glob 확장 결과 등 인자가 직접 가리키는 경로 수
+    pub matches: usize,
+    pub files: usize,
+    pub dirs: usize,
+    pub bytes: u64,
+    /// 일치한 경로 예시 (cwd 안이면 상대 경로)
+    pub samples: Vec<String>,
+    /// 항목이 너무 많아 세다가 멈췄는지
+    pub truncated: bool,
+    /// 평가하지 않은 `find` 조건이 있어 실제보다 많게 셌을 수 있는지
+    pub approximate: bool,
+}
+
+impl Impact {
+    fn new(action: Action, argument: &str) -> Self {
+        Self {
+            action,
+            argument: argument.to_string(),
+            matches: 0,
+            files: 0,
+            dirs: 0,
+            bytes: 0,
+            samples: Vec::new(),
+            truncated: false,
+            approximate: false,
+        }
+    }
+
+    /// 아무 경로와도 일치하지 않았는지 (셸은 glob을 그대로 넘기므로 보통 명령어가 실패함)
+    pub fn is_missing(&self) -> bool {
+        self.matches == 0
+    }
+
+    /// "40,000 files, 2 directories (1.2 GB)" 형식의 요약
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.files > 0 || self.dirs == 0 {
+            parts.push(format!("{} {}", format_count(self.files), plural(self.files, "file", "files")));
+        }
+        if self.dirs > 0 {
+            parts.push(format!("{} {}", format_count(self.dirs), plural(self.dirs, "directory", "directories")));
+        }
+
+        let mut summary = parts.join(", ");
+        if self.bytes > 0 {
+            summary.push_str(&format!(" ({})", format_size(self.bytes)));
+        }
+
+        if self.truncated {
+            format!("at least {}", summary)
+        } else if self.approximate {
+            format!("up to {}", summary)
+        } else {
+            summary
+        }
+    }
+
+    /// 항목 하나를 센다 (한도에 도달하면 false)
+    fn count(&mut self, is_dir: bool, len: u64, budget: &mut usize) -> bool {
+        if *budget == 0 {
+            self.truncated = true;
+            return false;
+        }
+        *budget -= 1;
+
+        if is_dir {
+            self.dirs += 1;
+        } else {
+            self.files += 1;
+            self.bytes += len;
+        }
+        true
+    }
+
+    fn add_sample(&mut self, path: &Path, cwd: &Path) {
+        if self.samples.len() < MAX_SAMPLES {
+            let display = path.strip_prefix(cwd).unwrap_or(path);
+            self.samples.push(display.display().to_string());
+        }
+    }
+}
+
+/// 명령어 전체의 파일 시스템 영향
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ImpactPreview {
+    pub impacts: Vec<Impact>,
+    /// 실행 전에는 알 수 없는 인자 (`$dir/*`, `$(ls)`)
+    pub unresolved: Vec<String>,
+}
+
+impl ImpactPreview {
+    /// 명령어를 파싱하여 파일을 건드리는 모든 단순 명령어의 대상을 계산
+    ///
+    /// 파싱할 수 없는 명령어는 빈 미리보기를 반환합니다.
+    pub fn analyze(command: &str, cwd: &Path) -> Self {
+        Self::analyze_with_limit(command, cwd, MAX_ENTRIES)
+    }
+
+    fn analyze_with_limit(command: &str, cwd: &Path, limit: usize) -> Self {
+        let mut preview = Self::default();
+        let Ok(list) = shell::parse(command) else {
+            return preview;
+        };
+
+        let mut budget = limit;
+        list.visit(&mut |node| {
+            if let Command::Simple(simple) = node {
+                preview.add_command(command, &simple.words, cwd, &mut budget);
+            }
+        });
+        preview
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.impacts.is_empty() && self.unresolved.is_empty()
+    }
+
+    /// 건드리는 파일 수 합계
+    pub fn total_files(&self) -> usize {
+        self.impacts.iter().map(|impact| impact.files).sum()
+    }
+
+    /// 확인 전에 경고할 만큼 많은 파일을 건드리는지
+    pub fn is_large(&self) -> bool {
+        self.total_files() >= LARGE_IMPACT || self.impacts.iter().any(|impact| impact.truncated)
+    }
+
+    fn add_command(&mut self, source: &str, words: &[Word], cwd: &Path, budget: &mut usize) {
+        let Some((words, _)) = strip_wrappers(words) else {
+            return;
+        };
+
+        let program = program_name(&words[0].text);
+        let args = &words[1..];
+        let (options, operands) = split_options(args);
+        let has = |flags: &[&str]| options.iter().any(|option| flags.iter().any(|flag| has_flag(option, flag)));
+
+        match program {
+            "rm" => {
+                let recursive = has(&["-r", "-R", "--recursive"]);
+                self.add_operands(Action::Delete, &operands, recursive, cwd, budget);
+            }
+            // 마지막 인자는 목적지
+            "mv" if operands.len() >= 2 => {
+                self.add_operands(Action::Move, &operands[..operands.len() - 1], true, cwd, budget);
+            }
+            "cp" if operands.len() >= 2 => {
+                let recursive = has(&["-r", "-R", "-a", "--recursive", "--archive"]);
+                self.add_operands(Action::Copy, &operands[..operands.len() - 1], recursive, cwd, budget);
+            }
+            // 첫 인자는 모드/소유자
+            "chmod" | "chown" | "chgrp" if operands.len() >= 2 => {
+                let action = if program == "chmod" { Action::ChangeMode } else { Action::ChangeOwner };
+                let recursive = has(&["-R", "--recursive"]);
+                self.add_operands(action, &operands[1..], recursive, cwd, budget);
+            }
+            "find" if args.iter().any(|word| word.text == "-delete") => {
+                let span = words[0].span.start..words[words.len() - 1].span.end;
+                let text = source.get(span).unwrap_or(program);
+                self.add_find(text, args, cwd, budget);
+            }
+            _ => {}
+        }
+    }
+
+    fn add_operands(&mut self, action: Action, operands: &[&Word], recursive: bool, cwd: &Path, budget: &mut usize) {
+        for word in operands {
+            let Some(paths) = expand(word, cwd) else {
+                self.unresolved.push(word.text.clone());
+                continue;
+            };
+
+            let mut impact = Impact::new(action, &word.text);
+            impact.matches = paths.len();
+            for path in &paths {
+                impact.add_sample(path, cwd);
+                if !measure(path, recursive, &mut impact, budget) {
+                    break;
+                }
+            }
+            self.impacts.push(impact);
+        }
+    }
+
+    /// `find <경로> <조건> -delete`: 이름/종류/깊이 조건만 평가하고 나머지는 근사치로 표시
+    fn add_find(&mut self, text: &str, args: &[Word], cwd: &Path, budget: &mut usize) {
+        // 심볼릭 링크 옵션 뒤부터 첫 조건 전까지가 시작 경로
+        let lead = args.iter().take_while(|w| matches!(w.text.as_str(), "-H" | "-L" | "-P")).count();
+        let start = args[lead..]
+            .iter()
+            .position(|w| w.text.starts_with('-') || w.text == "(" || w.text == "!")
+            .map_or(args.len(), |index| lead + index);
+
+        let filter = FindFilter::parse(&args[start..]);
+        let roots: Vec<&Word> = args[lead..start].iter().collect();
+        let default_root = Word {
+            text: ".".to_string(),
+            span: 0..0,
+            quoted: false,
+            substitutions: Vec::new(),
+        };
+        let roots = if roots.is_empty() { vec![&default_root] } else { roots };
+
+        for root in roots {
+            let Some(paths) = expand(root, cwd) else {
+                self.unresolved.push(root.text.clone());
+                continue;
+            };
+
+            let mut impact = Impact::new(Action::Delete, text);
+            impact.approximate = filter.approximate;
+            // find는 `.` 자체는 지우지 않음
+            let min_depth = if root.text == "." { filter.min_depth.max(1) } else { filter.min_depth };
+
+            'roots: for path in &paths {
+                let walker = WalkDir::new(path)
+                    .follow_links(false)
+                    .min_depth(min_depth)
+                    .max_depth(filter.max_depth);
+
+                for entry in walker.into_iter().filter_map(|e| e.ok()) {
+                    if !filter.matches(&entry) {
+                        continue;
+                    }
+                    let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                    if !impact.count(entry.file_type().is_dir(), len, budget) {
+                        break 'roots;
+                    }
+                    impact.matches += 1;
+                    impact.add_sample(entry.path(), cwd);
+                }
+            }
+            self.impacts.push(impact);
+        }
+    }
+}
+
+/// `find`에서 평가할 수 있는 조건
+struct FindFilter {
+    names: Vec<(Pattern, bool)>,
+    file_type: Option<char>,
+    min_depth: usize,
+    max_depth: usize,
+    /// 평가하지 않은 조건이 있는지
+    approximate: bool,
+}
+
+impl FindFilter {
+    fn parse(expression: &[Word]) -> Self {
+        let mut filter = Self {
+            names: Vec::new(),
+            file_type: None,
+            min_depth: 0,
+            max_depth: usize::MAX,
+            approximate: false,
+        };
+
+        let mut index = 0;
+        while index < expression.len() {
+            let value = expression.get(index + 1).map(|w| w.text.as_str());
+            let consumed = match (expression[index].text.as_str(), value) {
+                ("-name" | "-iname", Some(value)) => {
+                    match Pattern::new(value) {
+                        Ok(pattern) => filter.names.push((pattern, expression[index].text == "-iname")),
+                        Err(_) => filter.approximate = true,
+                    }
+                    2
+                }
+                ("-type", Some(value)) => {
+                    match value {
+                        "f" | "d" | "l" => filter.file_type = value.chars().next(),
+                        _ => filter.approximate = true,
+                    }
+                    2
+                }
+                ("-maxdepth", Some(value)) => {
+                    filter.max_depth = value.parse().unwrap_or(usize::MAX);
+                    2
+                }
+                ("-mindepth", Some(value)) => {
+                    filter.min_depth = value.parse().unwrap_or(0);
+                    2
+                }
+                ("-delete" | "-print" | "-depth", _) => 1,
+                _ => {
+                    filter.approximate = true;
+                    1
+                }
+            };
+            index += consumed;
+        }
+
+        filter
+    }
+
+    fn matches(&self, entry: &DirEntry) -> bool {
+        let name = entry.file_name().to_string_lossy();
+        let names_match = self.names.iter().all(|(pattern, ignore_case)| {
+            let options = MatchOptions {
+                case_sensitive: !ignore_case,
+                ..MatchOptions::new()
+            };
+            pattern.matches_with(&name, options)
+        });
+
+        let file_type = entry.file_type();
+        let type_matches = match self.file_type {
+            Some('f') => file_type.is_file(),
+            Some('d') => file_type.is_dir(),
+            Some('l') => file_type.is_symlink(),
+            _ => true,
+        };
+
+        names_match && type_matches
+    }
+}
+
+/// 인자를 옵션과 피연산자로 분리 (`--` 뒤는 모두 피연산자)
+fn split_options(args: &[Word]) -> (Vec<&str>, Vec<&Word>) {
+    let mut options = Vec::new();
+    let mut operands = Vec::new();
+    let mut options_ended = false;
+
+    for arg in args {
+        if options_ended || !arg.text.starts_with('-') || arg.text.len() == 1 {
+            operands.push(arg);
+        } else if arg.text == "--" {
+            options_ended = true;
+        } else {
+            options.push(arg.text.as_str());
+        }
+    }
+    (options, operands)
+}
+
+/// `-rf`처럼 묶인 짧은 옵션도 고려하여 플래그가 있는지 확인
+fn has_flag(option: &str, flag: &str) -> bool {
+    if option.starts_with("--") || flag.starts_with("--") {
+        return option == flag;
+    }
+    let short = &flag[1..];
+    option[1..].contains(short)
+}
+
+/// 인자를 셸처럼 경로로 확장 (알 수 없는 확장이 있으면 `None`)
+fn expand(word: &Word, cwd: &Path) -> Option<Vec<PathBuf>> {
+    let path = resolve_path(cwd, &word.text);
+    let text = path.to_string_lossy();
+    if !word.substitutions.is_empty() || text.contains('$') || text.contains('`') {
+        return None;
+    }
+
+    if word.quoted || !text.contains(['*', '?', '[']) {
+        return Some(path.symlink_metadata().map(|_| vec![path.clone()]).unwrap_or_default());
+    }
+
+    // cwd 자체에 glob 문자가 있어도 그대로 일치하도록 이스케이프
+    let pattern = match path.strip_prefix(cwd) {
+        Ok(rest) => Path::new(&Pattern::escape(&cwd.to_string_lossy())).join(rest),
+        Err(_) => path.clone(),
+    };
+    let options = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: true,
+    };
+
+    let paths = glob::glob_with(&pattern.to_string_lossy(), options)
+        .map(|paths| paths.filter_map(|p| p.ok()).collect())
+        .unwrap_or_default();
+    Some(paths)
+}
+
+/// 경로 하나의 영향 계산 (재귀면 하위 항목 전체, 한도에 도달하면 false)
+fn measure(path: &Path, recursive: bool, impact: &mut Impact, budget: &mut usize) -> bool {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return true;
+    };
+
+    if !recursive || !metadata.is_dir() {
+        return impact.count(metadata.is_dir(), metadata.len(), budget);
+    }
+
+    for entry in WalkDir::new(path).follow_links(false).into_iter().filter_map(|e| e.ok()) {
+        let len = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        if !impact.count(entry.file_type().is_dir(), len, budget) {
+            return false;
+        }
+    }
+    true
+}
+
+fn plural<'a>(count: usize, one: &'a str, many: &'a str) -> &'a str {
+    if count == 1 { one } else { many }
+}
+
+/// 천 단위 구분 기호를 넣은 개수 (`40000` -> `40,000`)
+pub fn format_count(count: usize) -> String {
+    let digits = count.to_string();
+    let mut formatted = String::new();
+    for (index, c) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(c);
+    }
+    formatted
+}
+
+/// 사람이 읽기 쉬운 크기 (`1536` -> `1.5 KB`)
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// a.log(10B), b.log(20B), .hidden.log, keep.txt, sub/c.log, sub/d.txt
+    fn fixture(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("askai-impact-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.log"), [0u8; 10]).unwrap();
+        fs::write(root.join("b.log"), [0u8; 20]).unwrap();
+        fs::write(root.join(".hidden.log"), "x").unwrap();
+        fs::write(root.join("keep.txt"), "x").unwrap();
+        fs::write(root.join("sub/c.log"), "x").unwrap();
+        fs::write(root.join("sub/d.txt"), "x").unwrap();
+        root
+    }
+
+    #[test]
+    fn test_glob_expands_like_the_shell() {
+        let root = fixture("glob");
+        let preview = ImpactPreview::analyze("rm *.log", &root);
+
+        assert_eq!(preview.impacts.len(), 1);
+        let impact = &preview.impacts[0];
+        assert_eq!(impact.action, Action::Delete);
+        assert_eq!(impact.matches, 2);  // 숨김 파일과 하위 디렉토리는 제외
+        assert_eq!(impact.bytes, 30);
+        assert_eq!(impact.samples, vec!["a.log", "b.log"]);
+
+        // 따옴표 안의 glob은 확장하지 않음
+        assert!(ImpactPreview::analyze("rm '*.log'", &root).impacts[0].is_missing());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_recursive_targets() {
+        let root = fixture("recursive");
+
+        let impact = &ImpactPreview::analyze("sudo rm -rf sub", &root).impacts[0];
+        assert_eq!((impact.files, impact.dirs), (2, 1));
+
+        // -r 없이는 디렉토리 자체만
+        let impact = &ImpactPreview::analyze("rm sub", &root).impacts[0];
+        assert_eq!((impact.files, impact.dirs), (0, 1));
+
+        let impact = &ImpactPreview::analyze("chmod -R 755 sub", &root).impacts[0];
+        assert_eq!(impact.action, Action::ChangeMode);
+        assert_eq!((impact.files, impact.dirs), (2, 1));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_move_and_copy_skip_destination() {
+        let root = fixture("move");
+
+        let preview = ImpactPreview::analyze("mv a.log keep.txt sub", &root);
+        let arguments: Vec<&str> = preview.impacts.iter().map(|i| i.argument.as_str()).collect();
+        assert_eq!(arguments, vec!["a.log", "keep.txt"]);
+
+        let preview = ImpactPreview::analyze("cp sub backup", &root);
+        assert_eq!((preview.impacts[0].files, preview.impacts[0].dirs), (0, 1));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_find_delete() {
+        let root = fixture("find");
+
+        let impact = &ImpactPreview::analyze("find . -name '*.log' -delete", &root).impacts[0];
+        assert_eq!(impact.files, 4);  // find는 숨김 파일도 포함
+        assert!(!impact.approximate);
+
+        let impact = &ImpactPreview::analyze("find . -maxdepth 1 -type f -name '*.log' -delete", &root).impacts[0];
+        assert_eq!(impact.files, 3);
+
+        let impact = &ImpactPreview::analyze("find . -mtime +7 -delete", &root).impacts[0];
+        assert!(impact.approximate);
+        assert!(impact.summary().starts_with("up to"));
+
+        assert!(ImpactPreview::analyze("find . -name '*.log'", &root).is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_unresolved_and_missing() {
+        let root = fixture("unresolved");
+
+        let preview = ImpactPreview::analyze("rm -rf \"$dir\"/* $(ls) *.tmp", &root);
+        assert_eq!(preview.unresolved, vec!["$dir/*", "$(ls)"]);
+        assert_eq!(preview.impacts.len(), 1);
+        assert!(preview.impacts[0].is_missing());
+
+        assert!(ImpactPreview::analyze("ls -la", &root).is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_entry_limit() {
+        let root = fixture("limit");
+
+        let preview = ImpactPreview::analyze_with_limit("rm -r sub *.log", &root, 2);
+        assert!(preview.impacts[0].truncated);
+        assert!(preview.is_large());
+        assert!(preview.impacts[0].summary().starts_with("at least"));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_summary_format() {
+        assert_eq!(format_count(40000), "40,000");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+
+        let mut impact = Impact::new(Action::Delete, "*.log");
+        impact.files = 40000;
+        impact.dirs = 1;
+        impact.bytes = 1536;
+        assert_eq!(impact.summary(), "40,000 files, 1 directory (1.5 KB)");
+    }
+}
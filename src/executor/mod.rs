@@ -3,6 +3,12 @@ pub mod validator;
 pub mod planner;
 pub mod batch;
 pub mod rollback;
+pub mod script;
+pub mod policy;
+pub mod shell;
+pub mod impact;
+pub mod sandbox;
+pub mod network;
 
 // Re-exports for convenience (used in main.rs and ui module)
-pub use validator::{CommandValidator, DangerLevel};
+pub use validator::{CommandValidator, DangerLevel, ValidationReport};
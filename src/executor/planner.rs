@@ -1,3 +1,4 @@
+use crate::error::{AskAiError, Result};
 use std::collections::{HashMap, HashSet};
 
 /// 실행 작업 단위
@@ -68,6 +69,46 @@ impl ExecutionPlan {
             .push(depends_on);
     }
 
+    /// 작업이 의존하는 작업 ID 목록 (없으면 빈 목록)
+    pub fn dependencies_of(&self, task_id: usize) -> &[usize] {
+        self.dependencies.get(&task_id).map_or(&[], |deps| deps.as_slice())
+    }
+
+    /// 없는 작업에 대한 의존성이나 순환 의존성이 있으면 에러
+    ///
+    /// `get_parallel_groups`는 순환에 걸린 작업을 조용히 빼므로 실행 전에 확인해야 합니다.
+    pub fn check_dependencies(&self) -> Result<()> {
+        let ids: HashSet<usize> = self.tasks.iter().map(|task| task.id).collect();
+
+        for task in &self.tasks {
+            if let Some(dep) = self.dependencies_of(task.id).iter().find(|dep| !ids.contains(dep)) {
+                return Err(AskAiError::ParallelExecutionError(format!(
+                    "task {} depends on unknown task {}",
+                    task.id, dep
+                )));
+            }
+        }
+
+        let scheduled: HashSet<usize> = self.get_parallel_groups()
+            .iter()
+            .flatten()
+            .map(|task| task.id)
+            .collect();
+        let stuck: Vec<String> = self.tasks
+            .iter()
+            .filter(|task| !scheduled.contains(&task.id))
+            .map(|task| task.id.to_string())
+            .collect();
+
+        if !stuck.is_empty() {
+            return Err(AskAiError::ParallelExecutionError(format!(
+                "dependency cycle among tasks {}",
+                stuck.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
     /// 병렬 실행 불가 설정
     pub fn disable_parallelization(&mut self) {
         self.can_parallelize = false;
@@ -244,6 +285,32 @@ mod tests {
         assert_eq!(groups[2].len(), 1); // task2
     }
 
+    #[test]
+    fn test_check_dependencies() {
+        let tasks = vec![
+            Task::new(1, "npm run build".to_string()),
+            Task::new(2, "cargo build".to_string()),
+            Task::new(3, "make integration-test".to_string()),
+        ];
+
+        let mut plan = ExecutionPlan::new(tasks);
+        plan.add_dependency(3, 1);
+        plan.add_dependency(3, 2);
+        assert!(plan.check_dependencies().is_ok());
+        assert_eq!(plan.dependencies_of(3), &[1, 2]);
+        assert!(plan.dependencies_of(1).is_empty());
+
+        // 순환 의존성 (1 -> 3 -> 1)
+        plan.add_dependency(1, 3);
+        let error = plan.check_dependencies().unwrap_err().to_string();
+        assert!(error.contains("cycle among tasks 1, 3"), "{}", error);
+
+        // 없는 작업에 대한 의존성
+        let mut plan = ExecutionPlan::new(vec![Task::new(0, "ls".to_string())]);
+        plan.add_dependency(0, 9);
+        assert!(plan.check_dependencies().unwrap_err().to_string().contains("unknown task 9"));
+    }
+
     #[test]
     fn test_create_single() {
         let plan = ExecutionPlanner::create_single("ls -la".to_string());
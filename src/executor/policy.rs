@@ -0,0 +1,520 @@
+//! 검증기 안전 정책
+//!
+//! 규칙은 다음 정책에서 모아집니다:
+//! 1. 프로젝트 정책: 현재 디렉토리나 상위 디렉토리의 `.askai/policy.toml`
+//! 2. 사용자 정책: `~/.askai/policy.toml`
+//! 3. 기본 정책: `default_policy.toml` (바이너리에 포함)
+//!
+//! 일치한 규칙 중 가장 위험한 규칙이 적용되므로, 정책 파일은 위험도를 올리거나
+//! 차단할 수만 있고 기본 규칙보다 낮출 수는 없습니다.
+//!
+//! 모든 패턴은 glob이며 `|`로 여러 패턴을 나열할 수 있습니다 (예: `main|master`).
+//!
+//! ```toml
+//! [[rules]]
+//! command = "kubectl"
+//! args = ["delete"]
+//! flags = ["--context=prod*"]
+//! level = "high"
+//! blocked = true
+//! reason = "deletes resources in a production cluster"
+//! ```
+
+use crate::error::{AskAiError, Result};
+use crate::executor::validator::{normalize_target, DangerLevel, RiskCategory};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// 바이너리에 포함된 기본 정책
+const DEFAULT_POLICY: &str = include_str!("default_policy.toml");
+
+/// 정책 파일 이름 (`.askai/` 아래)
+const POLICY_FILE: &str = "policy.toml";
+
+/// 단순 명령어에 적용하는 위험 규칙
+///
+/// 모든 패턴은 glob이며, 지정하지 않은 조건은 항상 일치합니다.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    /// 프로그램 이름 (예: `rm`, `mkfs*`)
+    pub command: String,
+    /// 모두 있어야 하는 위치 인자 (예: `["delete"]`)
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 모두 있어야 하는 정규화된 플래그, `이름=값`으로 값도 검사 (예: `-r`, `--context=prod*`)
+    #[serde(default)]
+    pub flags: Vec<String>,
+    /// 하나라도 일치해야 하는 경로 인자 (예: `/`, `/dev/sd*`)
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// `args` 외의 위치 인자가 하나라도 일치하면 규칙을 적용하지 않음 (`["*"]`는 "다른 인자 없음")
+    #[serde(default)]
+    pub unless_args: Vec<String>,
+    /// 현재 디렉토리 밖을 가리키는 인자가 있어야 일치 (`/tmp/x`, `../data`, `~/notes`)
+    #[serde(default)]
+    pub outside_cwd: bool,
+    pub level: DangerLevel,
+    /// 일치하면 실행 자체를 막는지
+    #[serde(default)]
+    pub blocked: bool,
+    /// 사람이 읽는 이유 (명령어 뒤에 붙음)
+    pub reason: String,
+    /// 더 안전한 대안 (확인 화면에 함께 표시)
+    #[serde(default)]
+    pub alternative: Option<String>,
+    /// 위험의 종류 (`remote-code`, `exfiltration`, `credentials` ...)
+    #[serde(default)]
+    pub category: RiskCategory,
+}
+
+/// 규칙 검사를 위해 정규화된 명령어 호출
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    /// 경로를 뗀 프로그램 이름
+    pub program: String,
+    /// (정규화된 플래그, 값) - 값은 `=` 뒤나 바로 다음 단어
+    pub flags: Vec<(String, Option<String>)>,
+    /// 위치 인자 (경로는 `/etc/` -> `/etc`로 정규화)
+    pub args: Vec<String>,
+    /// 현재 디렉토리 (`outside_cwd`의 기준)
+    pub cwd: PathBuf,
+    /// 상대 경로 해석 기준 (앞의 `cd`로 바뀐 디렉토리, 없으면 `cwd`)
+    pub dir: PathBuf,
+}
+
+impl Invocation {
+    /// `paths`와 비교할 인자 (`cd`로 디렉토리가 바뀌었으면 상대 경로는 바뀐 디렉토리 기준 절대 경로도)
+    fn path_args(&self) -> impl Iterator<Item = String> + '_ {
+        self.args.iter().flat_map(|arg| {
+            let resolved = (self.dir != self.cwd && !arg.starts_with(['/', '~', '$']))
+                .then(|| normalize_target(&normalize_path(&self.dir.join(arg)).to_string_lossy()));
+            resolved.into_iter().chain(std::iter::once(arg.clone()))
+        })
+    }
+}
+
+impl Rule {
+    pub fn matches(&self, invocation: &Invocation) -> bool {
+        if !glob_match(&self.command, &invocation.program) {
+            return false;
+        }
+
+        let has_args = self.args
+            .iter()
+            .all(|pattern| invocation.args.iter().any(|arg| glob_match(pattern, arg)));
+
+        let has_flags = self.flags.iter().all(|pattern| {
+            let (name, value) = match pattern.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (pattern.as_str(), None),
+            };
+            invocation.flags.iter().any(|(flag, flag_value)| {
+                glob_match(name, flag)
+                    && value.is_none_or(|value| {
+                        flag_value.as_deref().is_some_and(|v| glob_match(value, v))
+                    })
+            })
+        });
+
+        let has_path = self.paths.is_empty()
+            || self.paths
+                .iter()
+                .any(|pattern| invocation.path_args().any(|arg| glob_match(pattern, &arg)));
+
+        let excluded = invocation.args
+            .iter()
+            .filter(|arg| !self.args.iter().any(|pattern| glob_match(pattern, arg)))
+            .any(|arg| self.unless_args.iter().any(|pattern| glob_match(pattern, arg)));
+
+        let outside = !self.outside_cwd
+            || invocation.args.iter().any(|arg| is_outside(invocation, arg));
+
+        has_args && has_flags && has_path && !excluded && outside
+    }
+
+    /// 규칙이 겨냥한 대상 인자 (`paths` 또는 `outside_cwd`에 일치한 첫 인자)
+    ///
+    /// Critical 명령어를 확인할 때 사용자가 직접 입력해야 하는 값입니다.
+    pub fn target(&self, invocation: &Invocation) -> Option<String> {
+        invocation.path_args()
+            .find(|arg| {
+                (!self.paths.is_empty() && self.paths.iter().any(|pattern| glob_match(pattern, arg)))
+                    || (self.outside_cwd && is_outside(invocation, arg))
+            })
+            // `of=/dev/sdb` 같은 key=value 인자는 값만
+            .map(|arg| arg.split_once('=').map_or(arg.as_str(), |(_, value)| value).to_string())
+    }
+
+    /// 규칙을 한 줄로 요약 (예: `rm -r on /, /bin, /boot, ...`)
+    pub fn describe(&self) -> String {
+        let mut parts = vec![self.command.clone()];
+        parts.extend(self.args.iter().cloned());
+        parts.extend(self.flags.iter().cloned());
+
+        let mut description = parts.join(" ");
+        if !self.paths.is_empty() {
+            let shown: Vec<&str> = self.paths.iter().take(3).map(String::as_str).collect();
+            let more = if self.paths.len() > 3 { ", ..." } else { "" };
+            description.push_str(&format!(" on {}{}", shown.join(", "), more));
+        }
+        if self.outside_cwd {
+            description.push_str(" outside cwd");
+        }
+        if !self.unless_args.is_empty() {
+            description.push_str(&format!(" unless {}", self.unless_args.join(", ")));
+        }
+        description
+    }
+
+    /// 모든 패턴이 올바른 glob인지 확인
+    fn check_patterns(&self) -> std::result::Result<(), String> {
+        let flag_values = self.flags.iter().filter_map(|f| f.split_once('=').map(|(_, v)| v));
+        let patterns = std::iter::once(self.command.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .chain(flag_values)
+            .chain(self.paths.iter().map(String::as_str))
+            .chain(self.unless_args.iter().map(String::as_str));
+
+        for pattern in patterns.flat_map(|p| p.split('|')) {
+            glob::Pattern::new(pattern)
+                .map_err(|e| format!("invalid pattern `{}` in rule for `{}`: {}", pattern, self.command, e))?;
+        }
+        Ok(())
+    }
+}
+
+/// 프로젝트, 사용자, 기본 정책 순으로 합쳐진 규칙 목록
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+impl Policy {
+    /// 기본 정책만 사용
+    pub fn builtin() -> Self {
+        Self::parse(DEFAULT_POLICY).expect("default policy must be valid")
+    }
+
+    /// 프로젝트 정책, 사용자 정책, 기본 정책을 순서대로 합쳐서 로드
+    ///
+    /// 정책 파일이 잘못되었으면 안전을 위해 에러를 반환합니다.
+    pub fn load() -> Result<Self> {
+        let mut rules = Vec::new();
+
+        for path in Self::policy_paths() {
+            let content = fs::read_to_string(&path)?;
+            let policy = Self::parse(&content).map_err(|e| {
+                AskAiError::ConfigError(format!("Invalid policy file {}: {}", path.display(), e))
+            })?;
+            rules.extend(policy.rules);
+        }
+
+        rules.extend(Self::builtin().rules);
+        Ok(Self { rules })
+    }
+
+    /// TOML 정책 파싱 및 패턴 검증
+    pub fn parse(content: &str) -> std::result::Result<Self, String> {
+        let policy: Policy = toml::from_str(content).map_err(|e| e.to_string())?;
+        for rule in &policy.rules {
+            rule.check_patterns()?;
+        }
+        Ok(policy)
+    }
+
+    /// 존재하는 정책 파일 (우선순위 순)
+    pub fn policy_paths() -> Vec<PathBuf> {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        let user_path = PathBuf::from(home).join(".askai").join(POLICY_FILE);
+
+        let mut paths: Vec<PathBuf> = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| Self::find_project_policy(&cwd))
+            .filter(|path| *path != user_path)
+            .into_iter()
+            .collect();
+
+        if user_path.is_file() {
+            paths.push(user_path);
+        }
+
+        paths
+    }
+
+    /// `dir`이나 가장 가까운 상위 디렉토리의 `.askai/policy.toml`
+    fn find_project_policy(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|ancestor| ancestor.join(".askai").join(POLICY_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// 호출에 일치하는 규칙 중 가장 위험한 규칙
+    ///
+    /// 차단 규칙이 하나라도 있으면 차단 규칙이, 없으면 위험도가 가장 높은 규칙이 이깁니다
+    /// (같으면 앞쪽 규칙). 프로젝트 정책이 `level = "low"`로 모든 명령어에 일치해도
+    /// 기본 정책의 위험도나 차단은 그대로 남습니다.
+    pub fn evaluate(&self, invocation: &Invocation) -> Option<&Rule> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matches(invocation))
+            .rev()
+            .max_by_key(|rule| (rule.blocked, rule.level))
+    }
+}
+
+/// glob 일치 여부 (`a|b`는 둘 중 하나)
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    pattern.split('|').any(|alternative| {
+        glob::Pattern::new(alternative)
+            .map(|p| p.matches(text))
+            .unwrap_or(alternative == text)
+    })
+}
+
+/// 인자가 현재 디렉토리 밖을 가리키는지 (변수는 알 수 없으므로 상대 경로로 취급)
+fn is_outside(invocation: &Invocation, arg: &str) -> bool {
+    !normalize_path(&resolve_path(&invocation.dir, arg)).starts_with(normalize_path(&invocation.cwd))
+}
+
+/// 인자가 가리키는 경로 (`~`, `$HOME`은 홈 디렉토리, 상대 경로는 cwd 기준)
+pub(crate) fn resolve_path(cwd: &Path, arg: &str) -> PathBuf {
+    let home = std::env::var("HOME").map(PathBuf::from).ok();
+
+    let home_relative = ["~", "$HOME", "${HOME}"].iter().find_map(|prefix| {
+        let rest = arg.strip_prefix(prefix)?;
+        if rest.is_empty() || rest.starts_with('/') {
+            Some(rest.trim_start_matches('/'))
+        } else {
+            None
+        }
+    });
+
+    match (home_relative, &home) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ if arg.starts_with('/') => PathBuf::from(arg),
+        _ => cwd.join(arg),
+    }
+}
+
+/// `.`과 `..`를 정리한 경로 (파일 시스템에 접근하지 않음)
+pub(crate) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invocation(program: &str, flags: &[(&str, Option<&str>)], args: &[&str]) -> Invocation {
+        Invocation {
+            program: program.to_string(),
+            flags: flags
+                .iter()
+                .map(|(f, v)| (f.to_string(), v.map(str::to_string)))
+                .collect(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: PathBuf::from("/home/dev/project"),
+            dir: PathBuf::from("/home/dev/project"),
+        }
+    }
+
+    #[test]
+    fn test_builtin_policy_is_valid() {
+        let policy = Policy::builtin();
+        assert!(policy.rules.iter().any(|r| r.command == "rm" && r.blocked));
+    }
+
+    #[test]
+    fn test_flag_value_matcher() {
+        let policy = Policy::parse(
+            r#"
+            [[rules]]
+            command = "kubectl"
+            args = ["delete"]
+            flags = ["--context=prod*"]
+            level = "high"
+            blocked = true
+            reason = "deletes resources in a production cluster"
+            "#,
+        )
+        .unwrap();
+
+        let prod = invocation("kubectl", &[("--context", Some("prod-eu"))], &["prod-eu", "delete", "pod", "web"]);
+        assert!(policy.evaluate(&prod).unwrap().blocked);
+
+        let staging = invocation("kubectl", &[("--context", Some("staging"))], &["staging", "delete", "pod", "web"]);
+        assert!(policy.evaluate(&staging).is_none());
+
+        let get = invocation("kubectl", &[("--context", Some("prod"))], &["prod", "get", "pods"]);
+        assert!(policy.evaluate(&get).is_none());
+    }
+
+    #[test]
+    fn test_unless_args_matcher() {
+        let policy = Policy::parse(
+            r#"
+            [[rules]]
+            command = "terraform"
+            args = ["apply"]
+            unless_args = ["*"]
+            level = "high"
+            blocked = true
+            reason = "applies changes without a reviewed plan file"
+            "#,
+        )
+        .unwrap();
+
+        assert!(policy.evaluate(&invocation("terraform", &[("-auto-approve", None)], &["apply"])).is_some());
+        assert!(policy.evaluate(&invocation("terraform", &[], &["apply", "release.tfplan"])).is_none());
+        assert!(policy.evaluate(&invocation("terraform", &[], &["plan"])).is_none());
+    }
+
+    #[test]
+    fn test_project_rule_cannot_lower_builtin_rules() {
+        let mut policy = Policy::parse(
+            r#"
+            [[rules]]
+            command = "*"
+            level = "low"
+            reason = "is fine in this project"
+            "#,
+        )
+        .unwrap();
+        policy.rules.extend(Policy::builtin().rules);
+
+        let scratch = invocation("rm", &[("-r", None)], &["build"]);
+        assert_eq!(policy.evaluate(&scratch).unwrap().level, DangerLevel::High);
+
+        let root = invocation("rm", &[("-r", None)], &["/"]);
+        assert!(policy.evaluate(&root).unwrap().blocked);
+
+        let disk = invocation("dd", &[], &["if=/dev/zero", "of=/dev/sda"]);
+        let rule = policy.evaluate(&disk).unwrap();
+        assert!(rule.blocked);
+        assert_eq!(rule.level, DangerLevel::Critical);
+
+        let push = invocation("git", &[("-f", Some("origin"))], &["push", "origin", "main"]);
+        assert_eq!(policy.evaluate(&push).unwrap().level, DangerLevel::Critical);
+
+        // 기본 규칙이 없는 명령어에는 프로젝트 규칙이 그대로 적용
+        assert_eq!(policy.evaluate(&invocation("ls", &[], &[])).unwrap().reason, "is fine in this project");
+    }
+
+    #[test]
+    fn test_project_rule_can_raise_level() {
+        let mut policy = Policy::parse(
+            r#"
+            [[rules]]
+            command = "rm"
+            level = "critical"
+            reason = "deletes tracked fixtures"
+            "#,
+        )
+        .unwrap();
+        policy.rules.extend(Policy::builtin().rules);
+
+        let scratch = invocation("rm", &[("-r", None)], &["build"]);
+        assert_eq!(policy.evaluate(&scratch).unwrap().level, DangerLevel::Critical);
+    }
+
+    #[test]
+    fn test_outside_cwd_matcher() {
+        let policy = Policy::builtin();
+        let level = |args: &[&str]| {
+            policy
+                .evaluate(&invocation("rm", &[("-r", None)], args))
+                .map(|rule| (rule.level, rule.blocked))
+        };
+
+        assert_eq!(level(&["build"]), Some((DangerLevel::High, false)));
+        assert_eq!(level(&["./build/../dist"]), Some((DangerLevel::High, false)));
+        assert_eq!(level(&["/home/dev/project/target"]), Some((DangerLevel::High, false)));
+        assert_eq!(level(&["../data"]), Some((DangerLevel::Critical, false)));
+        assert_eq!(level(&["/tmp/cache"]), Some((DangerLevel::Critical, false)));
+        assert_eq!(level(&["/"]), Some((DangerLevel::High, true)));
+    }
+
+    #[test]
+    fn test_critical_target() {
+        let policy = Policy::builtin();
+
+        let push = invocation("git", &[("-f", Some("origin"))], &["push", "origin", "main"]);
+        let rule = policy.evaluate(&push).unwrap();
+        assert_eq!(rule.level, DangerLevel::Critical);
+        assert_eq!(rule.target(&push).as_deref(), Some("main"));
+
+        let feature = invocation("git", &[("-f", Some("origin"))], &["push", "origin", "feature"]);
+        assert_eq!(policy.evaluate(&feature).unwrap().level, DangerLevel::High);
+
+        let rm = invocation("rm", &[("-r", None)], &["notes", "../data"]);
+        assert_eq!(policy.evaluate(&rm).unwrap().target(&rm).as_deref(), Some("../data"));
+    }
+
+    #[test]
+    fn test_rule_category() {
+        let policy = Policy::parse(
+            r#"
+            [[rules]]
+            command = "rclone"
+            args = ["copy"]
+            level = "medium"
+            category = "exfiltration"
+            reason = "copies files to cloud storage"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(policy.rules[0].category, RiskCategory::Exfiltration);
+        assert_eq!(Policy::builtin().rules[0].category, RiskCategory::General);
+
+        let upload = invocation("curl", &[("-F", Some("file=@notes.txt"))], &["file=@notes.txt", "https://x.example"]);
+        assert_eq!(Policy::builtin().evaluate(&upload).unwrap().category, RiskCategory::Exfiltration);
+        let form = invocation("curl", &[("-F", Some("name=askai"))], &["name=askai", "https://x.example"]);
+        assert!(Policy::builtin().evaluate(&form).is_none());
+    }
+
+    #[test]
+    fn test_describe() {
+        let policy = Policy::builtin();
+        let descriptions: Vec<String> = policy.rules.iter().map(Rule::describe).collect();
+
+        assert!(descriptions.contains(&"rm -r".to_string()));
+        assert!(descriptions.contains(&"rm -r on /, /bin, /boot, ...".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_policy() {
+        assert!(Policy::parse("[[rules]]\ncommand = \"rm\"\nlevel = \"extreme\"\nreason = \"x\"").is_err());
+        assert!(Policy::parse("[[rules]]\ncommand = \"rm\"\nlevel = \"high\"\nreason = \"x\"\npath = [\"/\"]").is_err());
+        assert!(Policy::parse("[[rules]]\ncommand = \"[rm\"\nlevel = \"high\"\nreason = \"x\"").is_err());
+    }
+
+    #[test]
+    fn test_find_project_policy() {
+        let root = std::env::temp_dir().join(format!("askai-policy-test-{}", std::process::id()));
+        let nested = root.join("src").join("deep");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(root.join(".askai")).unwrap();
+        fs::write(root.join(".askai").join(POLICY_FILE), "").unwrap();
+
+        assert_eq!(
+            Policy::find_project_policy(&nested),
+            Some(root.join(".askai").join(POLICY_FILE))
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,14 +1,66 @@
 use crate::error::{AskAiError, Result};
+use crate::executor::sandbox::Sandbox;
+use crate::executor::shell::{self, Command as ShellCommand, List, Separator};
+use crate::ui::ConfirmPrompt;
 use colored::*;
+use std::io::Write;
+use std::process::{ExitStatus, Stdio};
+use tokio::io::{AsyncRead, AsyncReadExt};
 use tokio::process::Command;
 
+/// 복구 프롬프트에 보낼 stderr/stdout 최대 크기 (마지막 부분만 보관)
+const STDERR_TAIL_BYTES: usize = 4096;
+
+/// 현재 셸의 상태를 바꾸는 내장 명령어 (자식 프로세스에서 실행하면 효과가 사라짐)
+const PARENT_SHELL_BUILTINS: &[&str] = &[
+    "cd", "pushd", "popd", "export", "unset", "alias", "unalias", "source", ".", "set", "shopt",
+    "ulimit", "umask", "exec", "declare", "typeset", "readonly", "hash", "trap", "eval", "builtin",
+    "deactivate",
+];
+
+/// 셸 함수로 설치되어 현재 셸을 바꾸는 도구 (`conda activate`, `nvm use`)
+const PARENT_SHELL_FUNCTIONS: &[(&str, &str)] = &[
+    ("conda", "activate"),
+    ("conda", "deactivate"),
+    ("nvm", "use"),
+    ("pyenv", "shell"),
+];
+
+/// `CommandRunner::stream`으로 실행한 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamResult {
+    /// 종료 코드 (시그널로 끝나면 128 + 시그널 번호)
+    pub exit_code: i32,
+    /// stderr 마지막 부분 (터미널에는 이미 출력됨)
+    pub stderr: String,
+    /// stdout 마지막 부분 (`with_stdout_capture`일 때만, 아니면 빈 문자열)
+    pub stdout: String,
+}
+
+impl StreamResult {
+    /// 실패했고 provider에 고쳐 달라고 할 만한지
+    ///
+    /// Ctrl-C로 중단했거나, 에러 메시지 없이 1로 끝난 경우(`grep`이 못 찾음 등)는 제외합니다.
+    pub fn is_repairable(&self) -> bool {
+        match self.exit_code {
+            0 | 130 => false,
+            1 => !self.stderr.trim().is_empty(),
+            _ => true,
+        }
+    }
+}
+
 pub struct CommandRunner {
     dry_run: bool,
+    capture_stdout: bool,
 }
 
 impl CommandRunner {
     pub fn new() -> Self {
-        Self { dry_run: false }
+        Self {
+            dry_run: false,
+            capture_stdout: false,
+        }
     }
 
     pub fn with_dry_run(mut self, dry_run: bool) -> Self {
@@ -16,10 +68,32 @@ impl CommandRunner {
         self
     }
 
+    /// `stream`에서 stdout도 파이프로 받아 출력하면서 마지막 부분을 보관
+    ///
+    /// 자식 프로세스의 stdout이 터미널이 아니게 되므로 색상 출력 등이 달라질 수 있습니다.
+    pub fn with_stdout_capture(mut self, capture: bool) -> Self {
+        self.capture_stdout = capture;
+        self
+    }
+
     pub async fn execute(&self, command: &str) -> Result<String> {
         if self.dry_run {
             eprintln!("{} {}", "[DRY-RUN]".yellow().bold(), command);
-            return Ok(format!("[DRY-RUN] {}", command));
+
+            // 샌드박스에서 실행하고, 샌드박스를 만들 수 없으면 명령어만 출력
+            let cwd = std::env::current_dir()?;
+            return match Sandbox::new(&cwd).run(command).await {
+                Ok(simulation) => {
+                    ConfirmPrompt::new().show_simulation(&simulation);
+                    if simulation.success() {
+                        Ok(simulation.stdout)
+                    } else {
+                        Err(AskAiError::ExecutionError(simulation.stderr))
+                    }
+                }
+                Err(AskAiError::SandboxUnavailable(_)) => Ok(format!("[DRY-RUN] {}", command)),
+                Err(e) => Err(e),
+            };
         }
 
         println!("{} {}", "[>] Executing:".cyan(), command);
@@ -45,6 +119,112 @@ impl CommandRunner {
 
         Ok(stdout)
     }
+
+    /// 출력을 그대로 터미널로 흘려보내며 실행 (`--exec`)
+    ///
+    /// stderr는 터미널에 출력하면서 마지막 부분을 함께 모아 실패 시 복구 프롬프트에 사용합니다.
+    /// 실행 중 Ctrl-C는 명령어에만 전달되고 askai는 끝날 때까지 기다립니다.
+    pub async fn stream(&self, command: &str) -> Result<StreamResult> {
+        let mut child = Command::new("bash");
+        child.arg("-c").arg(command).stderr(Stdio::piped());
+        if self.capture_stdout {
+            child.stdout(Stdio::piped());
+        }
+        let mut child = child
+            .spawn()
+            .map_err(|e| AskAiError::ExecutionError(e.to_string()))?;
+
+        let stderr = child.stderr.take().expect("stderr is piped");
+        let stderr_tee = tokio::spawn(tee(stderr, std::io::stderr()));
+        let stdout_tee = child.stdout.take().map(|stdout| tokio::spawn(tee(stdout, std::io::stdout())));
+
+        let status = loop {
+            tokio::select! {
+                status = child.wait() => break status?,
+                _ = tokio::signal::ctrl_c() => continue,
+            }
+        };
+        let stderr_tail = stderr_tee.await.unwrap_or_default();
+        let stdout_tail = match stdout_tee {
+            Some(stdout_tee) => stdout_tee.await.unwrap_or_default(),
+            None => Vec::new(),
+        };
+
+        Ok(StreamResult {
+            exit_code: exit_code(status),
+            stderr: String::from_utf8_lossy(&stderr_tail).to_string(),
+            stdout: String::from_utf8_lossy(&stdout_tail).to_string(),
+        })
+    }
+}
+
+/// 자식 프로세스의 출력을 그대로 내보내면서 마지막 `STDERR_TAIL_BYTES`만 보관
+async fn tee(mut input: impl AsyncRead + Unpin, mut output: impl Write) -> Vec<u8> {
+    let mut tail = Vec::new();
+    let mut buffer = [0u8; 4096];
+
+    while let Ok(read) = input.read(&mut buffer).await {
+        if read == 0 {
+            break;
+        }
+        let _ = output.write_all(&buffer[..read]);
+        let _ = output.flush();
+
+        tail.extend_from_slice(&buffer[..read]);
+        if tail.len() > STDERR_TAIL_BYTES {
+            tail.drain(..tail.len() - STDERR_TAIL_BYTES);
+        }
+    }
+    tail
+}
+
+/// 셸과 같은 방식의 exit code (시그널로 끝나면 128 + 시그널 번호)
+fn exit_code(status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+    status.code().unwrap_or(1)
+}
+
+/// 명령어가 현재 셸의 디렉토리, 환경 변수, alias 등을 바꾸는지 확인
+///
+/// 바꾼다면 자식 프로세스로 실행해서는 효과가 없으므로 eval로 넘겨야 합니다.
+/// 그 원인이 되는 명령어 이름을 반환합니다 (`cd`, `export`, `FOO=bar`).
+/// 서브셸, 파이프라인, 백그라운드 작업 안의 명령어는 현재 셸에 영향을 주지 않습니다.
+pub fn parent_shell_effect(command: &str) -> Option<String> {
+    // 파싱할 수 없으면 bash에 맡김
+    let list = shell::parse(command).ok()?;
+    list_effect(&list)
+}
+
+fn list_effect(list: &List) -> Option<String> {
+    list.items
+        .iter()
+        .filter(|item| item.separator != Separator::Background && item.pipeline.commands.len() == 1)
+        .find_map(|item| match &item.pipeline.commands[0] {
+            ShellCommand::Simple(simple) => match simple.words.first() {
+                None => simple.assignments.first().map(|word| word.text.clone()),
+                Some(program) => {
+                    let name = program.text.as_str();
+                    let subcommand = simple.words.get(1).map(|word| word.text.as_str());
+                    if PARENT_SHELL_BUILTINS.contains(&name) {
+                        Some(name.to_string())
+                    } else {
+                        PARENT_SHELL_FUNCTIONS
+                            .iter()
+                            .find(|(tool, sub)| *tool == name && Some(*sub) == subcommand)
+                            .map(|(tool, sub)| format!("{} {}", tool, sub))
+                    }
+                }
+            },
+            ShellCommand::Group(list, _) => list_effect(list),
+            ShellCommand::FunctionDef { name, .. } => Some(format!("{}()", name)),
+            ShellCommand::Subshell(..) => None,
+        })
 }
 
 impl Default for CommandRunner {
@@ -52,3 +232,74 @@ impl Default for CommandRunner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parent_shell_effect() {
+        let cases = [
+            ("cd src", Some("cd")),
+            ("mkdir -p build && cd build", Some("cd")),
+            ("export PATH=$HOME/.cargo/bin:$PATH", Some("export")),
+            ("source .venv/bin/activate", Some("source")),
+            ("conda activate ml", Some("conda activate")),
+            ("NODE_ENV=production", Some("NODE_ENV=production")),
+            ("{ cd /tmp; ls; }", Some("cd")),
+            ("greet() { echo hi; }", Some("greet()")),
+        ];
+        for (command, expected) in cases {
+            assert_eq!(parent_shell_effect(command).as_deref(), expected, "{}", command);
+        }
+    }
+
+    #[test]
+    fn test_repairable_failures() {
+        let result = |exit_code: i32, stderr: &str| StreamResult {
+            exit_code,
+            stderr: stderr.to_string(),
+            stdout: String::new(),
+        };
+
+        assert!(result(127, "bash: fd: command not found").is_repairable());
+        assert!(result(2, "").is_repairable());
+        assert!(result(1, "ls: unrecognized option '--colour=x'").is_repairable());
+        assert!(!result(0, "warning: deprecated").is_repairable());
+        assert!(!result(1, "").is_repairable());
+        assert!(!result(130, "").is_repairable());
+    }
+
+    #[tokio::test]
+    async fn test_stream_captures_stderr_and_exit_code() {
+        let result = CommandRunner::new().stream("echo out; echo oops >&2; exit 3").await.unwrap();
+        assert_eq!(result.exit_code, 3);
+        assert_eq!(result.stderr, "oops\n");
+        assert_eq!(result.stdout, "");
+
+        let result = CommandRunner::new()
+            .with_stdout_capture(true)
+            .stream("echo out; echo oops >&2")
+            .await
+            .unwrap();
+        assert_eq!(result.exit_code, 0);
+        assert_eq!(result.stdout, "out\n");
+        assert_eq!(result.stderr, "oops\n");
+    }
+
+    #[test]
+    fn test_child_process_commands() {
+        for command in [
+            "ls -la",
+            "NODE_ENV=production npm run build",
+            "(cd src && make)",
+            "cd src | cat",
+            "cd /tmp &",
+            "echo $(cd src && pwd)",
+            "conda list",
+            "echo 'cd",
+        ] {
+            assert_eq!(parent_shell_effect(command), None, "{}", command);
+        }
+    }
+}
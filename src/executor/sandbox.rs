@@ -0,0 +1,551 @@
+//! 격리된 환경에서 명령어를 시뮬레이션 (`--dry-run`)
+//!
+//! 권한 없는 user+mount+pid+net 네임스페이스 안에서 현재 디렉토리 위에 overlayfs를 씌우고
+//! 명령어를 실행한 뒤, 실행 전후의 파일 목록을 비교하여 생성/수정/삭제된 파일을 보고합니다.
+//! 변경은 임시 upper 디렉토리에만 기록되므로 실제 디렉토리는 그대로 남습니다.
+//! overlayfs를 쓸 수 없으면 현재 디렉토리를 임시로 복사하여 그 위에 bind mount합니다.
+//!
+//! 명령어는 전체 파일 시스템을 읽기 전용으로 복제한 새 루트 안에서 실행되며, 마운트 하나라도 읽기 전용으로
+//! 만들 수 없으면 실행하지 않고 `SandboxUnavailable`을 반환합니다. 네트워크는 끊기고, `/dev`는 기본 장치만 있는
+//! tmpfs로, `/tmp`는 빈 tmpfs로 가려집니다.
+
+use crate::error::{AskAiError, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::process::Command;
+use walkdir::WalkDir;
+
+/// 네임스페이스 안에서 실행되는 설정 스크립트
+const SANDBOX_SCRIPT: &str = include_str!("sandbox.sh");
+
+/// 명령어 실행 제한 시간 기본값
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 복사 방식에서 복사할 수 있는 현재 디렉토리의 최대 크기
+const MAX_COPY_BYTES: u64 = 512 * 1024 * 1024;
+
+/// 파일 목록의 마지막 항목 (없으면 목록을 다 쓰기 전에 종료된 것)
+const MANIFEST_END: &str = "done";
+
+/// 같은 프로세스에서 동시에 만든 작업 공간을 구분하는 번호
+static NEXT_WORKSPACE: AtomicUsize = AtomicUsize::new(0);
+
+/// 현재 디렉토리를 가리는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxMode {
+    /// overlayfs (변경만 임시 디렉토리에 기록)
+    Overlay,
+    /// 현재 디렉토리 전체를 임시로 복사
+    Copy,
+}
+
+impl SandboxMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            SandboxMode::Overlay => "overlay",
+            SandboxMode::Copy => "copy",
+        }
+    }
+}
+
+/// 파일 변경 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// 시뮬레이션으로 바뀐 파일 하나
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub kind: ChangeKind,
+    /// 현재 디렉토리 기준 경로
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// 디렉토리와 함께 생성/삭제된 하위 항목 수
+    pub nested: usize,
+}
+
+/// 시뮬레이션 결과
+///
+/// 샌드박스가 현재 디렉토리 밖에 쓸 수 없음을 확인한 뒤 실행했을 때만 만들어집니다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    pub mode: SandboxMode,
+    /// 종료 코드 (시그널로 종료되면 None)
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub changes: Vec<FileChange>,
+}
+
+impl Simulation {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
+/// 실행 전후를 비교하기 위한 항목 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EntryState {
+    /// `find -printf %y` 형식 (`d`, `f`, `l`, 그 외는 `o`)
+    kind: char,
+    size: u64,
+    mtime: (i64, u32),
+}
+
+type Snapshot = BTreeMap<PathBuf, EntryState>;
+
+/// 네임스페이스 샌드박스
+pub struct Sandbox {
+    cwd: PathBuf,
+    timeout: Duration,
+}
+
+impl Sandbox {
+    pub fn new(cwd: &Path) -> Self {
+        Self {
+            cwd: cwd.to_path_buf(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    #[allow(dead_code)]  // Public API (오래 걸리는 명령어를 시뮬레이션할 때)
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// 명령어를 시뮬레이션하여 출력과 파일 변경을 반환
+    ///
+    /// overlayfs를 먼저 시도하고, 안 되면 복사 방식으로 다시 시도합니다.
+    /// 네임스페이스를 만들 수 없으면 `SandboxUnavailable`을 반환합니다 (명령어는 실행되지 않음).
+    pub async fn run(&self, command: &str) -> Result<Simulation> {
+        if self.cwd.parent().is_none() {
+            return Err(AskAiError::SandboxUnavailable(
+                "can't simulate commands in the root directory".to_string(),
+            ));
+        }
+
+        match self.run_in(SandboxMode::Overlay, command).await {
+            Err(AskAiError::SandboxUnavailable(overlay_error)) => {
+                self.run_in(SandboxMode::Copy, command).await.map_err(|e| match e {
+                    AskAiError::SandboxUnavailable(copy_error) => AskAiError::SandboxUnavailable(format!(
+                        "overlay: {}; copy: {}",
+                        overlay_error, copy_error
+                    )),
+                    other => other,
+                })
+            }
+            result => result,
+        }
+    }
+
+    async fn run_in(&self, mode: SandboxMode, command: &str) -> Result<Simulation> {
+        let workspace = Workspace::create(mode)?;
+
+        // 실행 전 상태는 명령어가 보게 될 디렉토리 기준
+        let lower = match mode {
+            SandboxMode::Overlay => self.cwd.clone(),
+            SandboxMode::Copy => {
+                copy_tree(&self.cwd, &workspace.upper)?;
+                workspace.upper.clone()
+            }
+        };
+        let before = snapshot(&lower);
+
+        let child = Command::new("unshare")
+            .args([
+                "--user", "--map-root-user", "--mount", "--net", "--ipc", "--uts",
+                "--pid", "--fork", "--kill-child", "--mount-proc",
+                "--", "bash", "-c", SANDBOX_SCRIPT, "askai-sandbox",
+            ])
+            .arg(&self.cwd)
+            .arg(&workspace.rootfs)
+            .arg(&workspace.upper)
+            .arg(&workspace.work)
+            .arg(&workspace.manifest)
+            .arg(mode.name())
+            .arg(command)
+            .current_dir(&self.cwd)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)  // 제한 시간 초과 시 네임스페이스째 종료
+            .spawn()
+            .map_err(|e| AskAiError::SandboxUnavailable(format!("unshare: {}", e)))?;
+
+        let output = tokio::time::timeout(self.timeout, child.wait_with_output())
+            .await
+            .map_err(|_| AskAiError::ExecutionError(format!(
+                "simulation did not finish within {}s",
+                self.timeout.as_secs()
+            )))?
+            .map_err(|e| AskAiError::ExecutionError(e.to_string()))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        // 설정 단계에서 실패하면 목록 파일이 없음
+        let Ok(manifest) = fs::read(&workspace.manifest) else {
+            let reason = stderr.trim();
+            return Err(AskAiError::SandboxUnavailable(if reason.is_empty() {
+                format!("{} sandbox could not be set up", mode.name())
+            } else {
+                reason.to_string()
+            }));
+        };
+
+        let after = parse_manifest(&manifest).ok_or_else(|| {
+            AskAiError::ExecutionError("simulation ended before file changes could be recorded".to_string())
+        })?;
+
+        Ok(Simulation {
+            mode,
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr,
+            changes: diff(&before, &after),
+        })
+    }
+}
+
+/// 새 루트, upper/work 디렉토리와 목록 파일을 담는 임시 작업 공간 (drop 시 삭제)
+struct Workspace {
+    root: PathBuf,
+    rootfs: PathBuf,
+    upper: PathBuf,
+    work: PathBuf,
+    manifest: PathBuf,
+}
+
+impl Workspace {
+    fn create(mode: SandboxMode) -> Result<Self> {
+        let root = std::env::temp_dir().join(format!(
+            "askai-sandbox-{}-{}-{}",
+            std::process::id(),
+            NEXT_WORKSPACE.fetch_add(1, Ordering::Relaxed),
+            mode.name()
+        ));
+        remove_tree(&root);
+
+        let workspace = Self {
+            rootfs: root.join("rootfs"),
+            upper: root.join("upper"),
+            work: root.join("work"),
+            manifest: root.join("manifest"),
+            root,
+        };
+        fs::create_dir_all(&workspace.rootfs)?;
+        fs::create_dir_all(&workspace.upper)?;
+        fs::create_dir_all(&workspace.work)?;
+        Ok(workspace)
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        remove_tree(&self.root);
+    }
+}
+
+/// 디렉토리 삭제 (overlayfs가 만든 권한 000 디렉토리도 지울 수 있도록 권한을 먼저 복구)
+fn remove_tree(root: &Path) {
+    for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
+        if entry.file_type().is_dir() {
+            let _ = fs::set_permissions(entry.path(), fs::Permissions::from_mode(0o700));
+        }
+    }
+    let _ = fs::remove_dir_all(root);
+}
+
+/// 복사 방식용: 현재 디렉토리를 통째로 복사 (심볼릭 링크는 링크로)
+fn copy_tree(from: &Path, to: &Path) -> Result<()> {
+    let mut copied = 0u64;
+
+    for entry in WalkDir::new(from).min_depth(1).follow_links(false) {
+        let entry = entry.map_err(|e| AskAiError::SandboxUnavailable(e.to_string()))?;
+        let relative = entry.path().strip_prefix(from).unwrap_or(entry.path());
+        let target = to.join(relative);
+        let file_type = entry.file_type();
+
+        if file_type.is_dir() {
+            fs::create_dir_all(&target)?;
+        } else if file_type.is_symlink() {
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+        } else if file_type.is_file() {
+            copied += fs::copy(entry.path(), &target)?;
+            if copied > MAX_COPY_BYTES {
+                return Err(AskAiError::SandboxUnavailable(format!(
+                    "current directory is larger than {} MB and can't be copied",
+                    MAX_COPY_BYTES / 1024 / 1024
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 실행 전 디렉토리 상태
+fn snapshot(root: &Path) -> Snapshot {
+    WalkDir::new(root)
+        .min_depth(1)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let file_type = entry.file_type();
+            let kind = if file_type.is_dir() {
+                'd'
+            } else if file_type.is_symlink() {
+                'l'
+            } else if file_type.is_file() {
+                'f'
+            } else {
+                'o'
+            };
+            let relative = entry.path().strip_prefix(root).ok()?.to_path_buf();
+            let state = EntryState {
+                kind,
+                size: metadata.size(),
+                mtime: (metadata.mtime(), metadata.mtime_nsec() as u32),
+            };
+            Some((relative, state))
+        })
+        .collect()
+}
+
+/// 샌드박스가 기록한 `find -printf '%y %s %T@ %P\0'` 목록 해석
+///
+/// 마지막 `done` 항목이 없으면 목록이 중간에 끊긴 것이므로 `None`을 반환합니다.
+fn parse_manifest(manifest: &[u8]) -> Option<Snapshot> {
+    let text = String::from_utf8_lossy(manifest);
+    let mut records: Vec<&str> = text.split('\0').collect();
+
+    // 마지막 구분자 뒤의 빈 문자열 제거
+    if records.last() == Some(&"") {
+        records.pop();
+    }
+    if records.pop() != Some(MANIFEST_END) {
+        return None;
+    }
+
+    let mut entries = Snapshot::new();
+    for record in records {
+        let mut fields = record.splitn(4, ' ');
+        let (Some(kind), Some(size), Some(mtime), Some(path)) =
+            (fields.next(), fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        let kind = match kind {
+            "d" | "f" | "l" => kind.chars().next().unwrap_or('o'),
+            _ => 'o',
+        };
+        let state = EntryState {
+            kind,
+            size: size.parse().unwrap_or(0),
+            mtime: parse_mtime(mtime),
+        };
+        entries.insert(PathBuf::from(path), state);
+    }
+    Some(entries)
+}
+
+/// `1700000000.1234567890` -> (초, 나노초)
+fn parse_mtime(text: &str) -> (i64, u32) {
+    let (secs, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let nanos: String = fraction.chars().chain(std::iter::repeat('0')).take(9).collect();
+    (secs.parse().unwrap_or(0), nanos.parse().unwrap_or(0))
+}
+
+/// 실행 전후 비교 (생성/삭제된 디렉토리의 하위 항목은 디렉토리 하나로 묶음)
+///
+/// 디렉토리는 내용이 바뀌면 크기와 시각도 바뀌므로 생성/삭제만 봅니다.
+fn diff(before: &Snapshot, after: &Snapshot) -> Vec<FileChange> {
+    let mut changes: Vec<FileChange> = Vec::new();
+
+    for (path, old) in before {
+        let kind = match after.get(path) {
+            None => ChangeKind::Deleted,
+            Some(new) if new.kind != old.kind => ChangeKind::Modified,
+            Some(new) if old.kind != 'd' && (new.size != old.size || new.mtime != old.mtime) => ChangeKind::Modified,
+            Some(_) => continue,
+        };
+        changes.push(FileChange { kind, path: path.clone(), is_dir: old.kind == 'd', nested: 0 });
+    }
+    for (path, new) in after {
+        if !before.contains_key(path) {
+            changes.push(FileChange { kind: ChangeKind::Created, path: path.clone(), is_dir: new.kind == 'd', nested: 0 });
+        }
+    }
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    // 정렬하면 하위 항목은 디렉토리 바로 뒤에 이어짐
+    let mut collapsed: Vec<FileChange> = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+    for change in changes {
+        while open.last().is_some_and(|&index| !change.path.starts_with(&collapsed[index].path)) {
+            open.pop();
+        }
+        if let Some(&index) = open.last() {
+            if collapsed[index].kind == change.kind {
+                collapsed[index].nested += 1;
+                continue;
+            }
+        }
+        if change.is_dir && change.kind != ChangeKind::Modified {
+            open.push(collapsed.len());
+        }
+        collapsed.push(change);
+    }
+    collapsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(kind: char, size: u64, secs: i64) -> EntryState {
+        EntryState { kind, size, mtime: (secs, 0) }
+    }
+
+    fn summary(changes: &[FileChange]) -> Vec<(ChangeKind, &str, usize)> {
+        changes
+            .iter()
+            .map(|c| (c.kind, c.path.to_str().unwrap(), c.nested))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = b"d 4096 1700000000.5000000000 sub\0f 12 1700000000.1234567890 sub/a file.txt\0done\0";
+        let entries = parse_manifest(manifest).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[Path::new("sub/a file.txt")], EntryState {
+            kind: 'f',
+            size: 12,
+            mtime: (1700000000, 123456789),
+        });
+
+        // 끝 표시가 없으면 목록이 끊긴 것
+        assert!(parse_manifest(b"f 1 1.0 a\0").is_none());
+        assert_eq!(parse_manifest(b"done\0").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_diff_detects_changes() {
+        let before: Snapshot = [
+            (PathBuf::from("a.txt"), state('f', 1, 10)),
+            (PathBuf::from("b.txt"), state('f', 1, 10)),
+            (PathBuf::from("src"), state('d', 4096, 10)),
+            (PathBuf::from("src/main.rs"), state('f', 5, 10)),
+        ].into_iter().collect();
+        let after: Snapshot = [
+            (PathBuf::from("b.txt"), state('f', 2, 20)),
+            (PathBuf::from("c.txt"), state('f', 1, 20)),
+            (PathBuf::from("src"), state('d', 8192, 20)),  // 디렉토리 시각 변경은 무시
+            (PathBuf::from("src/main.rs"), state('f', 5, 10)),
+        ].into_iter().collect();
+
+        assert_eq!(summary(&diff(&before, &after)), vec![
+            (ChangeKind::Deleted, "a.txt", 0),
+            (ChangeKind::Modified, "b.txt", 0),
+            (ChangeKind::Created, "c.txt", 0),
+        ]);
+    }
+
+    #[test]
+    fn test_diff_collapses_directories() {
+        let before: Snapshot = [
+            (PathBuf::from("build"), state('d', 0, 10)),
+            (PathBuf::from("build/a.o"), state('f', 1, 10)),
+            (PathBuf::from("build/obj"), state('d', 0, 10)),
+            (PathBuf::from("build/obj/b.o"), state('f', 1, 10)),
+            (PathBuf::from("build-log"), state('f', 1, 10)),
+        ].into_iter().collect();
+        let after: Snapshot = [
+            (PathBuf::from("build-log"), state('f', 1, 10)),
+            (PathBuf::from("dist"), state('d', 0, 20)),
+            (PathBuf::from("dist/app"), state('f', 1, 20)),
+        ].into_iter().collect();
+
+        assert_eq!(summary(&diff(&before, &after)), vec![
+            (ChangeKind::Deleted, "build", 3),
+            (ChangeKind::Created, "dist", 1),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_simulation_leaves_directory_untouched() {
+        let root = std::env::temp_dir().join(format!("askai-sandbox-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("keep.txt"), "keep").unwrap();
+        fs::write(root.join("edit.txt"), "old").unwrap();
+
+        let result = Sandbox::new(&root)
+            .run("rm keep.txt && echo new > edit.txt && touch created.txt && echo done")
+            .await;
+
+        // 네임스페이스를 만들 수 없는 환경에서는 건너뜀
+        let simulation = match result {
+            Err(AskAiError::SandboxUnavailable(_)) => {
+                let _ = fs::remove_dir_all(&root);
+                return;
+            }
+            other => other.unwrap(),
+        };
+
+        assert!(simulation.success());
+        assert_eq!(simulation.stdout.trim(), "done");
+        assert_eq!(summary(&simulation.changes), vec![
+            (ChangeKind::Created, "created.txt", 0),
+            (ChangeKind::Modified, "edit.txt", 0),
+            (ChangeKind::Deleted, "keep.txt", 0),
+        ]);
+
+        // 실제 디렉토리는 그대로
+        assert_eq!(fs::read_to_string(root.join("edit.txt")).unwrap(), "old");
+        assert!(root.join("keep.txt").exists());
+        assert!(!root.join("created.txt").exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn test_simulation_cant_write_outside_directory() {
+        let root = std::env::temp_dir().join(format!("askai-sandbox-escape-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let cwd = root.join("project");
+        fs::create_dir_all(&cwd).unwrap();
+
+        let outside = root.join("absolute.txt");
+        let result = Sandbox::new(&cwd)
+            .run(&format!("touch ../escaped.txt; echo hi > {}; echo done", outside.display()))
+            .await;
+
+        let simulation = match result {
+            Err(AskAiError::SandboxUnavailable(_)) => {
+                let _ = fs::remove_dir_all(&root);
+                return;
+            }
+            other => other.unwrap(),
+        };
+
+        // 현재 디렉토리 밖은 읽기 전용이라 실패하고, 실제로 만들어진 파일도 없음
+        assert_eq!(simulation.stdout.trim(), "done");
+        assert!(simulation.stderr.contains("Read-only file system"));
+        assert!(simulation.changes.is_empty());
+        assert!(!root.join("escaped.txt").exists());
+        assert!(!outside.exists());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,67 @@
+# askai 샌드박스 (`unshare --user --map-root-user --mount --net --pid ...` 안에서 실행)
+#
+# 인자: <cwd> <rootfs> <upper> <work> <manifest> <overlay|copy> <command>
+# 설정에 실패하면 manifest를 만들지 않고 종료하며, 호출자는 이것으로 설정 실패를 구분합니다.
+# 원래 마운트는 건드리지 않고, 읽기 전용으로 복제한 새 루트로 들어가서 실행합니다.
+
+cwd=$1 rootfs=$2 upper=$3 work=$4 manifest=$5 mode=$6 command=$7
+
+# 1. 전체 파일 시스템을 새 루트에 복제
+mount --rbind / "$rootfs" || exit 1
+
+# 2. 복제한 마운트를 모두 읽기 전용으로 (하나라도 실패하면 실행하지 않음)
+while read -r _ _ _ _ point _; do
+    case "$point" in
+        "$rootfs" | "$rootfs"/*) ;;
+        *) continue ;;
+    esac
+    case "$point" in
+        "$rootfs/dev" | "$rootfs/dev/"*) continue ;;
+    esac
+    mount -o remount,bind,ro "$point" || exit 1
+done < /proc/self/mountinfo
+
+# 3. 실제 장치가 보이지 않도록 /dev는 기본 장치만 있는 tmpfs로 가림
+mount -t tmpfs -o mode=755 tmpfs "$rootfs/dev" || exit 1
+for dev in null zero full random urandom; do
+    : > "$rootfs/dev/$dev" || exit 1
+    mount --bind "/dev/$dev" "$rootfs/dev/$dev" || exit 1
+done
+ln -s /proc/self/fd "$rootfs/dev/fd"
+mkdir "$rootfs/dev/shm"
+
+# 4. 임시 파일은 버려지는 tmpfs에 (cwd가 /tmp 안이면 가리지 않음)
+case "$cwd" in
+    /tmp | /tmp/*) ;;
+    *) mount -t tmpfs tmpfs "$rootfs/tmp" || exit 1 ;;
+esac
+
+# 5. 현재 디렉토리를 임시 레이어로 가림 (변경은 upper에만 기록됨)
+if [ "$mode" = overlay ]; then
+    mount -t overlay overlay -o "lowerdir=$cwd,upperdir=$upper,workdir=$work,userxattr" "$rootfs$cwd" || exit 1
+else
+    mount --bind "$upper" "$rootfs$cwd" || exit 1
+fi
+
+# 6. 격리 확인: 현재 디렉토리 밖에 쓸 수 있으면 실행하지 않음
+probe="$rootfs$(dirname "$cwd")/.askai-sandbox-probe-$$"
+if : 2>/dev/null > "$probe"; then
+    rm -f "$probe"
+    echo "the filesystem outside the current directory is still writable" >&2
+    exit 1
+fi
+
+# 7. 결과 목록은 루트를 바꾸기 전에 열어 두고, 원래 루트는 떼어냄
+exec 3>"$manifest"
+cd "$rootfs" || exit 1
+pivot_root . . || { rm -f "$manifest"; exit 1; }
+umount -l . || { rm -f "$manifest"; exit 1; }
+
+# 8. 명령어 실행 후 실행 뒤의 파일 목록 기록
+cd "$cwd" || exit 1
+bash -c "$command" 3>&-
+status=$?
+cd "$cwd" || exit "$status"
+find . -mindepth 1 -printf '%y %s %T@ %P\0' >&3
+printf 'done\0' >&3
+exit "$status"
@@ -0,0 +1,131 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// heredoc 시작 (`<<EOF`, `<<-'EOF'`, `<< "EOF"`), here-string(`<<<`)은 제외
+static HEREDOC_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|[^<])<<(-?)\s*['"]?([A-Za-z_][A-Za-z0-9_]*)['"]?"#).unwrap()
+});
+
+/// 검증 단위가 되는 스크립트의 논리적 한 줄
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptLine {
+    /// 시작 줄 번호 (1부터)
+    pub line_no: usize,
+    /// 줄 이음(`\`)을 합친 명령어 (heredoc이 있으면 본문과 종료 줄까지 포함)
+    pub text: String,
+}
+
+/// 스크립트를 검증할 명령어 줄 단위로 분리
+///
+/// - 빈 줄과 주석(shebang 포함)은 제외
+/// - `\`로 이어진 줄은 하나로 합침
+/// - heredoc 본문은 따로 검사하지 않고 heredoc을 연 줄에 붙임
+///   (`bash <<EOF`처럼 본문을 실행하는 경우 검증기가 본문까지 검사)
+pub fn script_lines(script: &str) -> Vec<ScriptLine> {
+    let mut lines: Vec<ScriptLine> = Vec::new();
+    let mut pending: Option<ScriptLine> = None;
+    // 아직 닫히지 않은 heredoc 종료 문자열 (앞쪽부터 순서대로 닫힘)
+    let mut heredoc_terminators: Vec<String> = Vec::new();
+
+    for (index, raw) in script.lines().enumerate() {
+        let line_no = index + 1;
+
+        if let Some(terminator) = heredoc_terminators.first() {
+            if raw.trim() == terminator {
+                heredoc_terminators.remove(0);
+            }
+            if let Some(opening) = lines.last_mut() {
+                opening.text.push('\n');
+                opening.text.push_str(raw);
+            }
+            continue;
+        }
+
+        let trimmed = raw.trim();
+        if pending.is_none() && (trimmed.is_empty() || trimmed.starts_with('#')) {
+            continue;
+        }
+
+        let (content, continues) = match trimmed.strip_suffix('\\') {
+            Some(rest) => (rest.trim_end(), true),
+            None => (trimmed, false),
+        };
+
+        let current = pending.get_or_insert_with(|| ScriptLine {
+            line_no,
+            text: String::new(),
+        });
+        if !current.text.is_empty() {
+            current.text.push(' ');
+        }
+        current.text.push_str(content);
+
+        if continues {
+            continue;
+        }
+
+        let line = pending.take().unwrap();
+        heredoc_terminators.extend(
+            HEREDOC_REGEX
+                .captures_iter(&line.text)
+                .map(|caps| caps[2].to_string())
+        );
+        lines.push(line);
+    }
+
+    // 마지막 줄이 `\`로 끝난 경우
+    if let Some(line) = pending {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skips_comments_and_blank_lines() {
+        let script = "#!/usr/bin/env bash\n\n# rename files\nset -euo pipefail\n";
+        let lines = script_lines(script);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0], ScriptLine { line_no: 4, text: "set -euo pipefail".to_string() });
+    }
+
+    #[test]
+    fn test_joins_line_continuations() {
+        let script = "find . -name '*.jpeg' \\\n  -type f \\\n  -print\necho done";
+        let lines = script_lines(script);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].text, "find . -name '*.jpeg' -type f -print");
+        assert_eq!(lines[1].line_no, 4);
+    }
+
+    #[test]
+    fn test_heredoc_body_is_not_a_command() {
+        let script = "cat <<'EOF' > notes.txt\nrm -rf / is just text here\nEOF\necho ok";
+        let lines = script_lines(script);
+
+        let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, vec!["cat <<'EOF' > notes.txt\nrm -rf / is just text here\nEOF", "echo ok"]);
+        assert_eq!(lines[1].line_no, 4);
+    }
+
+    #[test]
+    fn test_here_string_is_not_a_heredoc() {
+        let lines = script_lines("grep foo <<< \"$text\"\necho after");
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_control_flow_lines_are_kept() {
+        let script = "for f in *.jpeg; do\n  mv \"$f\" \"${f%.jpeg}.jpg\" || echo \"failed: $f\" >&2\ndone";
+        let lines = script_lines(script);
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].text.starts_with("mv"));
+    }
+}
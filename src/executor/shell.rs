@@ -0,0 +1,890 @@
+//! 안전성 검사용 셸 구문 파서
+//!
+//! bash 문법의 부분집합을 AST로 변환합니다: 파이프라인, `&&`/`||`/`;`/`&` 목록,
+//! 서브셸, `{ ...; }` 그룹, 함수 정의, 명령어 치환(`$(...)`, 백틱), 프로세스 치환,
+//! 리다이렉션, heredoc, 따옴표. 실행용이 아니라 "어떤 명령어가 어떤 인자로 실행되는가"를
+//! 알아내기 위한 파서이므로 확장(변수, glob)은 하지 않습니다.
+
+use std::ops::Range;
+
+/// 명령어 목록 (`a && b; c &`)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct List {
+    pub items: Vec<ListItem>,
+}
+
+/// 목록의 한 항목과 뒤따르는 구분자
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListItem {
+    pub pipeline: Pipeline,
+    pub separator: Separator,
+}
+
+/// 파이프라인 뒤의 구분자
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separator {
+    /// `&&`
+    And,
+    /// `||`
+    Or,
+    /// `;` 또는 줄바꿈
+    Sequence,
+    /// `&`
+    Background,
+    /// 목록의 끝
+    End,
+}
+
+/// 파이프라인 (`a | b | c`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pipeline {
+    /// `!`로 종료 코드를 반전하는지
+    pub negated: bool,
+    pub commands: Vec<Command>,
+}
+
+/// 파이프라인을 구성하는 명령어
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// 단순 명령어 (`rm -rf build > log`)
+    Simple(SimpleCommand),
+    /// `( ... )`
+    Subshell(List, Vec<Redirect>),
+    /// `{ ...; }`
+    Group(List, Vec<Redirect>),
+    /// `name() body`
+    FunctionDef { name: String, body: Box<Command> },
+}
+
+/// 단순 명령어
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimpleCommand {
+    /// 앞쪽의 `NAME=value` 할당
+    pub assignments: Vec<Word>,
+    /// 프로그램과 인자
+    pub words: Vec<Word>,
+    pub redirects: Vec<Redirect>,
+    /// 원본 문자열에서의 위치
+    pub span: Range<usize>,
+}
+
+/// 따옴표가 제거된 단어
+#[derive(Debug, Clone, PartialEq)]
+pub struct Word {
+    /// 따옴표와 이스케이프를 제거한 값 (치환은 원문 그대로)
+    pub text: String,
+    /// 원본 문자열에서의 위치
+    pub span: Range<usize>,
+    /// 따옴표가 일부라도 사용되었는지
+    pub quoted: bool,
+    /// 단어 안의 명령어/프로세스 치환
+    pub substitutions: Vec<List>,
+}
+
+/// 리다이렉션 (`2>&1`, `> /dev/sda`, `<<EOF`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redirect {
+    /// 연산자 (`>`, `>>`, `<`, `<<`, `&>`, `>&` ...), fd 번호 제외
+    pub op: String,
+    pub target: Word,
+    pub span: Range<usize>,
+    /// heredoc(`<<`, `<<-`) 본문 (종료 문자열 줄 제외, `<<-`면 앞쪽 탭 제거)
+    pub body: Option<String>,
+}
+
+impl Redirect {
+    /// 파일에 쓰는 리다이렉션인지
+    pub fn is_output(&self) -> bool {
+        matches!(self.op.as_str(), ">" | ">>" | ">|" | "&>" | "&>>" | "<>")
+    }
+}
+
+impl List {
+    /// 치환, 서브셸, 함수 본문을 포함한 모든 명령어를 방문
+    pub fn visit<'a>(&'a self, f: &mut dyn FnMut(&'a Command)) {
+        for item in &self.items {
+            for command in &item.pipeline.commands {
+                command.visit(f);
+            }
+        }
+    }
+
+    /// 치환, 서브셸, 함수 본문을 포함한 모든 파이프라인을 방문
+    pub fn visit_pipelines<'a>(&'a self, f: &mut dyn FnMut(&'a Pipeline)) {
+        for item in &self.items {
+            f(&item.pipeline);
+            for command in &item.pipeline.commands {
+                for list in command.nested_lists() {
+                    list.visit_pipelines(f);
+                }
+            }
+        }
+    }
+}
+
+impl Command {
+    /// 자신과 치환, 서브셸, 함수 본문 안의 모든 명령어를 방문
+    pub fn visit<'a>(&'a self, f: &mut dyn FnMut(&'a Command)) {
+        f(self);
+
+        match self {
+            Command::FunctionDef { body, .. } => body.visit(f),
+            _ => {
+                for list in self.nested_lists() {
+                    list.visit(f);
+                }
+            }
+        }
+    }
+
+    /// 명령어 안에 들어 있는 목록 (치환, 서브셸/그룹 본문, 함수 본문)
+    fn nested_lists(&self) -> Vec<&List> {
+        match self {
+            Command::Simple(simple) => simple.assignments
+                .iter()
+                .chain(&simple.words)
+                .chain(simple.redirects.iter().map(|r| &r.target))
+                .flat_map(|word| &word.substitutions)
+                .collect(),
+            Command::Subshell(list, redirects) | Command::Group(list, redirects) => std::iter::once(list)
+                .chain(redirects.iter().flat_map(|r| &r.target.substitutions))
+                .collect(),
+            Command::FunctionDef { body, .. } => body.nested_lists(),
+        }
+    }
+}
+
+/// 명령어 문자열을 AST로 파싱
+///
+/// 닫히지 않은 따옴표나 괄호가 있으면 에러 메시지를 반환합니다.
+pub fn parse(source: &str) -> Result<List, String> {
+    let mut parser = Parser::new(source, 0);
+    let list = parser.parse_list(Terminator::EndOfInput)?;
+
+    if parser.pos < parser.chars.len() {
+        let (offset, c) = parser.chars[parser.pos];
+        return Err(format!("unexpected '{}' at offset {}", c, offset));
+    }
+
+    Ok(list)
+}
+
+/// 목록 파싱을 멈추는 지점
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Terminator {
+    EndOfInput,
+    /// 서브셸 / 명령어 치환의 `)`
+    Paren,
+    /// 그룹의 `}`
+    Brace,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    /// (원본 문자열 기준 byte offset, 문자)
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    /// 다음 줄바꿈 이후에 건너뛸 heredoc (종료 문자열, 탭 제거 여부)
+    pending_heredocs: Vec<(String, bool)>,
+}
+
+impl<'a> Parser<'a> {
+    /// `base`는 `source`가 원본에서 시작하는 offset (백틱 내부 파싱용)
+    fn new(source: &'a str, base: usize) -> Self {
+        Self {
+            source,
+            chars: source.char_indices().map(|(i, c)| (i + base, c)).collect(),
+            pos: 0,
+            pending_heredocs: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|(_, c)| *c)
+    }
+
+    fn peek_at(&self, ahead: usize) -> Option<char> {
+        self.chars.get(self.pos + ahead).map(|(_, c)| *c)
+    }
+
+    /// 현재 위치의 원본 offset (끝이면 마지막 위치)
+    fn offset(&self) -> usize {
+        self.chars
+            .get(self.pos)
+            .map(|(i, _)| *i)
+            .unwrap_or_else(|| self.chars.last().map_or(0, |(i, c)| i + c.len_utf8()))
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars().enumerate().all(|(i, c)| self.peek_at(i) == Some(c))
+    }
+
+    /// 공백(줄바꿈 제외)과 줄 이음 건너뛰기
+    fn skip_blanks(&mut self) {
+        while let Some(c) = self.peek() {
+            if c == ' ' || c == '\t' || c == '\r' {
+                self.pos += 1;
+            } else if c == '\\' && self.peek_at(1) == Some('\n') {
+                self.pos += 2;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// 주석을 줄 끝까지 건너뛰기
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while let Some(c) = self.peek() {
+                if c == '\n' {
+                    break;
+                }
+                self.pos += 1;
+            }
+        }
+    }
+
+    /// 줄바꿈 하나를 소비하고 대기 중인 heredoc 본문 건너뛰기
+    fn consume_newline(&mut self) {
+        self.pos += 1;
+
+        for (terminator, strip_tabs) in std::mem::take(&mut self.pending_heredocs) {
+            self.pos = self.scan_heredoc(self.pos, &terminator, strip_tabs).1;
+        }
+    }
+
+    /// `start`부터 종료 문자열 줄까지 heredoc 본문 읽기
+    ///
+    /// # Returns
+    /// * (본문, 종료 문자열 줄 다음 위치)
+    fn scan_heredoc(&self, start: usize, terminator: &str, strip_tabs: bool) -> (String, usize) {
+        let mut body = String::new();
+        let mut pos = start;
+
+        while pos < self.chars.len() {
+            let line_start = pos;
+            while self.chars.get(pos).is_some_and(|(_, c)| *c != '\n') {
+                pos += 1;
+            }
+            let line: String = self.chars[line_start..pos].iter().map(|(_, c)| c).collect();
+            if pos < self.chars.len() {
+                pos += 1;
+            }
+
+            let line = if strip_tabs { line.trim_start_matches('\t') } else { line.as_str() };
+            if line.trim_end() == terminator {
+                break;
+            }
+            body.push_str(line);
+            body.push('\n');
+        }
+
+        (body, pos)
+    }
+
+    /// 방금 읽은 heredoc의 본문을 미리 읽음 (다음 줄부터, 같은 줄의 앞선 heredoc 본문 다음)
+    fn peek_heredoc_body(&self, terminator: &str, strip_tabs: bool) -> String {
+        let Some(newline) = (self.pos..self.chars.len()).find(|&i| self.chars[i].1 == '\n') else {
+            return String::new();
+        };
+
+        let mut start = newline + 1;
+        for (pending, pending_strip_tabs) in &self.pending_heredocs {
+            start = self.scan_heredoc(start, pending, *pending_strip_tabs).1;
+        }
+        self.scan_heredoc(start, terminator, strip_tabs).0
+    }
+
+    /// 공백, 줄바꿈, 주석 건너뛰기
+    fn skip_separators(&mut self) {
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                Some('\n') => self.consume_newline(),
+                Some('#') => self.skip_comment(),
+                _ => break,
+            }
+        }
+    }
+
+    /// 현재 위치가 독립된 단어 `word`인지 (`{`, `}` 예약어 판별)
+    fn at_reserved(&self, word: char) -> bool {
+        self.peek() == Some(word)
+            && self.peek_at(1).is_none_or(|c| c.is_whitespace() || c == ';' || c == '&' || c == '|' || c == ')')
+    }
+
+    fn parse_list(&mut self, terminator: Terminator) -> Result<List, String> {
+        let mut list = List::default();
+
+        loop {
+            self.skip_separators();
+
+            match (self.peek(), terminator) {
+                (None, _) => break,
+                (Some(')'), Terminator::Paren) => break,
+                (Some('}'), Terminator::Brace) if self.at_reserved('}') => break,
+                (Some(')'), _) => {
+                    // `case` 절의 패턴 종료 (`*.jpg) ...`)
+                    self.pos += 1;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let pipeline = self.parse_pipeline(terminator)?;
+            self.skip_blanks();
+
+            let separator = if self.starts_with("&&") {
+                self.pos += 2;
+                Separator::And
+            } else if self.starts_with("||") {
+                self.pos += 2;
+                Separator::Or
+            } else if self.starts_with(";;") {
+                // case 절 구분자는 순차 실행으로 취급
+                self.pos += 2;
+                Separator::Sequence
+            } else if self.peek() == Some(';') {
+                self.pos += 1;
+                Separator::Sequence
+            } else if self.peek() == Some('&') {
+                self.pos += 1;
+                Separator::Background
+            } else if self.peek() == Some('\n') {
+                self.consume_newline();
+                Separator::Sequence
+            } else {
+                Separator::End
+            };
+
+            list.items.push(ListItem { pipeline, separator });
+        }
+
+        if let Some(last) = list.items.last_mut() {
+            if last.separator == Separator::Sequence {
+                last.separator = Separator::End;
+            }
+        }
+
+        Ok(list)
+    }
+
+    fn parse_pipeline(&mut self, terminator: Terminator) -> Result<Pipeline, String> {
+        self.skip_blanks();
+
+        let mut negated = false;
+        if self.peek() == Some('!') && self.peek_at(1).is_some_and(char::is_whitespace) {
+            negated = true;
+            self.pos += 1;
+        }
+
+        let mut commands = vec![self.parse_command(terminator)?];
+
+        loop {
+            self.skip_blanks();
+            if self.peek() == Some('|') && self.peek_at(1) != Some('|') {
+                // `|&`는 stderr까지 연결하는 파이프
+                self.pos += if self.peek_at(1) == Some('&') { 2 } else { 1 };
+                self.skip_separators();
+                commands.push(self.parse_command(terminator)?);
+            } else {
+                break;
+            }
+        }
+
+        Ok(Pipeline { negated, commands })
+    }
+
+    fn parse_command(&mut self, terminator: Terminator) -> Result<Command, String> {
+        self.skip_blanks();
+
+        if self.peek() == Some('(') && self.peek_at(1) != Some('(') {
+            let start = self.offset();
+            self.pos += 1;
+            let list = self.parse_list(Terminator::Paren)?;
+            if self.peek() != Some(')') {
+                return Err(format!("unclosed '(' at offset {}", start));
+            }
+            self.pos += 1;
+            let redirects = self.parse_trailing_redirects()?;
+            return Ok(Command::Subshell(list, redirects));
+        }
+
+        if self.at_reserved('{') {
+            let start = self.offset();
+            self.pos += 1;
+            let list = self.parse_list(Terminator::Brace)?;
+            if !self.at_reserved('}') {
+                return Err(format!("unclosed '{{' at offset {}", start));
+            }
+            self.pos += 1;
+            let redirects = self.parse_trailing_redirects()?;
+            return Ok(Command::Group(list, redirects));
+        }
+
+        let simple = self.parse_simple_command(terminator)?;
+
+        // `name() { ...; }` 함수 정의
+        if simple.words.len() == 1 && simple.redirects.is_empty() && simple.assignments.is_empty() {
+            self.skip_blanks();
+            if self.starts_with("()") {
+                self.pos += 2;
+                self.skip_separators();
+                let body = self.parse_command(terminator)?;
+                return Ok(Command::FunctionDef {
+                    name: simple.words[0].text.clone(),
+                    body: Box::new(body),
+                });
+            }
+        }
+
+        Ok(Command::Simple(simple))
+    }
+
+    fn parse_trailing_redirects(&mut self) -> Result<Vec<Redirect>, String> {
+        let mut redirects = Vec::new();
+        loop {
+            self.skip_blanks();
+            match self.try_parse_redirect()? {
+                Some(redirect) => redirects.push(redirect),
+                None => break,
+            }
+        }
+        Ok(redirects)
+    }
+
+    fn parse_simple_command(&mut self, terminator: Terminator) -> Result<SimpleCommand, String> {
+        let start = self.offset();
+        let mut assignments = Vec::new();
+        let mut words: Vec<Word> = Vec::new();
+        let mut redirects = Vec::new();
+
+        loop {
+            self.skip_blanks();
+
+            let Some(c) = self.peek() else { break };
+
+            if let Some(redirect) = self.try_parse_redirect()? {
+                redirects.push(redirect);
+                continue;
+            }
+
+            let is_process_substitution = (c == '<' || c == '>') && self.peek_at(1) == Some('(');
+            if !is_process_substitution && matches!(c, '|' | '&' | ';' | '\n' | ')' | '(' | '<' | '>') {
+                break;
+            }
+            if c == '#' {
+                self.skip_comment();
+                break;
+            }
+            if c == '}' && terminator == Terminator::Brace && words.is_empty() && self.at_reserved('}') {
+                break;
+            }
+
+            let word = self.parse_word()?;
+            if words.is_empty() && is_assignment(&word) {
+                assignments.push(word);
+            } else {
+                words.push(word);
+            }
+        }
+
+        let end = self.offset();
+        Ok(SimpleCommand {
+            assignments,
+            words,
+            redirects,
+            span: start..end.max(start),
+        })
+    }
+
+    /// 현재 위치가 리다이렉션이면 파싱 (`2>`, `>>`, `&>`, `<<EOF` ...)
+    fn try_parse_redirect(&mut self) -> Result<Option<Redirect>, String> {
+        let start_pos = self.pos;
+        let start = self.offset();
+
+        // fd 번호 (`2>`)
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        const OPERATORS: &[&str] = &["&>>", "&>", "<<<", "<<-", "<<", ">>", ">|", ">&", "<&", "<>", ">", "<"];
+        let Some(op) = OPERATORS.iter().find(|op| self.starts_with(op)) else {
+            self.pos = start_pos;
+            return Ok(None);
+        };
+
+        // 프로세스 치환(`<(`)은 리다이렉션이 아님
+        if (*op == "<" || *op == ">") && self.peek_at(1) == Some('(') {
+            self.pos = start_pos;
+            return Ok(None);
+        }
+        // `&`로 시작하는 연산자는 fd 번호와 함께 쓰지 않음
+        if op.starts_with('&') && self.pos != start_pos {
+            self.pos = start_pos;
+            return Ok(None);
+        }
+
+        self.pos += op.len();
+        self.skip_blanks();
+
+        if self.peek().is_none_or(|c| matches!(c, '\n' | ';' | '|' | '&' | ')')) {
+            return Err(format!("missing redirection target at offset {}", start));
+        }
+
+        let target = self.parse_word()?;
+        let mut body = None;
+        if *op == "<<" || *op == "<<-" {
+            body = Some(self.peek_heredoc_body(&target.text, *op == "<<-"));
+            self.pending_heredocs.push((target.text.clone(), *op == "<<-"));
+        }
+
+        Ok(Some(Redirect {
+            op: op.to_string(),
+            span: start..target.span.end,
+            target,
+            body,
+        }))
+    }
+
+    /// 단어 하나 파싱 (따옴표, 이스케이프, 치환 처리)
+    fn parse_word(&mut self) -> Result<Word, String> {
+        let start = self.offset();
+        let mut text = String::new();
+        let mut quoted = false;
+        let mut substitutions = Vec::new();
+
+        // 프로세스 치환 `<(...)` / `>(...)`
+        if matches!(self.peek(), Some('<') | Some('>')) && self.peek_at(1) == Some('(') {
+            self.pos += 2;
+            substitutions.push(self.parse_list(Terminator::Paren)?);
+            if self.peek() != Some(')') {
+                return Err(format!("unclosed process substitution at offset {}", start));
+            }
+            self.pos += 1;
+            let end = self.offset();
+            return Ok(Word {
+                text: self.slice(start, end),
+                span: start..end,
+                quoted: false,
+                substitutions,
+            });
+        }
+
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' | '\r' | '\n' | '|' | '&' | ';' | '<' | '>' | '(' | ')' => break,
+                '\'' => {
+                    quoted = true;
+                    self.pos += 1;
+                    loop {
+                        match self.peek() {
+                            None => return Err(format!("unclosed single quote at offset {}", start)),
+                            Some('\'') => {
+                                self.pos += 1;
+                                break;
+                            }
+                            Some(inner) => {
+                                text.push(inner);
+                                self.pos += 1;
+                            }
+                        }
+                    }
+                }
+                '"' => {
+                    quoted = true;
+                    self.pos += 1;
+                    loop {
+                        match self.peek() {
+                            None => return Err(format!("unclosed double quote at offset {}", start)),
+                            Some('"') => {
+                                self.pos += 1;
+                                break;
+                            }
+                            Some('\\') => {
+                                self.pos += 1;
+                                match self.peek() {
+                                    Some(escaped @ ('"' | '\\' | '$' | '`')) => {
+                                        text.push(escaped);
+                                        self.pos += 1;
+                                    }
+                                    Some('\n') => self.pos += 1,
+                                    _ => text.push('\\'),
+                                }
+                            }
+                            Some('$') => self.parse_dollar(&mut text, &mut substitutions)?,
+                            Some('`') => self.parse_backtick(&mut text, &mut substitutions)?,
+                            Some(inner) => {
+                                text.push(inner);
+                                self.pos += 1;
+                            }
+                        }
+                    }
+                }
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(escaped) => {
+                            quoted = true;
+                            text.push(escaped);
+                            self.pos += 1;
+                        }
+                        None => text.push('\\'),
+                    }
+                }
+                '$' => self.parse_dollar(&mut text, &mut substitutions)?,
+                '`' => self.parse_backtick(&mut text, &mut substitutions)?,
+                _ => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        let end = self.offset();
+        Ok(Word {
+            text,
+            span: start..end,
+            quoted,
+            substitutions,
+        })
+    }
+
+    /// `$(...)`, `$((...))`, `${...}`, `$var` 처리 (원문을 그대로 text에 남김)
+    fn parse_dollar(&mut self, text: &mut String, substitutions: &mut Vec<List>) -> Result<(), String> {
+        let start = self.offset();
+
+        if self.starts_with("$((") {
+            // 산술 확장: 짝이 맞는 `))`까지 그대로 복사
+            self.pos += 3;
+            let mut depth = 2;
+            while depth > 0 {
+                match self.peek() {
+                    None => return Err(format!("unclosed arithmetic expansion at offset {}", start)),
+                    Some('(') => depth += 1,
+                    Some(')') => depth -= 1,
+                    _ => {}
+                }
+                self.pos += 1;
+            }
+        } else if self.starts_with("$(") {
+            self.pos += 2;
+            substitutions.push(self.parse_list(Terminator::Paren)?);
+            if self.peek() != Some(')') {
+                return Err(format!("unclosed command substitution at offset {}", start));
+            }
+            self.pos += 1;
+        } else if self.starts_with("${") {
+            self.pos += 2;
+            let mut depth = 1;
+            while depth > 0 {
+                match self.peek() {
+                    None => return Err(format!("unclosed parameter expansion at offset {}", start)),
+                    Some('{') => depth += 1,
+                    Some('}') => depth -= 1,
+                    _ => {}
+                }
+                self.pos += 1;
+            }
+        } else {
+            self.pos += 1;
+            while self.peek().is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+                self.pos += 1;
+            }
+            // 특수 변수 ($?, $@, $1 ...)
+            if self.offset() == start + 1 {
+                if let Some(c) = self.peek().filter(|c| "?@*#$!-0123456789".contains(*c)) {
+                    self.pos += c.len_utf8().min(1);
+                }
+            }
+        }
+
+        let end = self.offset();
+        text.push_str(&self.slice(start, end));
+        Ok(())
+    }
+
+    /// 백틱 명령어 치환
+    fn parse_backtick(&mut self, text: &mut String, substitutions: &mut Vec<List>) -> Result<(), String> {
+        let start = self.offset();
+        self.pos += 1;
+        let inner_start = self.pos;
+
+        loop {
+            match self.peek() {
+                None => return Err(format!("unclosed backtick at offset {}", start)),
+                Some('\\') => self.pos += 2,
+                Some('`') => break,
+                Some(_) => self.pos += 1,
+            }
+        }
+
+        let inner_offset = self.chars[inner_start].0;
+        let inner_end = self.offset();
+        let inner_source = self.slice(inner_offset, inner_end);
+        self.pos += 1;
+
+        let mut inner = Parser::new(&inner_source, inner_offset);
+        substitutions.push(inner.parse_list(Terminator::EndOfInput)?);
+
+        text.push_str(&self.slice(start, self.offset()));
+        Ok(())
+    }
+
+    /// 원본 offset 범위의 문자열
+    fn slice(&self, start: usize, end: usize) -> String {
+        let base = self.chars.first().map_or(0, |(i, _)| *i);
+        self.source
+            .get(start - base..end - base)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// `NAME=value` 형식의 할당인지
+fn is_assignment(word: &Word) -> bool {
+    let Some((name, _)) = word.text.split_once('=') else {
+        return false;
+    };
+
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 모든 단순 명령어
+    fn simple_commands(list: &List) -> Vec<&SimpleCommand> {
+        let mut result = Vec::new();
+        list.visit(&mut |command| {
+            if let Command::Simple(simple) = command {
+                result.push(simple);
+            }
+        });
+        result
+    }
+
+    /// 모든 단순 명령어의 단어 목록
+    fn commands(source: &str) -> Vec<Vec<String>> {
+        simple_commands(&parse(source).unwrap())
+            .iter()
+            .map(|c| c.words.iter().map(|w| w.text.clone()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_lists_and_pipelines() {
+        let list = parse("make && make test || echo failed; ps aux | grep node &").unwrap();
+
+        let separators: Vec<Separator> = list.items.iter().map(|i| i.separator).collect();
+        assert_eq!(
+            separators,
+            vec![Separator::And, Separator::Or, Separator::Sequence, Separator::Background]
+        );
+        assert_eq!(list.items[3].pipeline.commands.len(), 2);
+    }
+
+    #[test]
+    fn test_nested_pipelines_are_visited() {
+        let list = parse("echo $(curl -s x | sh); (ls | wc -l)").unwrap();
+        let mut lengths = Vec::new();
+        list.visit_pipelines(&mut |pipeline| lengths.push(pipeline.commands.len()));
+
+        assert_eq!(lengths, vec![1, 2, 1, 2]);
+    }
+
+    #[test]
+    fn test_quoting() {
+        assert_eq!(
+            commands(r#"echo "format disk" 'rm -rf /' a\ b"#),
+            vec![vec!["echo", "format disk", "rm -rf /", "a b"]]
+        );
+    }
+
+    #[test]
+    fn test_substitutions_are_visited() {
+        let cmds = commands("echo $(rm -r -f /tmp/x) `whoami` \"$(date)\"");
+        assert_eq!(cmds[0][0], "echo");
+        assert!(cmds.contains(&vec!["rm".to_string(), "-r".to_string(), "-f".to_string(), "/tmp/x".to_string()]));
+        assert!(cmds.contains(&vec!["whoami".to_string()]));
+        assert!(cmds.contains(&vec!["date".to_string()]));
+    }
+
+    #[test]
+    fn test_subshell_group_and_process_substitution() {
+        let cmds = commands("(cd build && rm -rf out); { echo a; echo b; } > log; diff <(ls a) <(ls b)");
+        let programs: Vec<&str> = cmds.iter().map(|c| c[0].as_str()).collect();
+        assert_eq!(programs, vec!["cd", "rm", "echo", "echo", "diff", "ls", "ls"]);
+    }
+
+    #[test]
+    fn test_redirections() {
+        let list = parse("dd if=img of=out 2>&1 > /dev/sda").unwrap();
+        let command = simple_commands(&list)[0];
+
+        assert_eq!(command.words.len(), 3);
+        assert_eq!(command.redirects.len(), 2);
+        assert_eq!(command.redirects[1].op, ">");
+        assert_eq!(command.redirects[1].target.text, "/dev/sda");
+        assert!(command.redirects[1].is_output());
+    }
+
+    #[test]
+    fn test_heredoc_body_is_skipped() {
+        let cmds = commands("cat <<'EOF' > notes\nrm -rf /\nEOF\necho done");
+        assert_eq!(cmds, vec![vec!["cat"], vec!["echo", "done"]]);
+    }
+
+    #[test]
+    fn test_heredoc_body_is_kept() {
+        let list = parse("cat <<A <<-B\nfirst\nA\n\tsecond\n\tB\necho done").unwrap();
+        let command = simple_commands(&list)[0];
+        assert_eq!(command.redirects[0].body.as_deref(), Some("first\n"));
+        assert_eq!(command.redirects[1].body.as_deref(), Some("second\n"));
+        assert_eq!(commands("cat <<A <<-B\nfirst\nA\n\tsecond\n\tB\necho done")[1], vec!["echo", "done"]);
+
+        let list = parse("cat < notes").unwrap();
+        assert_eq!(simple_commands(&list)[0].redirects[0].body, None);
+    }
+
+    #[test]
+    fn test_assignments_and_function_definition() {
+        let list = parse("LC_ALL=C sort file; f() { f | f & }; f").unwrap();
+
+        let first = simple_commands(&list)[0];
+        assert_eq!(first.assignments[0].text, "LC_ALL=C");
+        assert_eq!(first.words[0].text, "sort");
+
+        assert!(matches!(
+            &list.items[1].pipeline.commands[0],
+            Command::FunctionDef { name, .. } if name == "f"
+        ));
+    }
+
+    #[test]
+    fn test_spans_point_into_source() {
+        let source = "ls && rm -rf build";
+        let list = parse(source).unwrap();
+        let command = simple_commands(&list)[1];
+        assert_eq!(&source[command.span.clone()], "rm -rf build");
+        assert_eq!(&source[command.words[1].span.clone()], "-rf");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("echo 'unclosed").is_err());
+        assert!(parse("(ls").is_err());
+        assert!(parse("echo $(date").is_err());
+    }
+
+    #[test]
+    fn test_case_patterns() {
+        let cmds = commands("case $ext in\n  jpg) convert a b;;\n  *) echo skip;;\nesac");
+        let programs: Vec<&str> = cmds.iter().map(|c| c[0].as_str()).collect();
+        assert_eq!(programs, vec!["case", "jpg", "convert", "*", "echo", "esac"]);
+    }
+}
@@ -1,57 +1,723 @@
 use crate::error::{AskAiError, Result};
+use crate::executor::network;
+use crate::executor::policy::{glob_match, normalize_path, resolve_path, Invocation, Policy};
+use crate::executor::script::{script_lines, ScriptLine};
+use crate::executor::shell::{self, Command, List, Pipeline, Redirect, Word};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
-pub struct CommandValidator;
+/// 리다이렉션으로 덮어쓰면 안 되는 블록 디바이스
+const BLOCK_DEVICES: &[&str] = &[
+    "/dev/sd*", "/dev/hd*", "/dev/vd*", "/dev/xvd*", "/dev/nvme*", "/dev/mmcblk*", "/dev/disk*",
+];
 
-impl CommandValidator {
-    const DANGEROUS_PATTERNS: &'static [&'static str] = &[
-        "rm -rf /",
-        "rm -rf /*",
-        "dd if=/dev/zero",
-        "mkfs",
-        "> /dev/sd",
-        "mv /* ",
-        ":(){ :|:& };:", // Fork bomb
-    ];
-
-    const DANGEROUS_KEYWORDS: &'static [&'static str] =
-        &["rm -rf", "mkfs", "dd if=/dev/zero", "format"];
+/// 다른 명령어를 실행하는 래퍼: (이름, 값을 받는 옵션)
+const WRAPPERS: &[(&str, &[&str])] = &[
+    ("sudo", &["-u", "-g", "-C", "-D", "-h", "-p", "-r", "-t", "-U"]),
+    ("doas", &["-u", "-C"]),
+    ("env", &["-u", "-C", "-S"]),
+    ("nice", &["-n"]),
+    ("ionice", &["-c", "-n", "-p"]),
+    ("stdbuf", &["-i", "-o", "-e"]),
+    ("timeout", &["-s", "-k"]),
+    ("exec", &["-a"]),
+    ("nohup", &[]),
+    ("time", &[]),
+    ("command", &[]),
+    ("busybox", &[]),
+];
+
+/// 뒤에 오는 단어가 실제 명령어인 예약어
+const LEADING_KEYWORDS: &[&str] = &["if", "then", "else", "elif", "do", "while", "until", "!", "{"];
+
+/// 실행할 명령어가 없는 예약어 (반복문 머리, 블록 끝)
+const NON_COMMAND_KEYWORDS: &[&str] = &["fi", "done", "esac", "for", "case", "select", "function", "in", "}"];
+
+/// 한 글자 대시로 긴 옵션을 쓰는 프로그램 (`-delete`를 `-d -e ...`로 쪼개지 않음)
+const SINGLE_DASH_LONG_OPTIONS: &[&str] = &["find", "java", "gcc", "clang", "ffmpeg", "terraform"];
+
+/// 같은 의미의 플래그 정규화: (프로그램, 플래그, 정규화된 플래그)
+const FLAG_ALIASES: &[(&str, &str, &str)] = &[
+    ("rm", "-R", "-r"),
+    ("rm", "--recursive", "-r"),
+    ("rm", "--force", "-f"),
+    ("cp", "-R", "-r"),
+    ("cp", "--recursive", "-r"),
+    ("cp", "--force", "-f"),
+    ("chmod", "--recursive", "-R"),
+    ("chown", "--recursive", "-R"),
+    ("git", "--force", "-f"),
+    ("curl", "--upload-file", "-T"),
+    ("curl", "--data", "-d"),
+    ("curl", "--form", "-F"),
+];
+
+/// 셸 문자열을 인자로 실행하는 프로그램 (`bash -c "..."`)
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh"];
+
+/// 표준 입력으로 받은 코드를 실행하는 프로그램 (`bash <<EOF`, `ssh host <<EOF`), `python*` 포함
+const STDIN_INTERPRETERS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "fish", "ssh"];
+
+/// 규칙 하나가 일치한 결과
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub level: DangerLevel,
+    /// 실행 자체가 차단되는지
+    pub blocked: bool,
+    /// 일치한 규칙 (예: `rm -r`, `redirect to block device`)
+    pub rule: String,
+    /// 문제가 된 명령어 부분
+    pub text: String,
+    /// 위험한 이유
+    pub reason: String,
+    /// 검사한 문자열에서 문제가 된 부분의 위치 (하이라이트용)
+    pub span: Range<usize>,
+    /// 더 안전한 대안
+    pub alternative: Option<String>,
+    /// 규칙이 겨냥한 대상 (Critical 확인 시 사용자가 다시 입력할 값, 예: `../data`)
+    pub target: Option<String>,
+    /// 위험의 종류 (로컬 파괴 명령어와 네트워크 위험을 구분해서 표시)
+    pub category: RiskCategory,
+}
+
+impl Finding {
+    /// 사람이 읽는 한 줄 설명
+    pub fn message(&self) -> String {
+        format!("`{}` {}", self.text, self.reason)
+    }
+}
 
+pub struct CommandValidator {
+    policy: Policy,
+    /// 상대 경로 해석 기준
+    cwd: PathBuf,
+}
+
+impl CommandValidator {
+    /// 기본 정책만 사용하는 검증기
+    #[allow(dead_code)]  // Public API (정책 파일을 무시해야 할 때)
     pub fn new() -> Self {
-        Self
+        Self::with_policy(Policy::builtin())
+    }
+
+    /// 프로젝트/사용자 정책 파일을 포함한 검증기
+    pub fn load() -> Result<Self> {
+        Ok(Self::with_policy(Policy::load()?))
+    }
+
+    pub fn with_policy(policy: Policy) -> Self {
+        Self {
+            policy,
+            cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        }
     }
 
-    pub fn validate(&self, command: &str) -> Result<DangerLevel> {
+    /// 명령어를 검사하여 위험도와 판단 근거를 반환 (차단 대상이면 에러)
+    pub fn validate(&self, command: &str) -> Result<ValidationReport> {
+        let report = self.assess(command);
+
         // 절대 금지 패턴
-        for pattern in Self::DANGEROUS_PATTERNS {
-            if command.contains(pattern) {
-                return Err(AskAiError::DangerousCommand(format!(
-                    "Dangerous pattern detected: {}",
-                    pattern
-                )));
+        if let Some(finding) = report.blocked_by() {
+            return Err(AskAiError::DangerousCommand(format!(
+                "Dangerous pattern detected: {}",
+                finding.message()
+            )));
+        }
+
+        Ok(report)
+    }
+
+    /// 스크립트의 모든 명령어 줄을 검증
+    ///
+    /// 스크립트 전체를 하나의 AST로 파싱하므로 여러 줄에 걸친 블록도 올바르게 검사합니다.
+    /// 금지 패턴이 있는 줄이 하나라도 있으면 줄 번호와 함께 에러를 반환합니다.
+    pub fn validate_script(&self, script: &str) -> Result<Vec<(ScriptLine, DangerLevel)>> {
+        let lines = script_lines(script);
+
+        // 전체를 파싱할 수 없으면 줄 단위로 검사
+        let Ok(findings) = self.findings(script, &self.cwd) else {
+            return lines
+                .into_iter()
+                .map(|line| match self.validate(&line.text) {
+                    Ok(report) => Ok((line, report.level)),
+                    Err(e) => Err(AskAiError::DangerousCommand(format!(
+                        "line {}: {}",
+                        line.line_no, e
+                    ))),
+                })
+                .collect();
+        };
+
+        if let Some(finding) = findings.iter().find(|f| f.blocked) {
+            let error = AskAiError::DangerousCommand(format!(
+                "Dangerous pattern detected: {}",
+                finding.message()
+            ));
+            return Err(AskAiError::DangerousCommand(format!(
+                "line {}: {}",
+                line_number(script, finding.span.start),
+                error
+            )));
+        }
+
+        let mut levels = vec![DangerLevel::Low; lines.len()];
+        for finding in &findings {
+            let line_no = line_number(script, finding.span.start);
+            if let Some(index) = lines.iter().rposition(|line| line.line_no <= line_no) {
+                levels[index] = levels[index].max(finding.level);
             }
         }
 
-        // 위험 키워드 확인
-        let danger_level = if Self::DANGEROUS_KEYWORDS
-            .iter()
-            .any(|k| command.contains(k))
-        {
-            DangerLevel::High
-        } else if command.contains("sudo") {
-            DangerLevel::Medium
-        } else {
-            DangerLevel::Low
+        Ok(lines.into_iter().zip(levels).collect())
+    }
+
+    /// 위험도와 그 판단 근거를 함께 계산 (차단 대상이어도 에러를 반환하지 않음)
+    pub fn assess(&self, command: &str) -> ValidationReport {
+        let findings = self.findings(command, &self.cwd).unwrap_or_else(|e| {
+            vec![Finding {
+                level: DangerLevel::Medium,
+                blocked: false,
+                rule: "unparseable command".to_string(),
+                text: command.trim().to_string(),
+                reason: format!("could not be parsed ({}), review it carefully", e),
+                span: 0..command.len(),
+                alternative: None,
+                target: None,
+                category: RiskCategory::General,
+            }]
+        });
+
+        let mut report = ValidationReport::default();
+        for finding in findings {
+            if !report.findings.iter().any(|f| f.message() == finding.message()) {
+                report.level = report.level.max(finding.level);
+                report.findings.push(finding);
+            }
+        }
+
+        report
+    }
+
+    /// 명령어를 파싱하여 모든 단순 명령어에 규칙 적용
+    ///
+    /// `dir`은 명령어가 시작하는 디렉토리이며, 최상위 목록의 `cd`는 뒤따르는 명령어의 상대 경로 기준을 바꿉니다.
+    fn findings(&self, source: &str, dir: &Path) -> std::result::Result<Vec<Finding>, String> {
+        let list = shell::parse(source)?;
+        let mut findings = Vec::new();
+        let dirs = track_cd(&list, dir);
+
+        list.visit(&mut |command| match command {
+            Command::Simple(simple) => {
+                let dir = dirs
+                    .iter()
+                    .rev()
+                    .find(|(end, _)| *end <= simple.span.start)
+                    .map_or(dir, |(_, dir)| dir.as_path());
+                self.check_words(source, &simple.words, dir, &mut findings);
+                self.check_stdin_scripts(&simple.words, &simple.redirects, dir, &mut findings);
+                Self::check_redirects(source, &simple.redirects, &mut findings);
+            }
+            Command::Subshell(_, redirects) | Command::Group(_, redirects) => {
+                Self::check_redirects(source, redirects, &mut findings);
+            }
+            Command::FunctionDef { name, body } => {
+                if is_fork_bomb(name, body) {
+                    findings.push(Finding {
+                        level: DangerLevel::High,
+                        blocked: true,
+                        rule: "fork bomb".to_string(),
+                        text: name.clone(),
+                        reason: "is a fork bomb that spawns itself until the system hangs".to_string(),
+                        span: 0..source.len(),
+                        alternative: None,
+                        target: None,
+                        category: RiskCategory::General,
+                    });
+                }
+            }
+        });
+
+        // 파이프라인 단위 검사 (셸로 넘기는 코드, 원격 코드 실행, 업로드, 인증 정보)
+        list.visit_pipelines(&mut |pipeline| self.check_piped_script(pipeline, dir, &mut findings));
+        network::check(source, &list, &self.cwd, &mut findings);
+
+        Ok(findings)
+    }
+
+    /// 래퍼(`sudo`, `env` ...)와 예약어를 벗긴 실제 명령어에 규칙 적용
+    fn check_words(&self, source: &str, words: &[Word], dir: &Path, findings: &mut Vec<Finding>) {
+        let Some((rest, privileged)) = strip_wrappers(words) else { return };
+
+        let program = program_name(&rest[0].text);
+        let args = &rest[1..];
+        let span = rest[0].span.start..rest[rest.len() - 1].span.end;
+
+        // `x=rm; $x -rf /`처럼 실행할 프로그램을 실행 전에는 알 수 없음 (인자가 파괴적으로 보이면 High)
+        if rest[0].text.contains('$') || !rest[0].substitutions.is_empty() {
+            let (level, reason) = if looks_destructive(args) {
+                (DangerLevel::High, format!(
+                    "command name is not static (`{}`) and its arguments look destructive",
+                    rest[0].text
+                ))
+            } else {
+                (DangerLevel::Medium, format!("command name is not static (`{}`), so it can't be checked", rest[0].text))
+            };
+            findings.push(Finding {
+                level,
+                blocked: false,
+                rule: "dynamic command".to_string(),
+                text: snippet(source, &span),
+                reason,
+                span: span.clone(),
+                alternative: None,
+                target: None,
+                category: RiskCategory::General,
+            });
+        }
+
+        self.check_nested(source, program, args, dir, findings);
+
+        let invocation = normalize_args(program, args, &self.cwd, dir);
+        if let Some(rule) = self.policy.evaluate(&invocation) {
+            findings.push(Finding {
+                level: rule.level,
+                blocked: rule.blocked,
+                rule: rule.describe(),
+                text: snippet(source, &span),
+                reason: rule.reason.clone(),
+                span: span.clone(),
+                alternative: rule.alternative.clone(),
+                target: rule.target(&invocation),
+                category: rule.category,
+            });
+        }
+
+        if let Some(word) = privileged {
+            findings.push(Finding {
+                level: DangerLevel::Medium,
+                blocked: false,
+                rule: "privileged execution".to_string(),
+                text: word.text.clone(),
+                reason: "runs the command with root privileges".to_string(),
+                span: word.span.clone(),
+                alternative: None,
+                target: None,
+                category: RiskCategory::Privilege,
+            });
+        }
+    }
+
+    /// 인자로 다른 명령어를 실행하는 경우 (`find -exec`, `xargs`, `bash -c`, `eval`)
+    fn check_nested(&self, source: &str, program: &str, args: &[Word], dir: &Path, findings: &mut Vec<Finding>) {
+        match program {
+            "find" => {
+                let mut index = 0;
+                while index < args.len() {
+                    if matches!(args[index].text.as_str(), "-exec" | "-execdir" | "-ok" | "-okdir") {
+                        let inner = &args[index + 1..];
+                        let end = inner
+                            .iter()
+                            .position(|w| w.text == ";" || w.text == "+")
+                            .unwrap_or(inner.len());
+                        self.check_words(source, &inner[..end], dir, findings);
+                        index += end + 1;
+                    }
+                    index += 1;
+                }
+            }
+            "xargs" => {
+                let inner = skip_options(args, &["-I", "-n", "-P", "-L", "-d", "-E", "-s", "-a"]);
+                self.check_words(source, inner, dir, findings);
+            }
+            "eval" if !args.is_empty() => {
+                let script = args.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ");
+                let span = args[0].span.start..args[args.len() - 1].span.end;
+                self.check_inline_script(&script, span, dir, findings);
+            }
+            _ if SHELLS.contains(&program) => {
+                let has_c_flag = args
+                    .iter()
+                    .take_while(|w| w.text.starts_with('-'))
+                    .any(|w| !w.text.starts_with("--") && w.text.contains('c'));
+                if has_c_flag {
+                    if let Some(script) = args.iter().find(|w| !w.text.starts_with('-')) {
+                        self.check_inline_script(&script.text, script.span.clone(), dir, findings);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 셸이나 인터프리터에 입력으로 넘기는 heredoc/here-string을 `sh -c` 인자처럼 검사
+    ///
+    /// `bash <<EOF ... EOF`의 본문은 명령어 목록에 나타나지 않으므로 따로 파싱합니다.
+    fn check_stdin_scripts(&self, words: &[Word], redirects: &[Redirect], dir: &Path, findings: &mut Vec<Finding>) {
+        if !reads_code_from_stdin(words) {
+            return;
+        }
+
+        for redirect in redirects {
+            if let Some(script) = stdin_text(redirect) {
+                self.check_inline_script(script, redirect.span.clone(), dir, findings);
+            }
+        }
+    }
+
+    /// 셸이나 인터프리터로 파이프하는 코드를 `sh -c` 인자처럼 검사 (`echo 'rm -rf /' | sh`)
+    ///
+    /// 바로 앞 단계가 `echo`/`printf`면 그 인자를, `cat <<EOF`면 heredoc 본문을 코드로 봅니다.
+    fn check_piped_script(&self, pipeline: &Pipeline, dir: &Path, findings: &mut Vec<Finding>) {
+        let [.., Command::Simple(upstream), Command::Simple(last)] = pipeline.commands.as_slice() else {
+            return;
+        };
+        if !reads_code_from_stdin(&last.words) {
+            return;
+        }
+        let Some((rest, _)) = strip_wrappers(&upstream.words) else { return };
+
+        let args = &rest[1..];
+        let script = match program_name(&rest[0].text) {
+            "echo" => {
+                let words: Vec<&str> = args
+                    .iter()
+                    .map(|w| w.text.as_str())
+                    .skip_while(|text| matches!(*text, "-n" | "-e" | "-E" | "-ne" | "-en"))
+                    .collect();
+                words.join(" ").replace("\\n", "\n")
+            }
+            "printf" => args
+                .iter()
+                .map(|w| w.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ")
+                .replace("\\n", "\n"),
+            "cat" if args.is_empty() => match upstream.redirects.iter().find_map(stdin_text) {
+                Some(body) => body.to_string(),
+                None => return,
+            },
+            _ => return,
         };
+        if !script.trim().is_empty() {
+            self.check_inline_script(&script, upstream.span.clone(), dir, findings);
+        }
+    }
+
+    /// 문자열로 전달된 셸 코드 검사 (결과의 위치는 해당 인자 전체로 표시)
+    fn check_inline_script(&self, script: &str, span: Range<usize>, dir: &Path, findings: &mut Vec<Finding>) {
+        match self.findings(script, dir) {
+            Ok(inner) => findings.extend(inner.into_iter().map(|finding| Finding {
+                span: span.clone(),
+                ..finding
+            })),
+            Err(e) => findings.push(Finding {
+                level: DangerLevel::Medium,
+                blocked: false,
+                rule: "unparseable command".to_string(),
+                text: script.trim().to_string(),
+                reason: format!("is inline shell code that could not be parsed ({}), review it carefully", e),
+                span,
+                alternative: None,
+                target: None,
+                category: RiskCategory::General,
+            }),
+        }
+    }
+
+    /// 블록 디바이스로 향하는 출력 리다이렉션 검사
+    fn check_redirects(source: &str, redirects: &[Redirect], findings: &mut Vec<Finding>) {
+        for redirect in redirects {
+            let target = &redirect.target.text;
+            if redirect.is_output() && BLOCK_DEVICES.iter().any(|pattern| glob_match(pattern, target)) {
+                findings.push(Finding {
+                    level: DangerLevel::Critical,
+                    blocked: true,
+                    rule: "redirect to block device".to_string(),
+                    text: snippet(source, &redirect.span),
+                    reason: "writes directly to a block device".to_string(),
+                    span: redirect.span.clone(),
+                    alternative: None,
+                    target: Some(target.clone()),
+                    category: RiskCategory::General,
+                });
+            }
+        }
+    }
+}
+
+/// 검증 결과: 전체 위험도와 그 근거
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ValidationReport {
+    /// 가장 높은 finding의 위험도 (없으면 Low)
+    pub level: DangerLevel,
+    /// 일치한 규칙들 (명령어 순서)
+    pub findings: Vec<Finding>,
+}
 
-        Ok(danger_level)
+impl ValidationReport {
+    /// 실행을 막는 finding (있으면 실행 불가)
+    pub fn blocked_by(&self) -> Option<&Finding> {
+        self.findings.iter().find(|f| f.blocked)
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DangerLevel {
+    #[default]
     Low,
     Medium,
     High,
+    /// 되돌릴 수 없고 범위가 넓은 명령어: `--yes`나 자동 승인으로 실행할 수 없고
+    /// 대상 경로나 확인 문구를 직접 입력해야 함
+    Critical,
+}
+
+/// 위험의 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RiskCategory {
+    /// 파일 삭제, 디스크 쓰기 등 로컬에서 일어나는 위험
+    #[default]
+    General,
+    /// 내려받은 코드를 바로 실행 (`curl ... | sh`)
+    RemoteCode,
+    /// 로컬 데이터를 외부로 전송 (`curl -T`, `scp`)
+    Exfiltration,
+    /// 인증 정보 파일 읽기 (`~/.ssh`, `.env`)
+    Credentials,
+    /// 권한 상승 (`sudo`)
+    Privilege,
+}
+
+impl RiskCategory {
+    /// 확인 화면에 붙는 라벨 (일반 위험은 없음)
+    pub fn label(self) -> Option<&'static str> {
+        match self {
+            RiskCategory::General => None,
+            RiskCategory::RemoteCode => Some("remote code"),
+            RiskCategory::Exfiltration => Some("exfiltration"),
+            RiskCategory::Credentials => Some("credentials"),
+            RiskCategory::Privilege => Some("privilege"),
+        }
+    }
+}
+
+/// 래퍼(`sudo`, `env` ...)와 예약어를 벗긴 실제 명령어 단어들
+///
+/// 두 번째 값은 권한 상승 래퍼(`sudo`, `doas`)의 단어입니다.
+/// 실행할 명령어가 없거나 `fi`, `done` 같은 예약어면 `None`을 반환합니다.
+pub(crate) fn strip_wrappers(words: &[Word]) -> Option<(&[Word], Option<&Word>)> {
+    let mut rest = words;
+    let mut privileged: Option<&Word> = None;
+
+    loop {
+        let first = rest.first()?;
+        let program = program_name(&first.text);
+
+        if NON_COMMAND_KEYWORDS.contains(&program) {
+            return None;
+        }
+        if LEADING_KEYWORDS.contains(&program) {
+            rest = &rest[1..];
+            continue;
+        }
+
+        let Some((_, value_options)) = WRAPPERS.iter().find(|(name, _)| *name == program) else {
+            break;
+        };
+
+        if program == "sudo" || program == "doas" {
+            privileged = Some(first);
+        }
+
+        rest = skip_options(&rest[1..], value_options);
+        match program {
+            "env" => {
+                while rest.first().is_some_and(|w| w.text.contains('=') && !w.text.starts_with('=')) {
+                    rest = &rest[1..];
+                }
+            }
+            // 제한 시간 인자
+            "timeout" => rest = rest.get(1..).unwrap_or_default(),
+            _ => {}
+        }
+    }
+
+    Some((rest, privileged))
+}
+
+/// 경로를 뗀 프로그램 이름 (`/usr/bin/rm` -> `rm`)
+pub(crate) fn program_name(word: &str) -> &str {
+    word.rsplit('/').next().filter(|name| !name.is_empty()).unwrap_or(word)
+}
+
+/// 앞쪽 옵션 건너뛰기 (값을 받는 옵션은 값까지)
+fn skip_options<'a>(words: &'a [Word], value_options: &[&str]) -> &'a [Word] {
+    let mut index = 0;
+    while let Some(word) = words.get(index) {
+        if word.text == "--" {
+            return &words[index + 1..];
+        }
+        if !word.text.starts_with('-') || word.text.len() == 1 {
+            break;
+        }
+        index += if value_options.contains(&word.text.as_str()) { 2 } else { 1 };
+    }
+    words.get(index..).unwrap_or_default()
+}
+
+/// 셸이나 인터프리터처럼 표준 입력으로 받은 코드를 실행하는 명령어인지 (`sudo bash`, `python3`)
+fn reads_code_from_stdin(words: &[Word]) -> bool {
+    let Some((rest, _)) = strip_wrappers(words) else { return false };
+    let program = program_name(&rest[0].text);
+    STDIN_INTERPRETERS.contains(&program) || program.starts_with("python")
+}
+
+/// 표준 입력으로 들어가는 heredoc 본문이나 here-string
+fn stdin_text(redirect: &Redirect) -> Option<&str> {
+    match redirect.op.as_str() {
+        "<<" | "<<-" => redirect.body.as_deref(),
+        "<<<" => Some(redirect.target.text.as_str()),
+        _ => None,
+    }
+}
+
+/// 최상위 목록의 `cd`를 따라간 디렉토리: (`cd`가 끝나는 위치, 그 뒤 명령어의 디렉토리)
+///
+/// `cd $DIR`이나 `cd -`처럼 실행 전에 알 수 없는 이동은 무시합니다.
+/// 서브셸 안의 `cd`는 그 서브셸 밖에 영향을 주지 않으므로 따라가지 않습니다.
+fn track_cd(list: &List, start: &Path) -> Vec<(usize, PathBuf)> {
+    let mut dirs: Vec<(usize, PathBuf)> = Vec::new();
+
+    for item in &list.items {
+        let [Command::Simple(simple)] = item.pipeline.commands.as_slice() else { continue };
+        let Some((cd, args)) = simple.words.split_first() else { continue };
+        if cd.text != "cd" {
+            continue;
+        }
+
+        let target = args.iter().find(|w| !w.text.starts_with('-') || w.text == "-");
+        let dynamic = target.is_some_and(|w| {
+            w.text == "-"
+                || !w.substitutions.is_empty()
+                || (w.text.contains('$') && !w.text.starts_with("$HOME") && !w.text.starts_with("${HOME}"))
+        });
+        if dynamic {
+            continue;
+        }
+
+        let current = dirs.last().map_or(start, |(_, dir)| dir.as_path());
+        let dir = normalize_path(&resolve_path(current, target.map_or("~", |w| w.text.as_str())));
+        dirs.push((simple.span.end, dir));
+    }
+    dirs
+}
+
+/// 실행할 프로그램을 모를 때 인자가 파괴적으로 보이는지 (`-rf`, `/`, 디스크 장치)
+fn looks_destructive(args: &[Word]) -> bool {
+    args.iter().any(|arg| {
+        let text = arg.text.as_str();
+        let recursive = text.starts_with('-')
+            && !text.starts_with("--")
+            && text.len() > 1
+            && text[1..].chars().all(|c| c.is_ascii_alphabetic())
+            && text.contains(['r', 'R']);
+        let device = text.strip_prefix("of=").unwrap_or(text);
+
+        recursive
+            || matches!(text, "--recursive" | "--force" | "--no-preserve-root" | "/" | "/*" | "~" | "~/" | "$HOME")
+            || BLOCK_DEVICES.iter().any(|pattern| glob_match(pattern, device))
+    })
+}
+
+/// 인자를 정규화된 플래그와 위치 인자로 분리
+///
+/// `-rf`는 `-r`, `-f`로 나누고, `--recursive` 같은 긴 옵션은 별칭으로 통일합니다.
+/// 옵션이 값을 받는지는 알 수 없으므로 단독 플래그 바로 뒤의 단어는 플래그 값이면서 위치 인자로도 취급합니다.
+fn normalize_args(program: &str, args: &[Word], cwd: &Path, dir: &Path) -> Invocation {
+    let single_dash_long = SINGLE_DASH_LONG_OPTIONS.contains(&program);
+    let mut flags: Vec<(String, Option<String>)> = Vec::new();
+    let mut positionals = Vec::new();
+    let mut options_ended = false;
+
+    for (index, arg) in args.iter().enumerate() {
+        let text = arg.text.as_str();
+        let next_value = args
+            .get(index + 1)
+            .map(|w| w.text.as_str())
+            .filter(|next| !next.starts_with('-'))
+            .map(str::to_string);
+
+        if options_ended || !text.starts_with('-') || text.len() == 1 {
+            positionals.push(normalize_target(text));
+        } else if text == "--" {
+            options_ended = true;
+        } else if text.starts_with("--") || single_dash_long {
+            let (name, value) = match text.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (text, next_value),
+            };
+            flags.push((flag_alias(program, name), value));
+        } else if text.len() == 2 {
+            flags.push((flag_alias(program, text), next_value));
+        } else {
+            for c in text[1..].chars() {
+                flags.push((flag_alias(program, &format!("-{}", c)), None));
+            }
+        }
+    }
+
+    Invocation {
+        program: program.to_string(),
+        flags,
+        args: positionals,
+        cwd: cwd.to_path_buf(),
+        dir: dir.to_path_buf(),
+    }
+}
+
+fn flag_alias(program: &str, flag: &str) -> String {
+    FLAG_ALIASES
+        .iter()
+        .find(|(p, from, _)| *p == program && *from == flag)
+        .map_or(flag, |(_, _, to)| to)
+        .to_string()
+}
+
+/// 경로 인자 정규화 (`/*`, `/etc/` -> `/`, `/etc`)
+pub(crate) fn normalize_target(target: &str) -> String {
+    let trimmed = target.strip_suffix('*').filter(|t| t.ends_with('/')).unwrap_or(target);
+    let trimmed = trimmed.trim_end_matches('/');
+
+    if trimmed.is_empty() && target.starts_with('/') {
+        "/".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// 함수가 자기 자신을 두 번 이상 호출하면 fork bomb (`:(){ :|:& };:`)
+fn is_fork_bomb(name: &str, body: &Command) -> bool {
+    let mut self_calls = 0;
+    body.visit(&mut |command| {
+        if let Command::Simple(simple) = command {
+            if simple.words.first().is_some_and(|w| w.text == name) {
+                self_calls += 1;
+            }
+        }
+    });
+    self_calls >= 2
+}
+
+pub(crate) fn snippet(source: &str, span: &Range<usize>) -> String {
+    source.get(span.clone()).unwrap_or(source).trim().to_string()
+}
+
+/// offset이 속한 줄 번호 (1부터)
+fn line_number(source: &str, offset: usize) -> usize {
+    source[..offset.min(source.len())].matches('\n').count() + 1
 }
 
 #[cfg(test)]
@@ -75,11 +741,265 @@ mod tests {
     fn test_danger_levels() {
         let validator = CommandValidator::new();
 
-        assert_eq!(validator.validate("ls").unwrap(), DangerLevel::Low);
+        assert_eq!(validator.validate("ls").unwrap().level, DangerLevel::Low);
+
+        assert_eq!(
+            validator.validate("sudo apt update").unwrap().level,
+            DangerLevel::Medium
+        );
+    }
+
+    #[test]
+    fn test_validate_script() {
+        let validator = CommandValidator::new();
+
+        let script = "#!/usr/bin/env bash\nfor f in *.jpeg; do\n  mv \"$f\" \"${f%.jpeg}.jpg\"\ndone\nsudo systemctl restart nginx";
+        let lines = validator.validate_script(script).unwrap();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines.iter().map(|(_, level)| *level).max(), Some(DangerLevel::Medium));
+
+        let error = validator.validate_script("echo start\nrm -rf /").unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+
+        // 인터프리터에 넘기는 heredoc 본문도 검사 (줄 단위로 검사하는 경우 포함)
+        let error = validator.validate_script("echo start\nbash <<EOF\nrm -rf ~\nEOF").unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+        let error = validator.validate_script("echo 'unclosed\nbash <<EOF\nrm -rf ~\nEOF").unwrap_err();
+        assert!(error.to_string().contains("line 2"));
+
+        let lines = validator.validate_script("bash <<EOF\nrm -r /tmp/cache\nEOF\necho done").unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].1, DangerLevel::Critical);
+    }
+
+    #[test]
+    fn test_validate_script_multiline_blocks() {
+        let validator = CommandValidator::new();
+
+        let script = "cleanup() {\n  rm -rf \"$tmp\"\n}\ncase $1 in\n  all) cleanup;;\nesac";
+        let lines = validator.validate_script(script).unwrap();
+        assert_eq!(lines[1].1, DangerLevel::High);
+        assert_eq!(lines[0].1, DangerLevel::Low);
+
+        let error = validator.validate_script("main() {\n  echo hi\n  rm -rf /\n}").unwrap_err();
+        assert!(error.to_string().contains("line 3"));
+    }
+
+    #[test]
+    fn test_assess_reasons() {
+        let validator = CommandValidator::new();
+
+        let report = validator.assess("sudo rm -rf build");
+        assert_eq!(report.level, DangerLevel::High);
+        assert!(report.blocked_by().is_none());
+        assert_eq!(report.findings.len(), 2);
+        assert!(report.findings[0].message().contains("rm -rf"));
+        assert!(report.findings[1].message().contains("sudo"));
+
+        let report = validator.assess("rm -rf /");
+        assert!(report.blocked_by().unwrap().message().contains("rm -rf /"));
+
+        assert!(validator.assess("ls -la").findings.is_empty());
+    }
+
+    #[test]
+    fn test_normalized_flags_are_blocked() {
+        let validator = CommandValidator::new();
+
+        assert!(validator.validate("rm -r -f /").is_err());
+        assert!(validator.validate("rm --recursive --force /*").is_err());
+        assert!(validator.validate("rm -fR /etc/").is_err());
+        assert!(validator.validate("/bin/rm -rf ~").is_err());
+        assert!(validator.validate("find / -delete").is_err());
+        assert!(validator.validate("mkfs.ext4 /dev/sdb1").is_err());
+    }
+
+    #[test]
+    fn test_heredoc_fed_to_interpreter_is_checked() {
+        let validator = CommandValidator::new();
+
+        assert!(validator.validate("bash <<EOF\nrm -rf ~\nEOF").is_err());
+        assert!(validator.validate("sudo sh <<-'EOF'\n\tcd /tmp\n\trm -rf /\n\tEOF").is_err());
+        assert!(validator.validate("ssh prod <<EOF\nmkfs.ext4 /dev/sdb1\nEOF").is_err());
+        assert!(validator.validate("bash <<< 'rm -rf ~'").is_err());
+        assert_eq!(
+            validator.validate("python3 <<EOF\nrm -r /tmp/cache\nEOF").unwrap().level,
+            DangerLevel::Critical
+        );
+
+        // 인터프리터가 아니면 본문은 데이터
+        assert_eq!(validator.validate("cat <<EOF > notes.md\nrm -rf ~\nEOF").unwrap().level, DangerLevel::Low);
+        assert_eq!(validator.validate("bash <<EOF\nls -la\nEOF").unwrap().level, DangerLevel::Low);
+    }
+
+    #[test]
+    fn test_dynamic_command_name_needs_review() {
+        let validator = CommandValidator::new();
+
+        let report = validator.validate("$EDITOR notes.md").unwrap();
+        assert_eq!(report.level, DangerLevel::Medium);
+        assert!(report.findings[0].reason.contains("command name is not static"));
+
+        // 인자가 파괴적으로 보이면 High
+        let report = validator.validate("x=rm; $x -rf /").unwrap();
+        assert_eq!(report.level, DangerLevel::High);
+        assert!(report.findings[0].reason.contains("command name is not static"));
+        assert_eq!(validator.validate("$(echo rm) -rf /").unwrap().level, DangerLevel::High);
+        assert_eq!(validator.validate("$(echo rm) -rf build").unwrap().level, DangerLevel::High);
+        assert_eq!(validator.validate("$DD if=x of=/dev/sda").unwrap().level, DangerLevel::High);
+        assert_eq!(validator.validate("echo $x").unwrap().level, DangerLevel::Low);
+    }
+
+    #[test]
+    fn test_script_piped_to_interpreter_is_checked() {
+        let validator = CommandValidator::new();
+
+        assert!(validator.validate("echo 'rm -rf /' | sh").is_err());
+        assert!(validator.validate("echo rm -rf ~ | bash").is_err());
+        assert!(validator.validate("printf 'cd /tmp\\nrm -rf /\\n' | sudo bash").is_err());
+        assert!(validator.validate("cat <<EOF | sh\nrm -rf /\nEOF").is_err());
+        assert!(validator.validate("echo 'mkfs.ext4 /dev/sdb1' | ssh prod").is_err());
 
+        // 인터프리터로 가지 않으면 데이터
+        assert_eq!(validator.validate("echo 'rm -rf /' | grep rm").unwrap().level, DangerLevel::Low);
+        assert_eq!(validator.validate("echo 'ls -la' | sh").unwrap().level, DangerLevel::Low);
+    }
+
+    #[test]
+    fn test_disk_erasers_and_busybox() {
+        let validator = CommandValidator::new();
+
+        assert!(validator.validate("shred /dev/sda").is_err());
+        assert!(validator.validate("wipefs -a /dev/nvme0n1").is_err());
+        assert!(validator.validate("blkdiscard /dev/sdb").is_err());
+        assert_eq!(validator.validate("shred -u secrets.txt").unwrap().level, DangerLevel::High);
+
+        // busybox는 애플릿을 실행하는 래퍼
+        assert!(validator.validate("busybox rm -rf /").is_err());
+        assert!(validator.validate("busybox sh -c 'rm -rf ~'").is_err());
+        assert_eq!(validator.validate("busybox ls -la").unwrap().level, DangerLevel::Low);
+    }
+
+    #[test]
+    fn test_cd_changes_path_checks() {
+        let validator = CommandValidator::new();
+
+        assert!(validator.validate("cd / && rm -rf *").is_err());
+        assert!(validator.validate("cd /etc; rm -rf ../usr").is_err());
+
+        let report = validator.validate("cd .. && rm -rf data").unwrap();
+        assert_eq!(report.level, DangerLevel::Critical);
+        assert_eq!(validator.validate("cd ~/old && rm -rf *").unwrap().level, DangerLevel::Critical);
+        let parent = std::env::current_dir().unwrap().parent().unwrap().join("data");
         assert_eq!(
-            validator.validate("sudo apt update").unwrap(),
+            report.findings.iter().find_map(|f| f.target.clone()),
+            Some(parent.to_string_lossy().to_string())
+        );
+
+        // 현재 디렉토리 안에서의 이동은 그대로 High, 경로가 아닌 인자는 영향 없음
+        assert_eq!(validator.validate("cd build && rm -rf *").unwrap().level, DangerLevel::High);
+        assert_eq!(validator.validate("cd /tmp && git push -f origin main").unwrap().level, DangerLevel::Critical);
+
+        // 알 수 없는 이동과 서브셸 안의 이동은 따라가지 않음
+        assert_eq!(validator.validate("cd \"$DIR\" && rm -rf build").unwrap().level, DangerLevel::High);
+        assert_eq!(validator.validate("(cd /); rm -rf build").unwrap().level, DangerLevel::High);
+    }
+
+    #[test]
+    fn test_nested_commands_are_checked() {
+        let validator = CommandValidator::new();
+
+        assert!(validator.validate("ls && rm -rf /").is_err());
+        assert!(validator.validate("echo $(rm -rf /)").is_err());
+        assert!(validator.validate("(cd /tmp; rm -rf /)").is_err());
+        assert!(validator.validate("sudo -u root rm -rf /").is_err());
+        assert!(validator.validate("bash -c 'rm -rf /'").is_err());
+        assert!(validator.validate("cat image.iso > /dev/sda").is_err());
+        assert!(validator.validate(":(){ :|:& };:").is_err());
+
+        assert_eq!(
+            validator.validate("find . -name '*.o' -exec rm -f {} +").unwrap().level,
             DangerLevel::Medium
         );
+        assert_eq!(validator.validate("ls | xargs rm -rf").unwrap().level, DangerLevel::High);
+    }
+
+    #[test]
+    fn test_quoted_text_is_not_a_command() {
+        let validator = CommandValidator::new();
+
+        assert_eq!(validator.validate("echo \"format\"").unwrap().level, DangerLevel::Low);
+        assert_eq!(validator.validate("grep -r 'rm -rf /' docs").unwrap().level, DangerLevel::Low);
+        assert_eq!(validator.validate("git commit -m 'use sudo less'").unwrap().level, DangerLevel::Low);
+        // 스왑 파일 생성처럼 흔한 dd는 위험하지만 차단하지 않음
+        assert_eq!(
+            validator.validate("dd if=/dev/zero of=swapfile bs=1M count=1024").unwrap().level,
+            DangerLevel::High
+        );
+    }
+
+    #[test]
+    fn test_critical_commands() {
+        let validator = CommandValidator::new();
+        let critical = |command: &str| validator.validate(command).unwrap().level == DangerLevel::Critical;
+
+        assert!(critical("rm -rf ../shared-data"));
+        assert!(critical("rm -r /tmp/build-cache"));
+        assert!(critical("chmod -R 777 /"));
+        assert!(critical("git push --force origin main"));
+        assert!(critical("git push origin +master"));
+
+        assert!(!critical("rm -rf ./build"));
+        assert!(!critical("chmod -R 755 ."));
+        assert!(!critical("git push -f origin feature/login"));
+        assert!(!critical("git push origin main"));
+
+        let report = validator.assess("rm -r notes /tmp/build-cache");
+        assert_eq!(report.findings[0].target.as_deref(), Some("/tmp/build-cache"));
+
+        // 디스크 장치 쓰기는 Critical 확인으로도 실행할 수 없음
+        assert!(validator.validate("sudo dd if=ubuntu.iso of=/dev/sdb bs=4M status=progress").is_err());
+        assert!(validator.validate("cat ubuntu.iso > /dev/nvme0n1").is_err());
+    }
+
+    #[test]
+    fn test_custom_policy() {
+        let mut policy = Policy::parse(
+            r#"
+            [[rules]]
+            command = "kubectl"
+            args = ["delete"]
+            flags = ["--context=prod*"]
+            level = "high"
+            blocked = true
+            reason = "deletes resources in a production cluster"
+
+            [[rules]]
+            command = "terraform"
+            args = ["apply"]
+            unless_args = ["*"]
+            level = "high"
+            blocked = true
+            reason = "applies changes without a reviewed plan file"
+            "#,
+        )
+        .unwrap();
+        policy.rules.extend(Policy::builtin().rules);
+        let validator = CommandValidator::with_policy(policy);
+
+        assert!(validator.validate("kubectl --context prod-eu delete pod web").is_err());
+        assert!(validator.validate("kubectl delete pod web --context=production").is_err());
+        assert!(validator.validate("kubectl --context staging delete pod web").is_ok());
+        assert!(validator.validate("terraform apply -auto-approve").is_err());
+        assert!(validator.validate("terraform apply release.tfplan").is_ok());
+        // 기본 정책도 그대로 적용
+        assert!(validator.validate("rm -rf /").is_err());
+    }
+
+    #[test]
+    fn test_unparseable_command_needs_review() {
+        let report = CommandValidator::new().assess("echo 'unclosed");
+        assert_eq!(report.level, DangerLevel::Medium);
+        assert!(report.findings[0].reason.contains("could not be parsed"));
     }
 }
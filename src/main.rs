@@ -1,4 +1,3 @@
-use clap::Parser;
 use colored::*;
 
 mod cli;
@@ -12,12 +11,14 @@ mod cache;
 mod daemon;
 mod commands;
 mod plugin;
+mod audit;
 
-use cli::Cli;
+use audit::{Approval, AuditRecord, Outcome};
+use cli::{Cli, Commands};
 use error::Result;
-use ai::{factory::ProviderFactory, history::{CommandHistory, HistoryStore}};
-use executor::CommandValidator;
-use ui::{ConfirmPrompt, create_spinner};
+use ai::{CommandFailure, factory::ProviderFactory, history::{CommandHistory, HistoryStore}, response_processor::CommandDetails};
+use executor::{CommandValidator, DangerLevel};
+use ui::{ConfirmChoice, ConfirmPrompt, create_spinner};
 use chrono::Utc;
 use config::Config;
 use cache::ResponseCache;
@@ -34,7 +35,7 @@ static RESPONSE_CACHE: Lazy<Mutex<ResponseCache>> = Lazy::new(|| {
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse_args();
 
     // --clear-cache 옵션 처리 (우선 처리)
     if cli.clear_cache {
@@ -71,6 +72,16 @@ async fn main() -> Result<()> {
         return commands::check_daemon_status().await;
     }
 
+    // `askai audit verify|show` 처리 (감사 로그 검사와 조회)
+    if let Some(Commands::Audit { action }) = &cli.command {
+        return commands::execute_audit_command(action);
+    }
+
+    // `askai hook <shell>` 처리 (askai fix용 셸 hook 출력)
+    if let Some(Commands::Hook { shell }) = &cli.command {
+        return commands::execute_hook_command(*shell);
+    }
+
     // 설정 파일 로드 (없으면 기본값 사용)
     let config = Config::load().unwrap_or_default();
 
@@ -81,6 +92,25 @@ async fn main() -> Result<()> {
         eprintln!("{} {:?}", "DEBUG:".yellow(), cli);
     }
 
+    // `askai explain '<command>'` 처리 (생성 없이 설명만)
+    if let Some(Commands::Explain { command }) = &cli.command {
+        return commands::execute_explain_command(&cli, &config, &command.join(" ")).await;
+    }
+
+    // `askai chat` 처리 (대화형 모드)
+    if let Some(Commands::Chat) = &cli.command {
+        return commands::execute_chat_command(&cli, &config).await;
+    }
+
+    // `askai fix`: 마지막으로 실패한 명령어를 프롬프트로 사용 (생성 단계에서 복구 프롬프트로 보냄)
+    let last_failure = match &cli.command {
+        Some(Commands::Fix { rerun }) => Some(commands::load_last_failure(&cli, *rerun).await?),
+        _ => None,
+    };
+    if let Some(failure) = &last_failure {
+        cli.prompt = vec![format!("fix `{}`", failure.command)];
+    }
+
     // --batch 모드 처리
     if cli.batch {
         return commands::execute_batch_mode(&cli, &config, &RESPONSE_CACHE).await;
@@ -97,13 +127,48 @@ async fn main() -> Result<()> {
         eprintln!("{} {}", "DEBUG Context:".yellow(), ctx);
     }
 
+    // 2-1. --show-context: provider로 보낼 컨텍스트만 출력하고 종료
+    if cli.show_context {
+        eprintln!("{} Context sent to the provider with the prompt:", "[i]".cyan());
+        println!("{}", ctx);
+
+        let summary = context::redaction_summary(&ctx);
+        if summary.is_empty() {
+            eprintln!("{} No secrets detected.", "[OK]".green());
+        } else {
+            let found: Vec<String> = summary.iter().map(|(name, count)| format!("{} ({})", name, count)).collect();
+            eprintln!("{} Redacted: {}", "[v]".green(), found.join(", "));
+        }
+        return Ok(());
+    }
+
+    // --script 모드 처리 (여러 줄 스크립트 생성)
+    if cli.script {
+        return commands::execute_script_mode(&cli, &config, &ctx).await;
+    }
+
+    // --plan 모드 처리 (의존성이 있는 여러 작업으로 나누어 실행)
+    if cli.plan {
+        return commands::execute_plan_mode(&cli, &config, &ctx).await;
+    }
+
+    // --consensus 모드 처리 (여러 provider 결과 비교)
+    if cli.consensus {
+        return commands::execute_consensus_mode(&cli, &config, &ctx).await;
+    }
+
+    // --candidates 모드 처리 (여러 후보 중 선택)
+    if let Some(count) = cli.candidates.filter(|n| *n > 1) {
+        return commands::execute_candidates_mode(&cli, &config, &ctx, count).await;
+    }
+
     // 3. AI provider 선택 및 명령어 생성
     if cli.debug && !cli.quiet {
         eprintln!("{} {}", "DEBUG Provider:".yellow(), provider_name);
     }
 
     // 3-1. Daemon 모드 또는 일반 모드로 명령어 생성
-    let command = if cli.daemon {
+    let command = if cli.daemon && last_failure.is_none() {
         // Daemon 모드: 데몬 서버에 요청
         use daemon::protocol::{DaemonRequest, DaemonResponse};
         use daemon::server::DaemonClient;
@@ -163,12 +228,24 @@ async fn main() -> Result<()> {
         String::new() // 일반 모드
     };
 
+    // 실제로 명령어를 생성한 provider (fallback chain에서 바뀔 수 있음)
+    let mut answered_by = provider_name.to_string();
+    // AI가 함께 돌려준 부가 정보 (캐시/데몬 응답에는 없음)
+    let mut details = CommandDetails::default();
+
     // Daemon 모드에서 명령어를 얻지 못했거나 일반 모드인 경우
     let command = if !cli.daemon || command.is_empty() {
-        let provider = ProviderFactory::create(provider_name)?;
-
-        // 캐시 확인 (--no-cache 플래그가 없으면)
-        if !cli.no_cache {
+        let provider = ProviderFactory::create_with_fallback(provider_name, &config)?;
+
+        if let Some(failure) = &last_failure {
+            // askai fix: 실패한 명령어와 에러로 고친 명령어 생성 (캐시 미사용)
+            let status = format!("Asking {} provider for a fix...", provider.name());
+            let generated = commands::ask_for_fix(&cli, provider.as_ref(), &ctx, failure, &status).await?;
+            details = generated.details;
+            answered_by = provider.answered_by();
+            generated.command
+        } else if !cli.no_cache {
+            // 캐시 확인 (--no-cache 플래그가 없으면)
             let mut cache = RESPONSE_CACHE.lock().unwrap();
             if let Some(cached_command) = cache.get(&cli.prompt_text(), &ctx) {
                 if !cli.quiet {
@@ -187,11 +264,18 @@ async fn main() -> Result<()> {
                     create_spinner("")  // quiet 모드에서는 빈 spinner
                 };
 
-                let generated_command = provider.generate_command(&cli.prompt_text(), &ctx).await?;
+                let generated = provider.generate_structured(&cli.prompt_text(), &ctx).await?;
+                let generated_command = generated.command;
+                details = generated.details;
+                answered_by = provider.answered_by();
 
                 spinner.finish_and_clear();
                 if !cli.quiet {
-                    eprintln!("{} Command generation complete!", "[v]".green());
+                    if answered_by != provider.name() {
+                        eprintln!("{} Command generation complete! (fallback: {})", "[v]".green(), answered_by);
+                    } else {
+                        eprintln!("{} Command generation complete!", "[v]".green());
+                    }
                 }
 
                 // 캐시에 저장
@@ -211,58 +295,236 @@ async fn main() -> Result<()> {
                 create_spinner("")  // quiet 모드에서는 빈 spinner
             };
 
-            let generated_command = provider.generate_command(&cli.prompt_text(), &ctx).await?;
+            let generated = provider.generate_structured(&cli.prompt_text(), &ctx).await?;
+            details = generated.details;
+            answered_by = provider.answered_by();
 
             spinner.finish_and_clear();
             if !cli.quiet {
-                eprintln!("{} 명령어 생성 완료!", "[v]".green());
+                if answered_by != provider.name() {
+                    eprintln!("{} 명령어 생성 완료! (fallback: {})", "[v]".green(), answered_by);
+                } else {
+                    eprintln!("{} 명령어 생성 완료!", "[v]".green());
+                }
             }
 
-            generated_command
+            generated.command
         }
     } else {
         command // daemon에서 얻은 명령어 사용
     };
 
-    // 4. 안전성 검사
-    let validator = CommandValidator::new();
-    let danger_level = validator.validate(&command)?;
+    // 4. 안전성 검사부터 실행까지
+    // --exec로 실행한 명령어가 실패하거나 확인 단계에서 사용자가 고치면 새 명령어로 반복
+    let validator = CommandValidator::load()?;
+    let mut command = command;
+    let mut failure: Option<CommandFailure> = None;
+    let mut round = 0;
+    // 사용자가 고치기 전의 명령어 (히스토리에 수정 신호로 남김)
+    let mut edited_from: Option<String> = None;
+
+    let exit_code = 'run: loop {
+        // 고친 명령어가 차단되거나 취소되면 원래 명령어의 exit code로 종료
+        let report = match (validator.validate(&command), &failure) {
+            (Ok(report), _) => report,
+            (Err(e), Some(failure)) if edited_from.is_none() => {
+                eprintln!("{} Suggested fix was rejected: {}", "[X]".red(), e);
+                std::process::exit(failure.exit_code);
+            }
+            (Err(e), _) => return Err(e),
+        };
 
-    // 5. 사용자 확인 (--yes 플래그가 없으면)
-    if !cli.yes && !cli.quiet {
-        let prompt = ConfirmPrompt::new();
-        if !prompt.confirm_execution(&command, danger_level)? {
-            eprintln!("{}", "[X] User cancelled.".yellow());
-            std::process::exit(1);  // 사용자 취소는 exit code 1로 종료
+        // 4-1. 명령어 설명 (--explain)
+        if cli.explain && !cli.quiet {
+            let provider = ProviderFactory::create_with_fallback(provider_name, &config)?;
+            commands::show_explanation(provider.as_ref(), &command, &ctx).await;
         }
-    } else if !cli.yes && cli.quiet {
-        // quiet 모드에서는 자동으로 yes로 처리 (위험할 수 있음)
-        // 또는 에러를 반환할 수도 있음
-        // 여기서는 안전을 위해 에러 반환
-        eprintln!("{}", "[X] --yes flag required in quiet mode.".red());
-        std::process::exit(1);  // 에러는 exit code 1로 종료
-    }
 
-    // 6. 명령어를 stdout에 출력 (stderr에는 아무것도 출력하지 않음)
-    // 사용자는 eval $(askai "프롬프트")로 실행하거나 shell function으로 감싸서 사용
-    println!("{}", command);
-
-    // 7. 히스토리 저장 (RAG)
-    let store = HistoryStore::new();
-    let history_entry = CommandHistory {
-        prompt: cli.prompt_text(),
-        command: command.clone(),
-        timestamp: Utc::now(),
-        executed: true,
-        provider: provider_name.to_string(),
-    };
+        // 4-2. 샌드박스 시뮬레이션 (--dry-run): 실제 디렉토리는 바꾸지 않고 결과만 보여줌
+        if cli.dry_run {
+            let prompt = ConfirmPrompt::new();
+            if !cli.quiet {
+                prompt.show_risk_assessment(&command, &report);
+            }
 
-    if let Err(e) = store.add(history_entry) {
-        if cli.debug {
-            eprintln!("{} Failed to save history: {}", "DEBUG:".yellow(), e);
+            // Critical 명령어는 샌드박스 안이라도 대상 경로를 입력해야 시뮬레이션
+            if report.level == DangerLevel::Critical {
+                let refusal = if cli.yes || cli.quiet {
+                    eprintln!("{}", "[X] Critical command requires typed confirmation, even with --dry-run. Run without --yes to confirm it.".red());
+                    Some(Outcome::Refused {
+                        reason: "critical command can't be auto-approved".to_string(),
+                    })
+                } else if !prompt.confirm_critical(&report)? {
+                    eprintln!("{}", "[X] User cancelled.".yellow());
+                    Some(Outcome::Cancelled)
+                } else {
+                    None
+                };
+                if let Some(outcome) = refusal {
+                    audit::log(AuditRecord::new(&cli.prompt_text(), &command, &answered_by, &report, Approval::None, outcome));
+                    std::process::exit(1);
+                }
+            }
+
+            let exit_code = commands::simulate_command(&cli, &command).await?;
+            audit::log(AuditRecord::new(
+                &cli.prompt_text(),
+                &command,
+                &answered_by,
+                &report,
+                Approval::None,
+                Outcome::Simulated { exit_code },
+            ));
+
+            let store = HistoryStore::new();
+            let history_entry = CommandHistory {
+                prompt: cli.prompt_text(),
+                command: command.clone(),
+                timestamp: Utc::now(),
+                executed: false,
+                provider: answered_by,
+                exit_code: None,
+                edited_from: None,
+                details,
+            };
+            if let Err(e) = store.add(history_entry) {
+                if cli.debug {
+                    eprintln!("{} Failed to save history: {}", "DEBUG:".yellow(), e);
+                }
+            }
+            if let Err(e) = RESPONSE_CACHE.lock().unwrap().save_to_disk() {
+                if cli.debug {
+                    eprintln!("{} Failed to save cache: {}", "DEBUG:".yellow(), e);
+                }
+            }
+
+            std::process::exit(exit_code);
         }
-        // 히스토리 저장 실패는 치명적이지 않으므로 계속 진행
-    }
+
+        // 5. 사용자 확인 (--yes 플래그가 없거나 자동 승인 대상이 아니면)
+        let auto_approved = cli.yes
+            || (config.auto_approve_safe_commands && report.level == DangerLevel::Low);
+
+        let approval = if cli.yes {
+            Approval::YesFlag
+        } else if auto_approved {
+            Approval::AutoApprove
+        } else {
+            Approval::Interactive
+        };
+        let audit_record = |approval: Approval, outcome: Outcome| {
+            AuditRecord::new(&cli.prompt_text(), &command, &answered_by, &report, approval, outcome)
+        };
+
+        // Critical 명령어는 --yes나 자동 승인으로 실행할 수 없음
+        if report.level == DangerLevel::Critical && (auto_approved || cli.quiet) {
+            audit::log(audit_record(Approval::None, Outcome::Refused {
+                reason: "critical command can't be auto-approved".to_string(),
+            }));
+            eprintln!("{}", "[X] Critical command requires typed confirmation; refusing to auto-approve. Run without --yes to confirm it.".red());
+            std::process::exit(1);
+        }
+
+        if !auto_approved && !cli.quiet {
+            let prompt = ConfirmPrompt::new();
+            loop {
+                match prompt.confirm_or_explain(&command, &report, &details)? {
+                    ConfirmChoice::Execute => break,
+                    ConfirmChoice::Explain => {
+                        // 설명을 본 뒤 다시 확인
+                        let provider = ProviderFactory::create_with_fallback(provider_name, &config)?;
+                        commands::show_explanation(provider.as_ref(), &command, &ctx).await;
+                    }
+                    choice @ (ConfirmChoice::Edit | ConfirmChoice::EditInEditor) => {
+                        // 고친 명령어는 처음부터 다시 검사하고 확인
+                        let edited = prompt.edit_with(choice, &command)?;
+                        if !edited.is_empty() && edited != command {
+                            edited_from.get_or_insert_with(|| command.clone());
+                            command = edited;
+                            // AI의 설명과 가정은 고친 명령어에 맞지 않음
+                            details = CommandDetails::default();
+                            continue 'run;
+                        }
+                    }
+                    ConfirmChoice::Cancel => {
+                        audit::log(audit_record(Approval::None, Outcome::Cancelled));
+                        eprintln!("{}", "[X] User cancelled.".yellow());
+                        std::process::exit(failure.as_ref().map_or(1, |f| f.exit_code));  // 사용자 취소는 exit code 1로 종료 (복구 중이면 원래 exit code)
+                    }
+                }
+            }
+        } else if !auto_approved && cli.quiet {
+            // quiet 모드에서는 자동으로 yes로 처리 (위험할 수 있음)
+            // 또는 에러를 반환할 수도 있음
+            // 여기서는 안전을 위해 에러 반환
+            audit::log(audit_record(Approval::None, Outcome::Refused {
+                reason: "--yes flag required in quiet mode".to_string(),
+            }));
+            eprintln!("{}", "[X] --yes flag required in quiet mode.".red());
+            std::process::exit(1);  // 에러는 exit code 1로 종료
+        }
+
+        // 6. 명령어를 stdout에 출력하거나 (eval용) --exec이면 직접 실행
+        // cd, export처럼 현재 셸을 바꾸는 명령어는 --exec에서도 eval용으로 넘김
+        let result = commands::execute_or_print(&cli, &command).await?;
+        let exit_code = result.as_ref().map(|result| result.exit_code);
+        audit::log(audit_record(approval, Outcome::from_exit_code(exit_code)));
+
+        // 7. 히스토리 저장 (RAG, 사용자가 고쳤으면 원래 명령어도 함께)
+        // 여러 번 고쳐서 원래 명령어로 돌아왔으면 수정으로 보지 않음
+        let edited_from = edited_from.take().filter(|original| *original != command);
+        let store = HistoryStore::new();
+        let history_entry = CommandHistory {
+            prompt: cli.prompt_text(),
+            command: command.clone(),
+            timestamp: Utc::now(),
+            executed: true,
+            provider: answered_by,
+            exit_code,
+            edited_from: edited_from.clone(),
+            details,
+        };
+
+        if let Err(e) = store.add(history_entry) {
+            if cli.debug {
+                eprintln!("{} Failed to save history: {}", "DEBUG:".yellow(), e);
+            }
+            // 히스토리 저장 실패는 치명적이지 않으므로 계속 진행
+        }
+
+        // 고친 명령어가 성공했거나 사용자가 고쳤으면 다음 번에 바로 쓰도록 캐시 갱신
+        let corrected = edited_from.is_some() && exit_code.unwrap_or(0) == 0;
+        if (failure.is_some() && exit_code == Some(0)) || corrected {
+            RESPONSE_CACHE.lock().unwrap().set(&cli.prompt_text(), &ctx, command.clone());
+        }
+
+        // 8. 실패하면 stderr와 함께 provider에 고친 명령어 요청 (--exec로 직접 실행한 경우에만)
+        let Some(result) = result.filter(|result| result.is_repairable()) else {
+            break exit_code;
+        };
+        if round >= config.repair_rounds {
+            if round > 0 && !cli.quiet {
+                eprintln!("{} Still failing after {} suggested fixes; giving up.", "[X]".red(), round);
+            }
+            break exit_code;
+        }
+        round += 1;
+
+        let next_failure = CommandFailure {
+            command: command.clone(),
+            exit_code: result.exit_code,
+            stderr: result.stderr,
+        };
+        match commands::request_repair(&cli, &config, provider_name, &ctx, &next_failure, round).await {
+            Some((generated, repaired_by)) => {
+                command = generated.command;
+                details = generated.details;
+                answered_by = repaired_by;
+                failure = Some(next_failure);
+            }
+            None => break exit_code,
+        }
+    };
 
     // 캐시를 디스크에 저장
     if let Err(e) = RESPONSE_CACHE.lock().unwrap().save_to_disk() {
@@ -271,5 +533,10 @@ async fn main() -> Result<()> {
         }
     }
 
+    // 직접 실행한 명령어의 exit code를 그대로 반환
+    if let Some(code) = exit_code.filter(|code| *code != 0) {
+        std::process::exit(code);
+    }
+
     Ok(())
 }
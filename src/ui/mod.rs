@@ -1,5 +1,5 @@
 pub mod prompt;
 pub mod progress;
 
-pub use prompt::ConfirmPrompt;
+pub use prompt::{CandidateChoice, ChatAction, ConfirmChoice, ConfirmPrompt};
 pub use progress::{BatchProgressDisplay, create_spinner};
@@ -1,7 +1,67 @@
 use crate::error::{AskAiError, Result};
-use crate::executor::validator::DangerLevel;
+use crate::ai::explanation::CommandExplanation;
+use crate::ai::response_processor::CommandDetails;
+use crate::executor::impact::ImpactPreview;
+use crate::executor::planner::ExecutionPlan;
+use crate::executor::sandbox::{ChangeKind, Simulation};
+use crate::executor::script::ScriptLine;
+use crate::executor::validator::{CommandValidator, DangerLevel, Finding, ValidationReport};
 use colored::*;
-use dialoguer::Confirm;
+use dialoguer::{Confirm, Editor, Input, Select};
+use std::ops::Range;
+
+/// 후보 선택 결과
+#[derive(Debug, Clone, PartialEq)]
+pub enum CandidateChoice {
+    /// 후보 중 하나를 그대로 선택 (인덱스)
+    Selected(usize),
+    /// 후보를 수정하여 사용
+    Edited(String),
+    /// 모두 거부
+    Rejected,
+}
+
+/// 실행 확인 결과
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfirmChoice {
+    /// 실행
+    Execute,
+    /// 실행 전에 명령어 설명 보기
+    Explain,
+    /// 실행 전에 명령어 수정 (미리 채운 입력창)
+    Edit,
+    /// 실행 전에 `$EDITOR`에서 명령어 수정
+    EditInEditor,
+    /// 취소
+    Cancel,
+}
+
+/// `askai chat`에서 한 턴의 명령어에 대한 선택
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChatAction {
+    /// 실행
+    Run,
+    /// 수정 후 다시 선택
+    Edit,
+    /// 설명 보기
+    Explain,
+    /// 클립보드로 복사
+    Copy,
+    /// 아무것도 하지 않고 다음 요청으로
+    Next,
+}
+
+/// 설명 출력 시 토큰 열의 최대 너비
+const MAX_TOKEN_WIDTH: usize = 24;
+
+/// 시뮬레이션 결과에서 출력/에러를 보여줄 최대 줄 수
+const MAX_OUTPUT_LINES: usize = 20;
+
+/// 시뮬레이션 결과에서 보여줄 최대 파일 변경 수
+const MAX_CHANGES: usize = 30;
+
+/// 대상 경로를 알 수 없는 Critical 명령어를 확인할 때 입력할 문구
+const CRITICAL_PHRASE: &str = "I understand";
 
 pub struct ConfirmPrompt;
 
@@ -10,25 +70,206 @@ impl ConfirmPrompt {
         Self
     }
 
-    pub fn confirm_execution(&self, command: &str, danger_level: DangerLevel) -> Result<bool> {
-        // Output to stderr to avoid passing to eval
-        eprintln!("\n{}", "[>] Generated command:".cyan().bold());
-
-        let colored_command = match danger_level {
+    /// 위험도별 색상이 적용된 명령어
+    fn colorize(command: &str, danger_level: DangerLevel) -> ColoredString {
+        match danger_level {
             DangerLevel::Low => command.green(),
             DangerLevel::Medium => command.yellow(),
             DangerLevel::High => command.red().bold(),
-        };
-
-        eprintln!("  {}", colored_command);
+            DangerLevel::Critical => command.white().on_red().bold(),
+        }
+    }
 
-        let (danger_msg, danger_level_str) = match danger_level {
+    /// 위험도 라벨 (레벨 이름, 설명)
+    pub(crate) fn danger_label(danger_level: DangerLevel) -> (ColoredString, &'static str) {
+        match danger_level {
             DangerLevel::Low => ("Low".green(), "[*] Safe"),
             DangerLevel::Medium => ("Medium".yellow(), "[!] Caution"),
             DangerLevel::High => ("High".red().bold(), "[!!!] Very dangerous"),
-        };
+            DangerLevel::Critical => ("Critical".white().on_red().bold(), "[!!!!] Irreversible, typed confirmation required"),
+        }
+    }
+
+    /// 생성된 명령어와 위험도, 판단 근거 출력
+    fn print_command(command: &str, report: &ValidationReport) {
+        // Output to stderr to avoid passing to eval
+        eprintln!("\n{}", "[>] Generated command:".cyan().bold());
+
+        eprintln!("  {}", Self::highlight(command, report));
+
+        let (danger_msg, danger_level_str) = Self::danger_label(report.level);
 
         eprintln!("\n{} {} - {}", "Risk level:".bold(), danger_msg, danger_level_str);
+        Self::print_findings(&report.findings);
+        Self::print_impact(command);
+    }
+
+    /// 문제가 된 부분을 강조한 명령어 (나머지는 위험도 색상)
+    fn highlight(command: &str, report: &ValidationReport) -> String {
+        Self::highlight_segments(command, &report.findings)
+            .into_iter()
+            .map(|(range, offending)| {
+                let part = &command[range];
+                if offending {
+                    part.red().bold().underline().to_string()
+                } else {
+                    Self::colorize(part, report.level).to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// 명령어를 (구간, 문제 여부)로 분할 (겹치는 finding 위치는 합침)
+    fn highlight_segments(command: &str, findings: &[Finding]) -> Vec<(Range<usize>, bool)> {
+        let mut spans: Vec<Range<usize>> = findings
+            .iter()
+            .map(|f| f.span.start.min(command.len())..f.span.end.min(command.len()))
+            .filter(|span| !span.is_empty() && command.is_char_boundary(span.start) && command.is_char_boundary(span.end))
+            .collect();
+        spans.sort_by_key(|span| span.start);
+
+        let mut merged: Vec<Range<usize>> = Vec::new();
+        for span in spans {
+            match merged.last_mut() {
+                Some(last) if span.start <= last.end => last.end = last.end.max(span.end),
+                _ => merged.push(span),
+            }
+        }
+
+        let mut segments = Vec::new();
+        let mut cursor = 0;
+        for span in merged {
+            if cursor < span.start {
+                segments.push((cursor..span.start, false));
+            }
+            cursor = span.end;
+            segments.push((span, true));
+        }
+        if cursor < command.len() {
+            segments.push((cursor..command.len(), false));
+        }
+        segments
+    }
+
+    /// 판단 근거와 더 안전한 대안 출력
+    fn print_findings(findings: &[Finding]) {
+        for finding in findings {
+            // 네트워크 위험은 로컬 파괴 명령어와 구분되도록 종류를 앞에 표시
+            let category = finding
+                .category
+                .label()
+                .map(|label| format!("{} ", format!("[{}]", label).magenta().bold()))
+                .unwrap_or_default();
+            eprintln!("  - {}{} {}", category, finding.message(), format!("[rule: {}]", finding.rule).dimmed());
+            if let Some(alternative) = &finding.alternative {
+                eprintln!("    {} {}", "Safer:".green(), alternative);
+            }
+        }
+    }
+
+    /// 명령어가 건드릴 파일과 디렉토리 출력 (파일을 건드리지 않는 명령어는 생략)
+    fn print_impact(command: &str) {
+        let Ok(cwd) = std::env::current_dir() else { return };
+        let preview = ImpactPreview::analyze(command, &cwd);
+        if preview.is_empty() {
+            return;
+        }
+
+        eprintln!("\n{}", "[i] Impact:".cyan().bold());
+        for impact in &preview.impacts {
+            let argument = format!("`{}`", impact.argument);
+            if impact.is_missing() {
+                eprintln!("  - {} matches nothing", argument.bold());
+                continue;
+            }
+
+            let mut line = format!("  - {} {} {}", argument.bold(), impact.action.verb(), impact.summary());
+            if !impact.samples.is_empty() {
+                line.push_str(&format!(": {}", impact.samples.join(", ")));
+                if impact.matches > impact.samples.len() {
+                    line.push_str(", ...");
+                }
+            }
+            eprintln!("{}", line);
+        }
+        for argument in &preview.unresolved {
+            eprintln!("  - {} can't be previewed (depends on shell expansion)", format!("`{}`", argument).bold());
+        }
+
+        if preview.is_large() {
+            eprintln!(
+                "{} This command touches {} files. Make sure the pattern is what you meant.",
+                "[!]".yellow().bold(),
+                crate::executor::impact::format_count(preview.total_files())
+            );
+        }
+    }
+
+    /// Critical 명령어 확인 시 입력해야 하는 값 (대상 경로를 알면 그 경로)
+    fn critical_phrase(report: &ValidationReport) -> String {
+        report.findings
+            .iter()
+            .filter(|f| f.level == DangerLevel::Critical)
+            .find_map(|f| f.target.clone())
+            .unwrap_or_else(|| CRITICAL_PHRASE.to_string())
+    }
+
+    /// 입력한 값을 받아 Critical 확인 결과로 해석
+    fn ask_critical(expected: &str, allow_explain: bool, allow_edit: bool) -> Result<ConfirmChoice> {
+        eprintln!(
+            "\n{} This command can't be auto-approved. Type {} to run it.",
+            "[!!!!]".red().bold(),
+            format!("`{}`", expected).bold()
+        );
+
+        let mut options = Vec::new();
+        if allow_explain {
+            options.push("e=explain");
+        }
+        if allow_edit {
+            options.push("m=edit, v=editor");
+        }
+        let prompt = if options.is_empty() {
+            format!("Type `{}` to execute", expected)
+        } else {
+            format!("Type `{}` to execute [{}]", expected, options.join(", "))
+        };
+
+        let answer: String = Input::new()
+            .with_prompt(prompt)
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        let choice = match Self::parse_critical_answer(&answer, expected) {
+            ConfirmChoice::Explain if !allow_explain => ConfirmChoice::Cancel,
+            ConfirmChoice::Edit | ConfirmChoice::EditInEditor if !allow_edit => ConfirmChoice::Cancel,
+            choice => choice,
+        };
+        Ok(choice)
+    }
+
+    /// Critical 확인 입력 해석 (기대값과 정확히 일치해야 실행)
+    fn parse_critical_answer(answer: &str, expected: &str) -> ConfirmChoice {
+        let answer = answer.trim();
+        if answer == expected {
+            return ConfirmChoice::Execute;
+        }
+        match answer.to_lowercase().as_str() {
+            "e" | "explain" => ConfirmChoice::Explain,
+            "m" | "edit" => ConfirmChoice::Edit,
+            "v" | "editor" => ConfirmChoice::EditInEditor,
+            _ => ConfirmChoice::Cancel,
+        }
+    }
+
+    pub fn confirm_execution(&self, command: &str, report: &ValidationReport) -> Result<bool> {
+        Self::print_command(command, report);
+
+        if report.level == DangerLevel::Critical {
+            let choice = Self::ask_critical(&Self::critical_phrase(report), false, false)?;
+            return Ok(choice == ConfirmChoice::Execute);
+        }
 
         let result = Confirm::new()
             .with_prompt("Execute this command?")
@@ -38,4 +279,567 @@ impl ConfirmPrompt {
 
         Ok(result)
     }
+
+    /// 실행 / 설명 / 수정 / 취소 중 선택 (설명과 수정은 호출자가 처리)
+    ///
+    /// AI가 돌려준 부가 정보(설명, 가정, 자신감)가 있으면 함께 출력합니다.
+    /// 수정을 고르면 호출자가 `edit_with`로 고친 뒤 다시 검사하고 확인해야 합니다.
+    pub fn confirm_or_explain(
+        &self,
+        command: &str,
+        report: &ValidationReport,
+        details: &CommandDetails,
+    ) -> Result<ConfirmChoice> {
+        Self::print_command(command, report);
+        Self::print_details(details);
+
+        if report.level == DangerLevel::Critical {
+            return Self::ask_critical(&Self::critical_phrase(report), true, true);
+        }
+
+        let answer: String = Input::new()
+            .with_prompt("Execute this command? [y/N/e=explain/m=edit/v=editor]")
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        Ok(Self::parse_confirm_answer(&answer))
+    }
+
+    /// `askai chat`: 실행 / 수정 / 설명 / 복사 / 다음 요청 중 선택
+    ///
+    /// Critical 명령어는 실행을 고르면 `confirm_critical`로 한 번 더 확인해야 합니다.
+    pub fn choose_chat_action(
+        &self,
+        command: &str,
+        report: &ValidationReport,
+        details: &CommandDetails,
+    ) -> Result<ChatAction> {
+        Self::print_command(command, report);
+        Self::print_details(details);
+
+        let answer: String = Input::new()
+            .with_prompt("r=run, e=explain, m=edit, c=copy, or Enter for a new request")
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        Ok(Self::parse_chat_action(&answer))
+    }
+
+    /// Critical 명령어 실행 전 대상 경로(또는 확인 문구) 입력 받기
+    pub fn confirm_critical(&self, report: &ValidationReport) -> Result<bool> {
+        let choice = Self::ask_critical(&Self::critical_phrase(report), false, false)?;
+        Ok(choice == ConfirmChoice::Execute)
+    }
+
+    /// 대화 모드 선택 입력 해석 (알 수 없는 입력은 다음 요청)
+    ///
+    /// 확인 프롬프트와 같은 키를 씁니다 (`e` = 설명, `m` = 수정).
+    fn parse_chat_action(answer: &str) -> ChatAction {
+        match answer.trim().to_lowercase().as_str() {
+            "r" | "run" | "y" | "yes" => ChatAction::Run,
+            "m" | "edit" => ChatAction::Edit,
+            "e" | "explain" => ChatAction::Explain,
+            "c" | "copy" => ChatAction::Copy,
+            _ => ChatAction::Next,
+        }
+    }
+
+    /// 실행 / 수정 / 취소 중 선택 (설명할 provider가 없는 모드용)
+    pub fn confirm_or_edit(&self, command: &str, report: &ValidationReport) -> Result<ConfirmChoice> {
+        Self::print_command(command, report);
+
+        if report.level == DangerLevel::Critical {
+            return Self::ask_critical(&Self::critical_phrase(report), false, true);
+        }
+
+        let answer: String = Input::new()
+            .with_prompt("Execute this command? [y/N/m=edit/v=editor]")
+            .allow_empty(true)
+            .interact_text()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        match Self::parse_confirm_answer(&answer) {
+            ConfirmChoice::Explain => Ok(ConfirmChoice::Cancel),
+            choice => Ok(choice),
+        }
+    }
+
+    /// 실행할 때까지 확인하고, 수정하면 다시 검사 (취소하면 `None`)
+    ///
+    /// 수정한 명령어가 차단되면 에러를 반환합니다.
+    pub fn confirm_with_edits(
+        &self,
+        command: &str,
+        report: ValidationReport,
+        validator: &CommandValidator,
+    ) -> Result<Option<String>> {
+        let mut command = command.to_string();
+        let mut report = report;
+
+        loop {
+            match self.confirm_or_edit(&command, &report)? {
+                ConfirmChoice::Execute => return Ok(Some(command)),
+                choice @ (ConfirmChoice::Edit | ConfirmChoice::EditInEditor) => {
+                    let edited = self.edit_with(choice, &command)?;
+                    if !edited.is_empty() {
+                        report = validator.validate(&edited)?;
+                        command = edited;
+                    }
+                }
+                ConfirmChoice::Explain | ConfirmChoice::Cancel => return Ok(None),
+            }
+        }
+    }
+
+    /// AI 응답의 부가 정보 출력 (없는 항목은 생략)
+    fn print_details(details: &CommandDetails) {
+        if let Some(confidence) = details.confidence {
+            let percent = format!("{:.0}%", confidence * 100.0);
+            let colored = if confidence >= 0.8 {
+                percent.green()
+            } else if confidence >= 0.5 {
+                percent.yellow()
+            } else {
+                percent.red()
+            };
+            eprintln!("{} {}", "AI confidence:".bold(), colored);
+        }
+
+        if details.requires_sudo {
+            eprintln!("{} Requires root privileges (sudo)", "[!]".yellow());
+        }
+
+        if let Some(explanation) = &details.explanation {
+            eprintln!("{} {}", "[i]".cyan(), explanation);
+        }
+
+        for assumption in &details.assumptions {
+            eprintln!("  {} {}", "Assumes:".dimmed(), assumption);
+        }
+    }
+
+    /// 확인 입력 해석 (기본값은 취소)
+    fn parse_confirm_answer(answer: &str) -> ConfirmChoice {
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => ConfirmChoice::Execute,
+            "e" | "explain" => ConfirmChoice::Explain,
+            "m" | "edit" => ConfirmChoice::Edit,
+            "v" | "editor" => ConfirmChoice::EditInEditor,
+            _ => ConfirmChoice::Cancel,
+        }
+    }
+
+    /// 기존 명령어의 위험도와 판단 근거 출력 (`askai explain`용)
+    pub fn show_risk_assessment(&self, command: &str, report: &ValidationReport) {
+        eprintln!("\n{}", "[>] Command:".cyan().bold());
+
+        if report.blocked_by().is_some() {
+            eprintln!("  {}", Self::highlight(command, report));
+            eprintln!(
+                "\n{} {} - [X] askai will never run this command",
+                "Risk level:".bold(),
+                "Blocked".red().bold()
+            );
+        } else {
+            eprintln!("  {}", Self::highlight(command, report));
+            let (danger_msg, danger_level_str) = Self::danger_label(report.level);
+            eprintln!("\n{} {} - {}", "Risk level:".bold(), danger_msg, danger_level_str);
+        }
+
+        Self::print_findings(&report.findings);
+        Self::print_impact(command);
+    }
+
+    /// 샌드박스 시뮬레이션 결과 출력 (출력, 에러, 파일 변경)
+    pub fn show_simulation(&self, simulation: &Simulation) {
+        eprintln!(
+            "\n{} {}",
+            "[i] Simulation".cyan().bold(),
+            format!("({} sandbox, no network)", simulation.mode.name()).dimmed()
+        );
+
+        let exit_code = simulation.exit_code.map_or("killed".to_string(), |code| code.to_string());
+        if simulation.success() {
+            eprintln!("  {} {}", "Exit code:".bold(), exit_code.green());
+        } else {
+            eprintln!("  {} {}", "Exit code:".bold(), exit_code.red());
+        }
+
+        Self::print_output("Output:", &simulation.stdout);
+        Self::print_output("Errors:", &simulation.stderr);
+
+        if simulation.changes.is_empty() {
+            eprintln!("  {} none", "File changes:".bold());
+        } else {
+            eprintln!("  {}", "File changes:".bold());
+            for change in simulation.changes.iter().take(MAX_CHANGES) {
+                let mut path = change.path.display().to_string();
+                if change.is_dir {
+                    path.push('/');
+                }
+                match change.nested {
+                    0 => {}
+                    1 => path.push_str(" (1 entry inside)"),
+                    n => path.push_str(&format!(" ({} entries inside)", n)),
+                }
+
+                match change.kind {
+                    ChangeKind::Created => eprintln!("    {} {}", "+".green().bold(), path),
+                    ChangeKind::Modified => eprintln!("    {} {}", "~".yellow().bold(), path),
+                    ChangeKind::Deleted => eprintln!("    {} {}", "-".red().bold(), path),
+                }
+            }
+            if simulation.changes.len() > MAX_CHANGES {
+                eprintln!("    ... and {} more", simulation.changes.len() - MAX_CHANGES);
+            }
+        }
+
+        eprintln!("{} Nothing was changed on disk.", "[OK]".green());
+    }
+
+    /// 시뮬레이션 출력의 앞부분을 들여쓰기하여 출력
+    fn print_output(label: &str, output: &str) {
+        let lines: Vec<&str> = output.lines().collect();
+        if lines.is_empty() {
+            return;
+        }
+
+        eprintln!("  {}", label.bold());
+        for line in lines.iter().take(MAX_OUTPUT_LINES) {
+            eprintln!("    {} {}", "|".dimmed(), line);
+        }
+        if lines.len() > MAX_OUTPUT_LINES {
+            eprintln!("    ... and {} more lines", lines.len() - MAX_OUTPUT_LINES);
+        }
+    }
+
+    /// 명령어 설명 출력 (요약 + 토큰별 설명)
+    pub fn show_explanation(&self, explanation: &CommandExplanation) {
+        eprintln!("\n{}", "[i] Explanation:".cyan().bold());
+
+        if !explanation.summary.is_empty() {
+            eprintln!("  {}", explanation.summary);
+        }
+
+        if explanation.parts.is_empty() {
+            return;
+        }
+
+        let width = explanation.parts
+            .iter()
+            .map(|part| part.token.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(MAX_TOKEN_WIDTH);
+
+        eprintln!();
+        for part in &explanation.parts {
+            // 색상 코드가 너비 계산에 섞이지 않도록 먼저 정렬
+            let token = format!("{:<width$}", part.token, width = width);
+            eprintln!("  {}  {}", token.yellow(), part.description);
+        }
+    }
+
+    /// 생성된 스크립트를 줄 번호와 함께 출력하고 실행 여부 확인
+    ///
+    /// 위험도가 Low보다 높은 줄은 색으로 강조합니다.
+    pub fn confirm_script(&self, script: &str, checked: &[(ScriptLine, DangerLevel)]) -> Result<bool> {
+        Self::print_script(script, checked);
+
+        if checked.iter().any(|(_, level)| *level == DangerLevel::Critical) {
+            let choice = Self::ask_critical(CRITICAL_PHRASE, false, false)?;
+            return Ok(choice == ConfirmChoice::Execute);
+        }
+
+        let result = Confirm::new()
+            .with_prompt("Use this script?")
+            .default(false)
+            .interact()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        Ok(result)
+    }
+
+    /// 스크립트와 전체 위험도 출력
+    fn print_script(script: &str, checked: &[(ScriptLine, DangerLevel)]) {
+        eprintln!("\n{}", "[>] Generated script:".cyan().bold());
+
+        let width = script.lines().count().to_string().len();
+        for (index, line) in script.lines().enumerate() {
+            let line_no = index + 1;
+            let level = checked
+                .iter()
+                .find(|(l, _)| l.line_no == line_no)
+                .map_or(DangerLevel::Low, |(_, level)| *level);
+
+            let text = match level {
+                DangerLevel::Low => line.normal(),
+                _ => Self::colorize(line, level),
+            };
+            eprintln!("  {} {}", format!("{:>width$}", line_no, width = width).dimmed(), text);
+        }
+
+        let overall = checked
+            .iter()
+            .map(|(_, level)| *level)
+            .max()
+            .unwrap_or(DangerLevel::Low);
+        let (danger_msg, danger_level_str) = Self::danger_label(overall);
+
+        eprintln!(
+            "\n{} {} - {} ({} command line(s) checked)",
+            "Risk level:".bold(),
+            danger_msg,
+            danger_level_str,
+            checked.len()
+        );
+    }
+
+    /// 계획을 단계별 DAG로 출력하고 실행 여부 확인
+    ///
+    /// `reports`는 작업 ID별 검사 결과이며, Critical 작업이 있으면 대상 경로를 입력해야 실행합니다.
+    pub fn confirm_plan(&self, plan: &ExecutionPlan, reports: &[(usize, ValidationReport)]) -> Result<bool> {
+        Self::print_plan(plan, reports);
+
+        if let Some((_, report)) = reports.iter().find(|(_, report)| report.level == DangerLevel::Critical) {
+            let choice = Self::ask_critical(&Self::critical_phrase(report), false, false)?;
+            return Ok(choice == ConfirmChoice::Execute);
+        }
+
+        let result = Confirm::new()
+            .with_prompt("Run this plan?")
+            .default(false)
+            .interact()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        Ok(result)
+    }
+
+    /// 계획만 출력 (`--dry-run`)
+    pub fn show_plan(&self, plan: &ExecutionPlan, reports: &[(usize, ValidationReport)]) {
+        Self::print_plan(plan, reports);
+    }
+
+    /// 단계(동시에 실행할 수 있는 작업 묶음)별 작업, 의존성, 위험도 출력
+    fn print_plan(plan: &ExecutionPlan, reports: &[(usize, ValidationReport)]) {
+        let groups = plan.get_parallel_groups();
+        eprintln!(
+            "\n{} {} tasks in {} stage(s); tasks in the same stage run in parallel",
+            "[>] Plan:".cyan().bold(),
+            plan.task_count(),
+            groups.len()
+        );
+
+        for (index, group) in groups.iter().enumerate() {
+            eprintln!("\n  {}", format!("Stage {}", index + 1).bold());
+
+            for task in group {
+                let report = reports.iter().find(|(id, _)| *id == task.id).map(|(_, report)| report);
+                let level = report.map_or(DangerLevel::Low, |report| report.level);
+
+                let mut header = format!("    [{}] {}", task.id, task.description);
+                if let Some(dir) = &task.working_dir {
+                    header.push_str(&format!("  {}", format!("(in {})", dir).dimmed()));
+                }
+                let deps = plan.dependencies_of(task.id);
+                if !deps.is_empty() {
+                    let deps: Vec<String> = deps.iter().map(|id| id.to_string()).collect();
+                    header.push_str(&format!("  {}", format!("<- after {}", deps.join(", ")).cyan()));
+                }
+                eprintln!("{}", header);
+
+                match report {
+                    Some(report) => eprintln!("        {}", Self::highlight(&task.command, report)),
+                    None => eprintln!("        {}", task.command),
+                }
+                if level > DangerLevel::Low {
+                    let (danger_msg, _) = Self::danger_label(level);
+                    eprintln!("        {} {}", "Risk level:".bold(), danger_msg);
+                    if let Some(report) = report {
+                        Self::print_findings(&report.findings);
+                    }
+                }
+            }
+        }
+
+        let overall = reports
+            .iter()
+            .map(|(_, report)| report.level)
+            .max()
+            .unwrap_or(DangerLevel::Low);
+        let (danger_msg, danger_level_str) = Self::danger_label(overall);
+
+        eprintln!(
+            "\n{} {} - {} ({} command(s) checked)",
+            "Risk level:".bold(),
+            danger_msg,
+            danger_level_str,
+            reports.len()
+        );
+    }
+
+    /// 여러 후보 명령어 중 하나를 선택 (선택 / 수정 / 거부)
+    pub fn select_candidate(&self, candidates: &[(String, DangerLevel)]) -> Result<CandidateChoice> {
+        eprintln!("\n{}", "[>] Generated candidates:".cyan().bold());
+
+        let mut items: Vec<String> = candidates
+            .iter()
+            .map(|(command, level)| {
+                let (danger_msg, _) = Self::danger_label(*level);
+                format!("{}  ({})", Self::colorize(command, *level), danger_msg)
+            })
+            .collect();
+        items.push("[Edit] Modify a candidate...".to_string());
+        items.push("[X] Reject all".to_string());
+
+        let edit_index = candidates.len();
+        let selection = Select::new()
+            .with_prompt("Choose a command")
+            .items(&items)
+            .default(0)
+            .interact()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        if selection < edit_index {
+            return Ok(CandidateChoice::Selected(selection));
+        }
+
+        if selection > edit_index {
+            return Ok(CandidateChoice::Rejected);
+        }
+
+        // 수정할 후보 선택 (후보가 하나면 바로 수정)
+        let base = if candidates.len() == 1 {
+            0
+        } else {
+            let plain: Vec<&str> = candidates.iter().map(|(c, _)| c.as_str()).collect();
+            Select::new()
+                .with_prompt("Which candidate to edit?")
+                .items(&plain)
+                .default(0)
+                .interact()
+                .map_err(|_| AskAiError::UserCancelled)?
+        };
+
+        let edited = self.edit_command(&candidates[base].0)?;
+        Ok(CandidateChoice::Edited(edited))
+    }
+
+    /// 명령어를 미리 채운 입력창에서 수정
+    pub fn edit_command(&self, command: &str) -> Result<String> {
+        let edited: String = Input::new()
+            .with_prompt("Edit command")
+            .with_initial_text(command)
+            .interact_text()
+            .map_err(|_| AskAiError::UserCancelled)?;
+
+        Ok(edited.trim().to_string())
+    }
+
+    /// `$VISUAL`/`$EDITOR`(없으면 vi)에서 명령어 수정 (저장하지 않고 닫으면 그대로)
+    pub fn edit_in_editor(&self, command: &str) -> Result<String> {
+        let edited = Editor::new()
+            .extension(".sh")
+            .edit(command)
+            .map_err(|e| AskAiError::ExecutionError(format!("Could not open editor: {}", e)))?;
+
+        Ok(edited.map_or_else(|| command.to_string(), |edited| edited.trim().to_string()))
+    }
+
+    /// 확인 결과(`Edit`, `EditInEditor`)에 맞는 방식으로 명령어 수정
+    pub fn edit_with(&self, choice: ConfirmChoice, command: &str) -> Result<String> {
+        if choice == ConfirmChoice::EditInEditor {
+            self.edit_in_editor(command)
+        } else {
+            self.edit_command(command)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::CommandValidator;
+
+    #[test]
+    fn test_parse_critical_answer() {
+        assert_eq!(ConfirmPrompt::parse_critical_answer(" /dev/sdb ", "/dev/sdb"), ConfirmChoice::Execute);
+        assert_eq!(ConfirmPrompt::parse_critical_answer("e", "/dev/sdb"), ConfirmChoice::Explain);
+        // y/yes로는 실행되지 않음
+        assert_eq!(ConfirmPrompt::parse_critical_answer("y", "/dev/sdb"), ConfirmChoice::Cancel);
+        assert_eq!(ConfirmPrompt::parse_critical_answer("/dev/sda", "/dev/sdb"), ConfirmChoice::Cancel);
+        assert_eq!(ConfirmPrompt::parse_critical_answer("m", "/dev/sdb"), ConfirmChoice::Edit);
+        assert_eq!(ConfirmPrompt::parse_critical_answer("V", "/dev/sdb"), ConfirmChoice::EditInEditor);
+    }
+
+    #[test]
+    fn test_critical_phrase_uses_target() {
+        let validator = CommandValidator::new();
+
+        let report = validator.assess("rm -rf ../shared-data");
+        assert_eq!(report.level, DangerLevel::Critical);
+        assert_eq!(ConfirmPrompt::critical_phrase(&report), "../shared-data");
+
+        let report = validator.assess("git push --force origin main");
+        assert_eq!(ConfirmPrompt::critical_phrase(&report), "main");
+    }
+
+    #[test]
+    fn test_highlight_segments() {
+        let command = "sudo rm -rf build && ls";
+        let report = CommandValidator::new().assess(command);
+
+        let segments = ConfirmPrompt::highlight_segments(command, &report.findings);
+        let offending: Vec<&str> = segments
+            .iter()
+            .filter(|(_, offending)| *offending)
+            .map(|(range, _)| &command[range.clone()])
+            .collect();
+
+        assert_eq!(offending, vec!["sudo", "rm -rf build"]);
+        // 구간을 이어 붙이면 원래 명령어
+        let joined: String = segments.iter().map(|(range, _)| &command[range.clone()]).collect();
+        assert_eq!(joined, command);
+    }
+
+    #[test]
+    fn test_highlight_segments_merges_overlaps() {
+        let command = "bash -c 'rm -rf build; rm -r out'";
+        let report = CommandValidator::new().assess(command);
+
+        let segments = ConfirmPrompt::highlight_segments(command, &report.findings);
+        assert_eq!(segments.iter().filter(|(_, offending)| *offending).count(), 1);
+    }
+
+    #[test]
+    fn test_parse_confirm_answer() {
+        assert_eq!(ConfirmPrompt::parse_confirm_answer("y"), ConfirmChoice::Execute);
+        assert_eq!(ConfirmPrompt::parse_confirm_answer(" YES "), ConfirmChoice::Execute);
+        assert_eq!(ConfirmPrompt::parse_confirm_answer("e"), ConfirmChoice::Explain);
+        assert_eq!(ConfirmPrompt::parse_confirm_answer("explain"), ConfirmChoice::Explain);
+        assert_eq!(ConfirmPrompt::parse_confirm_answer("m"), ConfirmChoice::Edit);
+        assert_eq!(ConfirmPrompt::parse_confirm_answer("edit"), ConfirmChoice::Edit);
+        assert_eq!(ConfirmPrompt::parse_confirm_answer("v"), ConfirmChoice::EditInEditor);
+        // 빈 입력이나 알 수 없는 입력은 취소 (기본값)
+        assert_eq!(ConfirmPrompt::parse_confirm_answer(""), ConfirmChoice::Cancel);
+        assert_eq!(ConfirmPrompt::parse_confirm_answer("maybe"), ConfirmChoice::Cancel);
+    }
+
+    #[test]
+    fn test_parse_chat_action() {
+        assert_eq!(ConfirmPrompt::parse_chat_action("r"), ChatAction::Run);
+        assert_eq!(ConfirmPrompt::parse_chat_action(" Y "), ChatAction::Run);
+        // 확인 프롬프트와 같은 키: e = 설명, m = 수정
+        assert_eq!(ConfirmPrompt::parse_chat_action("e"), ChatAction::Explain);
+        assert_eq!(ConfirmPrompt::parse_chat_action("explain"), ChatAction::Explain);
+        assert_eq!(ConfirmPrompt::parse_chat_action("m"), ChatAction::Edit);
+        assert_eq!(ConfirmPrompt::parse_chat_action("edit"), ChatAction::Edit);
+        assert_eq!(ConfirmPrompt::parse_chat_action("x"), ChatAction::Next);
+        assert_eq!(ConfirmPrompt::parse_chat_action("c"), ChatAction::Copy);
+        // 빈 입력은 실행하지 않고 다음 요청으로
+        assert_eq!(ConfirmPrompt::parse_chat_action(""), ChatAction::Next);
+        assert_eq!(ConfirmPrompt::parse_chat_action("maybe"), ChatAction::Next);
+    }
 }